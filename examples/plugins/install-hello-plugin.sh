@@ -70,9 +70,9 @@ while read -r request; do
   # Handle different method calls
   case "$method" in
     "initialize")
-      # Just return success for initialization
+      # Return what the plugin supports
       log_debug "Handling initialize"
-      send_success "$id" "true"
+      send_success "$id" '{"protocol_version":2,"methods":["initialize","get_time_entries","shutdown"],"writes":false,"search":false,"suggestions":false}'
       ;;
       
     "get_time_entries")
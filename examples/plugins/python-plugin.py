@@ -41,9 +41,24 @@ def handle_initialize(params, request_id):
     # You could load the config file here
     # config = toml.load(config_path) if os.path.exists(config_path) else {}
     
-    # For this example, we just log the path and return success
+    # For this example, we just log the path and return what the plugin supports
     log_debug(f"Initializing with config path: {config_path}")
-    return {"jsonrpc": "2.0", "result": True, "id": request_id}
+    capabilities = {
+        "protocol_version": 2,
+        "methods": [
+            "initialize",
+            "get_time_entries",
+            "on_time_entry_imported",
+            "on_time_entry_updated",
+            "on_time_entry_deleted",
+            "suggest_descriptions",
+            "shutdown",
+        ],
+        "writes": True,
+        "search": False,
+        "suggestions": True,
+    }
+    return {"jsonrpc": "2.0", "result": capabilities, "id": request_id}
 
 # Function to handle 'get_time_entries' method
 def handle_get_time_entries(params, request_id):
@@ -52,6 +67,7 @@ def handle_get_time_entries(params, request_id):
     end_date_str = params.get("end_date", "")
     
     log_debug(f"Fetching time entries from {start_date_str} to {end_date_str}")
+    send_notification("log", {"level": "debug", "message": f"Fetching entries from {start_date_str}"})
     
     # Create sample entries (in real plugin, fetch from your data source)
     now = datetime.datetime.utcnow()
@@ -59,6 +75,7 @@ def handle_get_time_entries(params, request_id):
     
     # Create 3 sample entries
     for i in range(3):
+        send_notification("progress", {"message": "Creating entries", "current": i + 1, "total": 3})
         entry_start = now + datetime.timedelta(hours=i)
         entry_end = entry_start + datetime.timedelta(hours=1)
         
@@ -79,6 +96,21 @@ def handle_get_time_entries(params, request_id):
     
     return {"jsonrpc": "2.0", "result": time_entries, "id": request_id}
 
+# Function to handle the 'on_time_entry_imported', 'on_time_entry_updated' and
+# 'on_time_entry_deleted' methods, a real plugin could mark the entry as logged
+def handle_time_entry_event(method, params, request_id):
+    moneybird_id = params.get("moneybird_id")
+    entry_id = params.get("entry_id")
+    log_debug(f"{method}: {entry_id} is MoneyBird entry {moneybird_id}")
+    return {"jsonrpc": "2.0", "result": True, "id": request_id}
+
+# Function to handle 'suggest_descriptions' method, a real plugin could suggest issue titles
+def handle_suggest_descriptions(params, request_id):
+    text = params.get("text", "").lower()
+    tasks = [f"Python task #{i+1}" for i in range(3)]
+    suggestions = [task for task in tasks if text in task.lower()]
+    return {"jsonrpc": "2.0", "result": suggestions, "id": request_id}
+
 # Function to handle 'shutdown' method
 def handle_shutdown(params, request_id):
     log_debug("Shutting down plugin")
@@ -88,6 +120,11 @@ def handle_shutdown(params, request_id):
     sys.stdout.flush()
     sys.exit(0)
 
+# Helper function to send a notification to mot, like a line for its log panel
+def send_notification(method, params):
+    print(json.dumps({"jsonrpc": "2.0", "method": method, "params": params}))
+    sys.stdout.flush()
+
 # Helper function to log debug messages
 def log_debug(message):
     # For logging you could write to a file in the plugin directory
@@ -114,6 +151,10 @@ def main():
                 response = handle_initialize(params, request_id)
             elif method == "get_time_entries":
                 response = handle_get_time_entries(params, request_id)
+            elif method in ("on_time_entry_imported", "on_time_entry_updated", "on_time_entry_deleted"):
+                response = handle_time_entry_event(method, params, request_id)
+            elif method == "suggest_descriptions":
+                response = handle_suggest_descriptions(params, request_id)
             elif method == "shutdown":
                 handle_shutdown(params, request_id)
                 # handle_shutdown will exit, so we never reach here
@@ -0,0 +1,419 @@
+use crate::config::AnalysisConfig;
+use crate::datetime;
+use crate::model::TimeEntryForTable;
+use chrono::{DateTime, FixedOffset, NaiveTime};
+use std::collections::HashMap;
+
+pub(crate) type Interval = (DateTime<FixedOffset>, DateTime<FixedOffset>);
+
+/// Something that looks wrong about a listed time entry
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Issue {
+    Overlap(String),   // Id of the MoneyBird entry it overlaps
+    Gap(i64),          // Minutes without entries before it, during working hours
+    Duplicate(String), // Id of the MoneyBird entry with the same time span
+}
+
+impl Issue {
+    /// Badge shown next to the time of the entry
+    pub(crate) fn badge(&self) -> &'static str {
+        match self {
+            Issue::Overlap(_) => "⚠",
+            Issue::Gap(_) => "⋯",
+            Issue::Duplicate(_) => "⧉",
+        }
+    }
+}
+
+/// The issues of the listed entries, by entry id
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Analysis {
+    issues: HashMap<String, Vec<Issue>>,
+}
+
+impl Analysis {
+    pub(crate) fn issues(&self, id: &str) -> &[Issue] {
+        self.issues.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The badges of an entry, one per kind of issue
+    pub(crate) fn badges(&self, id: &str) -> String {
+        let mut badges: Vec<&str> = self.issues(id).iter().map(Issue::badge).collect();
+        badges.dedup();
+        badges.concat()
+    }
+
+    fn add(&mut self, id: &str, issue: Issue) {
+        self.issues.entry(id.to_string()).or_default().push(issue);
+    }
+}
+
+/// Parse the start and end of a listed entry, empty or reversed entries have none
+pub(crate) fn entry_interval(entry: &TimeEntryForTable) -> Option<Interval> {
+    let start = DateTime::parse_from_rfc3339(&entry.started_at).ok()?;
+    let end = DateTime::parse_from_rfc3339(&entry.ended_at).ok()?;
+    (end > start).then_some((start, end))
+}
+
+fn is_moneybird(entry: &TimeEntryForTable) -> bool {
+    entry.source.to_lowercase() == "moneybird"
+}
+
+/// Find overlapping MoneyBird entries of the same user, gaps between them during working
+/// hours, and plugin entries that were already imported with the same time span
+pub(crate) fn analyze(
+    entries: &[TimeEntryForTable],
+    timezone: &str,
+    config: &AnalysisConfig,
+) -> Analysis {
+    let mut analysis = Analysis::default();
+
+    let mut moneybird: Vec<(&TimeEntryForTable, Interval)> = entries
+        .iter()
+        .filter(|entry| is_moneybird(entry))
+        .filter_map(|entry| Some((entry, entry_interval(entry)?)))
+        .collect();
+    moneybird.sort_by_key(|(_, (start, _))| *start);
+
+    for (idx, (entry, (_, end))) in moneybird.iter().enumerate() {
+        let same_user = moneybird[idx + 1..]
+            .iter()
+            .filter(|(other, _)| other.user_id == entry.user_id);
+        for (other, (other_start, _)) in same_user {
+            // Sorted on start, so the later entries can't overlap once one starts after the end
+            if other_start >= end {
+                break;
+            }
+            analysis.add(&entry.id, Issue::Overlap(other.id.clone()));
+            analysis.add(&other.id, Issue::Overlap(entry.id.clone()));
+        }
+    }
+
+    // Gaps since the latest end of the earlier entries of the same user on the same day
+    let mut latest_end: HashMap<Option<&str>, DateTime<FixedOffset>> = HashMap::new();
+    for (entry, (start, end)) in &moneybird {
+        let user = entry.user_id.as_deref();
+        if let Some(minutes) = latest_end
+            .get(&user)
+            .and_then(|previous_end| working_gap(*previous_end, *start, timezone, config))
+        {
+            if minutes > config.gap_minutes as i64 {
+                analysis.add(&entry.id, Issue::Gap(minutes));
+            }
+        }
+        let latest = latest_end.entry(user).or_insert(*end);
+        *latest = (*latest).max(*end);
+    }
+
+    // Linked entries are known imports, they show their own icon
+    let plugin_entries = entries
+        .iter()
+        .filter(|entry| !is_moneybird(entry) && entry.linked_id.is_none());
+    for entry in plugin_entries {
+        let Some(span) = entry_interval(entry) else {
+            continue;
+        };
+        if let Some((imported, _)) = moneybird.iter().find(|(_, other)| *other == span) {
+            analysis.add(&entry.id, Issue::Duplicate(imported.id.clone()));
+        }
+    }
+
+    analysis
+}
+
+/// Minutes between the end of an entry and the start of the next that fall within working
+/// hours, when both are on the same day. Gap detection is off when `gap_minutes` is 0.
+fn working_gap(
+    end: DateTime<FixedOffset>,
+    next_start: DateTime<FixedOffset>,
+    timezone: &str,
+    config: &AnalysisConfig,
+) -> Option<i64> {
+    if config.gap_minutes == 0 {
+        return None;
+    }
+    let day_start = NaiveTime::parse_from_str(&config.day_start, "%H:%M").ok()?;
+    let day_end = NaiveTime::parse_from_str(&config.day_end, "%H:%M").ok()?;
+    let end = datetime::parse_iso_datetime(&end.to_rfc3339(), timezone)?.naive_local();
+    let next_start =
+        datetime::parse_iso_datetime(&next_start.to_rfc3339(), timezone)?.naive_local();
+    if end.date() != next_start.date() || next_start <= end {
+        return None;
+    }
+
+    let from = end.time().max(day_start);
+    let to = next_start.time().min(day_end);
+    (to > from).then(|| (to - from).num_minutes())
+}
+
+/// How to resolve an overlap by trimming: the later entry starts when the earlier one
+/// ends, unless it lies within the earlier one, then the earlier one ends when it starts.
+/// Returns whether the first entry changes, with its new start and end.
+pub(crate) fn trim_overlap(first: Interval, second: Interval) -> Option<(bool, Interval)> {
+    let first_is_earlier = first.0 <= second.0;
+    let ((earlier_start, earlier_end), (later_start, later_end)) = if first_is_earlier {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    if later_start >= earlier_end {
+        return None;
+    }
+
+    if later_end > earlier_end {
+        Some((!first_is_earlier, (earlier_end, later_end)))
+    } else if later_start > earlier_start {
+        Some((first_is_earlier, (earlier_start, later_start)))
+    } else {
+        // Both start at the same time, trimming would leave nothing of one of them
+        None
+    }
+}
+
+/// The start and end of two entries merged into one
+pub(crate) fn merge_intervals(first: Interval, second: Interval) -> Interval {
+    (first.0.min(second.0), first.1.max(second.1))
+}
+
+/// Plugin fragments that belong together with the selected entry: not yet imported, from
+/// the same plugin, client and project, and at most `max_gap_minutes` apart. Returns
+/// their indices by start, a lone entry has nothing to merge with.
+pub(crate) fn adjacent_fragments(
+    entries: &[TimeEntryForTable],
+    selected: usize,
+    max_gap_minutes: u32,
+) -> Vec<usize> {
+    let Some(selected_entry) = entries.get(selected) else {
+        return Vec::new();
+    };
+    let belongs = |entry: &TimeEntryForTable| {
+        !is_moneybird(entry)
+            && entry.linked_id.is_none()
+            && entry.source == selected_entry.source
+            && entry.plugin_name == selected_entry.plugin_name
+            && entry.customer == selected_entry.customer
+            && entry.project == selected_entry.project
+    };
+    if !belongs(selected_entry) {
+        return Vec::new();
+    }
+
+    let mut fragments: Vec<(usize, Interval)> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| belongs(entry))
+        .filter_map(|(idx, entry)| Some((idx, entry_interval(entry)?)))
+        .collect();
+    fragments.sort_by_key(|(_, (start, _))| *start);
+
+    // Chain the fragments that start before the previous ones ended plus the gap
+    let max_gap = chrono::Duration::minutes(max_gap_minutes as i64);
+    let mut chain: Vec<usize> = Vec::new();
+    let mut chain_end: Option<DateTime<FixedOffset>> = None;
+    for (idx, (start, end)) in fragments {
+        if chain_end.is_some_and(|chain_end| start > chain_end + max_gap) {
+            if chain.contains(&selected) {
+                break;
+            }
+            chain.clear();
+            chain_end = None;
+        }
+        chain.push(idx);
+        chain_end = Some(chain_end.map_or(end, |chain_end| chain_end.max(end)));
+    }
+
+    if chain.len() > 1 && chain.contains(&selected) {
+        chain
+    } else {
+        Vec::new()
+    }
+}
+
+/// One entry from the earliest start to the latest end of the fragments, with their
+/// different descriptions joined by the separator
+pub(crate) fn merge_fragments(
+    fragments: &[&TimeEntryForTable],
+    separator: &str,
+) -> Option<TimeEntryForTable> {
+    let first = fragments
+        .iter()
+        .min_by_key(|entry| entry_interval(entry).map(|(start, _)| start))?;
+    let last = fragments
+        .iter()
+        .max_by_key(|entry| entry_interval(entry).map(|(_, end)| end))?;
+
+    let mut descriptions: Vec<&str> = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+    for fragment in fragments {
+        let description = fragment.description.trim();
+        if !description.is_empty() && !descriptions.contains(&description) {
+            descriptions.push(description);
+        }
+        for tag in &fragment.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+
+    Some(TimeEntryForTable {
+        description: descriptions.join(separator),
+        ended_at: last.ended_at.clone(),
+        billable: fragments.iter().any(|fragment| fragment.billable),
+        tags,
+        merged_ids: fragments
+            .iter()
+            .flat_map(|fragment| fragment.plugin_entry_ids())
+            .map(str::to_string)
+            .collect(),
+        ..(*first).clone()
+    })
+}
+
+/// Replace the items at the indices, the fragments of a merge, with the merged item.
+/// It takes the place of the first fragment, which is returned.
+pub(crate) fn replace_fragments<T>(items: &mut Vec<T>, indices: &[usize], merged: T) -> usize {
+    let position = indices.iter().min().copied().unwrap_or_default();
+    let mut idx = 0;
+    items.retain(|_| {
+        idx += 1;
+        !indices.contains(&(idx - 1))
+    });
+    items.insert(position, merged);
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, source: &str, start: &str, end: &str) -> TimeEntryForTable {
+        TimeEntryForTable {
+            id: id.to_string(),
+            source: source.to_string(),
+            started_at: format!("2025-01-15T{}:00+00:00", start),
+            ended_at: format!("2025-01-15T{}:00+00:00", end),
+            user_id: Some("1".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn at(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&format!("2025-01-15T{}:00+00:00", time)).unwrap()
+    }
+
+    #[test]
+    fn test_analyze() {
+        let entries = vec![
+            entry("1", "moneybird", "09:00", "10:30"),
+            entry("2", "moneybird", "10:00", "11:00"),
+            entry("3", "moneybird", "13:00", "14:00"),
+            entry("4", "moneybird", "18:00", "19:00"),
+            entry("p1", "toggl", "13:00", "14:00"),
+            entry("p2", "toggl", "15:00", "16:00"),
+            TimeEntryForTable {
+                linked_id: Some("3".to_string()),
+                ..entry("p3", "toggl", "13:00", "14:00")
+            },
+        ];
+        let analysis = analyze(&entries, "UTC", &AnalysisConfig::default());
+
+        assert_eq!(analysis.issues("1"), [Issue::Overlap("2".to_string())]);
+        assert_eq!(analysis.issues("2"), [Issue::Overlap("1".to_string())]);
+        assert_eq!(analysis.issues("3"), [Issue::Gap(120)]);
+        // The gap after 14:00 is only counted until the end of the working day
+        assert_eq!(analysis.issues("4"), [Issue::Gap(180)]);
+        assert_eq!(analysis.issues("p1"), [Issue::Duplicate("3".to_string())]);
+        assert!(analysis.issues("p2").is_empty());
+        assert!(analysis.issues("p3").is_empty());
+        assert_eq!(analysis.badges("1"), "⚠");
+
+        let quiet = AnalysisConfig {
+            gap_minutes: 0,
+            ..Default::default()
+        };
+        assert!(analyze(&entries, "UTC", &quiet).issues("3").is_empty());
+    }
+
+    #[test]
+    fn test_trim_overlap() {
+        let span = |start, end| (at(start), at(end));
+        assert_eq!(
+            trim_overlap(span("09:00", "10:30"), span("10:00", "11:00")),
+            Some((false, span("10:30", "11:00")))
+        );
+        assert_eq!(
+            trim_overlap(span("10:00", "11:00"), span("09:00", "10:30")),
+            Some((true, span("10:30", "11:00")))
+        );
+        // Within the other entry, the earlier entry ends when the later one starts
+        assert_eq!(
+            trim_overlap(span("09:00", "12:00"), span("10:00", "11:00")),
+            Some((true, span("09:00", "10:00")))
+        );
+        assert_eq!(
+            trim_overlap(span("09:00", "10:00"), span("10:00", "11:00")),
+            None
+        );
+        assert_eq!(
+            trim_overlap(span("09:00", "10:00"), span("09:00", "09:30")),
+            None
+        );
+        assert_eq!(
+            merge_intervals(span("09:00", "10:30"), span("10:00", "11:00")),
+            span("09:00", "11:00")
+        );
+    }
+
+    #[test]
+    fn test_merge_fragments() {
+        let fragment = |id, start, end, description: &str| TimeEntryForTable {
+            customer: "Acme".to_string(),
+            project: "Website".to_string(),
+            description: description.to_string(),
+            ..entry(id, "git", start, end)
+        };
+        let entries = vec![
+            fragment("c3", "10:20", "10:40", "Fix login"),
+            fragment("c1", "09:00", "09:30", "Add login"),
+            fragment("c2", "09:40", "10:10", "Add login"),
+            fragment("c4", "13:00", "13:30", "Update docs"),
+            TimeEntryForTable {
+                project: "Shop".to_string(),
+                ..fragment("c5", "10:10", "10:20", "Other project")
+            },
+            entry("m1", "moneybird", "09:30", "09:40"),
+        ];
+
+        // The fragments chain while they are at most 15 minutes apart
+        assert_eq!(adjacent_fragments(&entries, 0, 15), [1, 2, 0]);
+        assert_eq!(adjacent_fragments(&entries, 1, 5), Vec::<usize>::new());
+        assert_eq!(adjacent_fragments(&entries, 3, 15), Vec::<usize>::new());
+        assert_eq!(adjacent_fragments(&entries, 5, 15), Vec::<usize>::new());
+
+        let fragments: Vec<&TimeEntryForTable> = [1, 2, 0].iter().map(|i| &entries[*i]).collect();
+        let merged = merge_fragments(&fragments, "; ").unwrap();
+        assert_eq!(merged.id, "c1");
+        assert_eq!(entry_interval(&merged), Some((at("09:00"), at("10:40"))));
+        assert_eq!(merged.description, "Add login; Fix login");
+        assert_eq!(merged.merged_ids, ["c1", "c2", "c3"]);
+        assert_eq!(merged.plugin_entry_ids(), ["c1", "c2", "c3"]);
+
+        // Merging a merged entry again keeps all its fragments
+        let again = merge_fragments(&[&merged, &entries[3]], " | ").unwrap();
+        assert_eq!(again.merged_ids, ["c1", "c2", "c3", "c4"]);
+        assert_eq!(again.description, "Add login; Fix login | Update docs");
+    }
+
+    #[test]
+    fn test_replace_fragments() {
+        let mut items = vec!["a", "b", "c", "d", "e"];
+        assert_eq!(replace_fragments(&mut items, &[3, 1, 2], "bcd"), 1);
+        assert_eq!(items, ["a", "bcd", "e"]);
+
+        // Fragments don't have to be next to each other in the list
+        let mut items = vec!["a", "b", "c", "d", "e"];
+        assert_eq!(replace_fragments(&mut items, &[4, 2], "ce"), 2);
+        assert_eq!(items, ["a", "b", "ce", "d"]);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::config::Configuration;
+use crate::model::{TimeEntryFilter, TimeEntryStateFilter};
 use crate::moneybird::types::{Contact, Project, TimeEntry, User};
 use crate::moneybird::{self, types::Administration};
-use crate::plugin::PluginInfo;
+use crate::plugin::{FetchedTimeEntries, PluginInfo, PluginTimeEntry};
 use crate::ui;
 use crate::{datetime, AppModel, TimeEntryForTable};
 use color_eyre::eyre::Result;
@@ -260,9 +261,10 @@ pub(crate) async fn get_time_entries_by_date_range(
     administration_id: &str,
     start_date: &str,
     end_date: &str,
+    time_entry_filter: &TimeEntryFilter,
 ) -> Result<Vec<TimeEntry>> {
     // Create a date range filter for the MoneyBird API
-    let filter = match create_date_range_filter(start_date, end_date) {
+    let filter = match create_time_entry_filter(start_date, end_date, time_entry_filter) {
         Ok(filter) => filter,
         Err(err) => return Err(err),
     };
@@ -346,8 +348,38 @@ pub(crate) fn log_debug_curl(model: &mut AppModel, endpoint: &str, method: &str)
     model.log_debug(format!("{}", t!("api_debug_curl_command", curl = curl_cmd)));
 }
 
-/// Helper function to fetch time entries for the current date range
+/// Helper function to fetch time entries for the current date range, waiting for the
+/// plugin entries as well
 pub(crate) async fn get_time_entries(model: &mut AppModel) {
+    if !get_moneybird_time_entries(model).await {
+        return;
+    }
+    if let Some(fetch) = fetch_plugin_time_entries(model) {
+        let (entries, errors) = fetch.await;
+        add_plugin_time_entries(model, entries, errors);
+
+        // All entries are in, so start at the first entry again
+        if !model.time_entries_for_table.is_empty() {
+            model.time_entry_table_state.select(Some(0));
+        }
+    }
+}
+
+/// Fetch the time entries for the current date range, the plugins are asked in a task
+/// that is picked up as [`Message::PluginEntriesLoaded`](crate::event::Message::PluginEntriesLoaded) once done.
+/// A fetch of an earlier refresh that's still running is dropped.
+pub(crate) async fn refresh_time_entries(model: &mut AppModel) {
+    if let Some(fetch) = model.plugin_fetch.take() {
+        fetch.abort();
+    }
+    model.plugin_progress.clear();
+    if get_moneybird_time_entries(model).await {
+        model.plugin_fetch = fetch_plugin_time_entries(model).map(tokio::spawn);
+    }
+}
+
+/// Fetch the MoneyBird time entries for the current date range, false when that failed
+async fn get_moneybird_time_entries(model: &mut AppModel) -> bool {
     let admin_id = model.administration.id.clone().unwrap_or_default();
 
     // Get administration timezone, default to UTC if not set
@@ -364,19 +396,20 @@ pub(crate) async fn get_time_entries(model: &mut AppModel) {
         &model.config.week_starts_on,
     );
 
-    // Create a date range filter for the MoneyBird API
-    let filter = match create_date_range_filter(&week_range.0, &week_range.1) {
-        Ok(filter) => filter,
-        Err(err) => {
-            // If we couldn't create a filter, show an error
-            crate::ui::show_error(
-                model,
-                t!("api_failed_create_date_filter", error = err).to_string(),
-            );
-            model.log_error(t!("api_failed_create_date_filter", error = err).to_string());
-            return;
-        }
-    };
+    // Create a date range filter for the MoneyBird API, including the server-side filters
+    let filter =
+        match create_time_entry_filter(&week_range.0, &week_range.1, &model.time_entry_filter) {
+            Ok(filter) => filter,
+            Err(err) => {
+                // If we couldn't create a filter, show an error
+                crate::ui::show_error(
+                    model,
+                    t!("api_failed_create_date_filter", error = err).to_string(),
+                );
+                model.log_error(t!("api_failed_create_date_filter", error = err).to_string());
+                return false;
+            }
+        };
 
     // Prepare debug information in case of failure
     let endpoint = format!("time_entries.json?filter={}", filter);
@@ -388,6 +421,7 @@ pub(crate) async fn get_time_entries(model: &mut AppModel) {
         &admin_id,
         &week_range.0,
         &week_range.1,
+        &model.time_entry_filter,
     )
     .await
     {
@@ -431,83 +465,31 @@ pub(crate) async fn get_time_entries(model: &mut AppModel) {
                         source: "moneybird".to_string(),
                         icon: None,
                         plugin_name: None,
+                        user: ui::get_user_name(&model.users, entry.user_id.as_ref()),
+                        user_id: entry.user_id.clone(),
+                        contact_id: entry.contact_id.clone().or_else(|| {
+                            entry
+                                .contact
+                                .as_ref()
+                                .and_then(|contact| contact.id.clone())
+                        }),
+                        tags: Vec::new(), // MoneyBird time entries don't have tags
+                        linked_id: None,
+                        merged_ids: Vec::new(),
                     }
                 })
                 .collect();
 
             model.time_entries_for_table_backup = model.time_entries_for_table.clone();
+            model.plugin_entries = Vec::new();
 
-            // Load plugin entries if plugin system is available
-            if let Some(plugin_manager) = &mut model.plugin_manager {
-                // Calculate the week range dates in UTC
-                let (start, end) = datetime::calculate_week_range(
-                    model.week_offset,
-                    &admin_timezone_str,
-                    &model.config.week_starts_on,
-                );
+            // Plugin entries that were imported before are linked to their MoneyBird entry
+            model.import_links = crate::links::read_links(&admin_id);
+            model.link_plugin_entries();
+            model.analyze_time_entries();
 
-                // Convert to UTC for plugin API
-                let start_utc = start.with_timezone(&chrono::Utc);
-                let end_utc = end.with_timezone(&chrono::Utc);
-
-                // Get time entries from all plugins
-                match plugin_manager
-                    .get_all_time_entries(&start_utc, &end_utc)
-                    .await
-                {
-                    Ok((plugin_entries, errors)) => {
-                        // First, store the plugin errors for later
-                        let plugin_errors = errors;
-
-                        // Process plugin entries if we have any
-                        if !plugin_entries.is_empty() {
-                            // Convert plugin entries to TimeEntryForTable
-                            let mut table_entries: Vec<TimeEntryForTable> = plugin_entries
-                                .into_iter()
-                                .map(TimeEntryForTable::from)
-                                .collect();
-
-                            // Get the plugin info list once
-                            let plugin_infos = plugin_manager.list_plugins();
-
-                            // Apply icons to the entries
-                            apply_plugin_icons(model, &mut table_entries, &plugin_infos);
-
-                            // Log after updating the entries
-                            model.log_notice(t!(
-                                "plugin_entries_loaded",
-                                count = table_entries.len()
-                            ));
-
-                            // Store plugin entries
-                            model.plugin_entries = table_entries.clone();
-
-                            // Add plugin entries to regular entries
-                            model.time_entries_for_table.extend(table_entries);
-                            model.time_entries_for_table_backup =
-                                model.time_entries_for_table.clone();
-                        }
-
-                        // Now process any errors
-                        for (plugin_name, error_msg) in plugin_errors {
-                            model.log_error(error_msg.clone());
-                            crate::ui::show_error(
-                                model,
-                                t!("plugin_error", name = plugin_name, error = error_msg),
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        let error_msg = format!("Failed to get plugin time entries: {}", e);
-                        model.log_error(error_msg.clone());
-                        crate::ui::show_error(model, error_msg);
-                    }
-                }
-            }
-
-            if model.search_state.active {
-                model.filter_items();
-            }
+            // Sort on the configured column, this also applies an active search
+            model.sort_time_entries();
 
             // New entries are loaded, so select the first entry
             if !model.time_entries_for_table.is_empty() {
@@ -527,6 +509,7 @@ pub(crate) async fn get_time_entries(model: &mut AppModel) {
                     date = entry.started_at.clone().unwrap_or_default()
                 ));
             }
+            true
         }
         Err(err) => {
             // If we couldn't fetch entries, clear the list and show error
@@ -538,11 +521,83 @@ pub(crate) async fn get_time_entries(model: &mut AppModel) {
             model.time_entries = Vec::new();
             model.time_entries_for_table = Vec::new();
             model.time_entries_for_table_backup = Vec::new();
+            model.analyze_time_entries();
             model.time_entry_table_state.select(None);
+            false
         }
     }
 }
 
+/// Start asking the plugins for their entries in the current date range, None when no
+/// plugin provides time entries
+fn fetch_plugin_time_entries(
+    model: &AppModel,
+) -> Option<impl std::future::Future<Output = FetchedTimeEntries> + Send + 'static> {
+    let plugin_manager = model
+        .plugin_manager
+        .as_ref()
+        .filter(|plugin_manager| plugin_manager.provides_time_entries())?;
+    let admin_timezone_str = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+
+    // Calculate the week range dates in UTC
+    let (start, end) = datetime::calculate_week_range(
+        model.week_offset,
+        &admin_timezone_str,
+        &model.config.week_starts_on,
+    );
+
+    // Convert to UTC for plugin API
+    let start_utc = start.with_timezone(&chrono::Utc);
+    let end_utc = end.with_timezone(&chrono::Utc);
+    Some(plugin_manager.fetch_time_entries(&start_utc, &end_utc))
+}
+
+/// Add the entries fetched from the plugins to the table and show the plugins that failed
+pub(crate) fn add_plugin_time_entries(
+    model: &mut AppModel,
+    plugin_entries: Vec<PluginTimeEntry>,
+    errors: Vec<(String, String)>,
+) {
+    if !plugin_entries.is_empty() {
+        // Convert plugin entries to TimeEntryForTable
+        let mut table_entries: Vec<TimeEntryForTable> = plugin_entries
+            .into_iter()
+            .map(TimeEntryForTable::from)
+            .collect();
+
+        // Apply icons to the entries
+        let plugin_infos = model
+            .plugin_manager
+            .as_ref()
+            .map(|plugin_manager| plugin_manager.list_plugins())
+            .unwrap_or_default();
+        apply_plugin_icons(model, &mut table_entries, &plugin_infos);
+
+        model.log_notice(t!("plugin_entries_loaded", count = table_entries.len()));
+
+        // Store plugin entries and add them to the regular entries
+        model.plugin_entries = table_entries.clone();
+        model.time_entries_for_table_backup.extend(table_entries);
+
+        // Imported entries are linked to their MoneyBird entry, the sort keeps the selection
+        model.link_plugin_entries();
+        model.analyze_time_entries();
+        model.sort_time_entries();
+    }
+
+    for (plugin_name, error_msg) in errors {
+        model.log_error(error_msg.clone());
+        crate::ui::show_error(
+            model,
+            t!("plugin_error", name = plugin_name, error = error_msg),
+        );
+    }
+}
+
 /// Generate a curl command that can be used to debug API calls
 pub(crate) fn generate_debug_curl(
     endpoint: &str,
@@ -788,6 +843,31 @@ pub(crate) fn create_date_range_filter(start_date: &str, end_date: &str) -> Resu
     ))
 }
 
+/// Create the full time entry filter for the MoneyBird API: the period plus any
+/// state, contact, project or user filter, separated by commas
+pub(crate) fn create_time_entry_filter(
+    start_date: &str,
+    end_date: &str,
+    time_entry_filter: &TimeEntryFilter,
+) -> Result<String> {
+    let mut filters = vec![create_date_range_filter(start_date, end_date)?];
+
+    if time_entry_filter.state != TimeEntryStateFilter::All {
+        filters.push(format!("state:{}", time_entry_filter.state.as_str()));
+    }
+    if let Some(contact_id) = &time_entry_filter.contact_id {
+        filters.push(format!("contact_id:{}", contact_id));
+    }
+    if let Some(project_id) = &time_entry_filter.project_id {
+        filters.push(format!("project_id:{}", project_id));
+    }
+    if let Some(user_id) = &time_entry_filter.user_id {
+        filters.push(format!("user_id:{}", user_id));
+    }
+
+    Ok(filters.join(","))
+}
+
 pub(crate) async fn get_all_users(
     client: &moneybird::Client,
     administration_id: &str,
@@ -877,3 +957,37 @@ fn apply_plugin_icons(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_time_entry_filter() {
+        let start = "2025-04-07T00:00:00+02:00";
+        let end = "2025-04-13T23:59:59+02:00";
+        let filter = create_time_entry_filter(start, end, &TimeEntryFilter::default()).unwrap();
+        assert_eq!(filter, "period:20250407..20250413");
+
+        let time_entry_filter = TimeEntryFilter {
+            state: TimeEntryStateFilter::NonBillable,
+            contact_id: Some("1".to_string()),
+            project_id: Some("2".to_string()),
+            user_id: Some("3".to_string()),
+        };
+        let filter = create_time_entry_filter(start, end, &time_entry_filter).unwrap();
+        assert_eq!(
+            filter,
+            "period:20250407..20250413,state:non_billable,contact_id:1,project_id:2,user_id:3"
+        );
+
+        let time_entry_filter = TimeEntryFilter {
+            state: TimeEntryStateFilter::Open,
+            ..Default::default()
+        };
+        let filter = create_time_entry_filter(start, end, &time_entry_filter).unwrap();
+        assert_eq!(filter, "period:20250407..20250413,state:open");
+
+        assert!(create_time_entry_filter("2025", end, &time_entry_filter).is_err());
+    }
+}
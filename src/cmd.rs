@@ -8,18 +8,42 @@ pub(crate) struct Args {
     #[clap(short = 'e', long, action = clap::ArgAction::SetTrue, help = t!("cmd_export_help").to_string())]
     pub(crate) export: bool,
 
-    /// Week number to export
-    #[clap(short = 'w', long, default_value = "current week", help = t!("cmd_week_help").to_string())]
+    /// Week to export, relative weeks like -3w start with a hyphen
+    #[clap(short = 'w', long, default_value = "current week", allow_hyphen_values = true, help = t!("cmd_week_help").to_string())]
     pub(crate) week: String,
 
     /// Filter query
     #[clap(short = 'q', long, default_value = "", help = t!("cmd_query_help").to_string())]
     pub(crate) query: String,
 
+    /// Apply a saved search by name
+    #[clap(long = "saved-query", value_name = "NAME", help = t!("cmd_saved_query_help").to_string())]
+    pub(crate) saved_query: Option<String>,
+
+    /// Only fetch time entries with this state
+    #[clap(long, value_name = "STATE", help = t!("cmd_state_help").to_string())]
+    pub(crate) state: Option<String>,
+
+    /// Only fetch time entries for this contact
+    #[clap(long = "contact-id", value_name = "CONTACT_ID", help = t!("cmd_contact_id_help").to_string())]
+    pub(crate) contact_id: Option<String>,
+
+    /// Only fetch time entries for this project
+    #[clap(long = "project-id", value_name = "PROJECT_ID", help = t!("cmd_project_id_help").to_string())]
+    pub(crate) project_id: Option<String>,
+
+    /// Only fetch time entries for this user
+    #[clap(long = "user-id", value_name = "USER_ID", help = t!("cmd_user_id_help").to_string())]
+    pub(crate) user_id: Option<String>,
+
     /// Set the display language (e.g., en, nl)
     #[clap(short = 'l', long, help = t!("cmd_language_help").to_string())]
     pub(crate) language: Option<String>,
 
+    /// Print the active key bindings
+    #[clap(long, action = clap::ArgAction::SetTrue, help = t!("cmd_keybindings_help").to_string())]
+    pub(crate) keybindings: bool,
+
     /// Debug a plugin initialization issues
     #[clap(long = "plugin-debug", value_name = "PLUGIN_NAME", help = t!("cmd_plugin_debug_help").to_string())]
     pub(crate) plugin_debug: Option<String>,
@@ -1,7 +1,9 @@
 use config::{Config, File};
 use locale_config::Locale;
+use regex::Regex;
 use rust_i18n::t;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
@@ -19,6 +21,512 @@ pub fn detect_system_language() -> Option<String> {
     }
 }
 
+/// A search query saved under a name
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+}
+
+/// Column the time entry table is sorted on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortColumn {
+    #[default]
+    Date,
+    Client,
+    Project,
+    Duration,
+    Source,
+}
+
+impl SortColumn {
+    /// Get the next sort column, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            SortColumn::Date => SortColumn::Client,
+            SortColumn::Client => SortColumn::Project,
+            SortColumn::Project => SortColumn::Duration,
+            SortColumn::Duration => SortColumn::Source,
+            SortColumn::Source => SortColumn::Date,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn toggle(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+}
+
+/// Sort order of the time entry table, also used for exports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSort {
+    #[serde(default)]
+    pub column: SortColumn,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+/// Column that can be shown in the time entry table
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableColumnKind {
+    Icon,
+    Date,
+    Time,
+    Duration,
+    Billable,
+    User,
+    Client,
+    Project,
+    Source,
+    Tags,
+    Description,
+}
+
+impl TableColumnKind {
+    /// Columns with a higher priority stay visible longer on narrow terminals
+    pub fn default_priority(self) -> u8 {
+        match self {
+            TableColumnKind::Description => 10,
+            TableColumnKind::Date => 9,
+            TableColumnKind::Time => 8,
+            TableColumnKind::Client => 7,
+            TableColumnKind::Project => 6,
+            TableColumnKind::Duration => 5,
+            TableColumnKind::User => 4,
+            TableColumnKind::Icon => 3,
+            TableColumnKind::Billable => 2,
+            TableColumnKind::Source => 1,
+            TableColumnKind::Tags => 0,
+        }
+    }
+
+    /// The sort column that a click on the column header switches to
+    pub fn sort_column(self) -> Option<SortColumn> {
+        match self {
+            TableColumnKind::Icon | TableColumnKind::Source => Some(SortColumn::Source),
+            TableColumnKind::Date => Some(SortColumn::Date),
+            TableColumnKind::Time | TableColumnKind::Duration => Some(SortColumn::Duration),
+            TableColumnKind::Client => Some(SortColumn::Client),
+            TableColumnKind::Project => Some(SortColumn::Project),
+            TableColumnKind::Billable
+            | TableColumnKind::User
+            | TableColumnKind::Tags
+            | TableColumnKind::Description => None,
+        }
+    }
+}
+
+/// A column of the time entry table and how wide it may get
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableColumn {
+    pub column: TableColumnKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_width: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<u16>, // Share of the table width instead of fitting the content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>, // Columns with a lower priority are hidden first
+}
+
+impl TableColumn {
+    pub fn new(column: TableColumnKind) -> Self {
+        Self {
+            column,
+            min_width: None,
+            max_width: None,
+            percentage: None,
+            priority: None,
+        }
+    }
+
+    pub fn get_priority(&self) -> u8 {
+        self.priority
+            .unwrap_or_else(|| self.column.default_priority())
+    }
+}
+
+/// Columns of the time entry table, in display order
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableConfig {
+    #[serde(default = "default_table_columns")]
+    pub columns: Vec<TableColumn>,
+}
+
+fn default_table_columns() -> Vec<TableColumn> {
+    [
+        TableColumnKind::Icon,
+        TableColumnKind::Date,
+        TableColumnKind::Time,
+        TableColumnKind::User,
+        TableColumnKind::Client,
+        TableColumnKind::Project,
+        TableColumnKind::Description,
+    ]
+    .into_iter()
+    .map(TableColumn::new)
+    .collect()
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            columns: default_table_columns(),
+        }
+    }
+}
+
+impl TableConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Get the configured columns, an empty list falls back to the default columns
+    pub fn get_columns(&self) -> Vec<TableColumn> {
+        if self.columns.is_empty() {
+            default_table_columns()
+        } else {
+            self.columns.clone()
+        }
+    }
+}
+
+/// Checks on the listed entries, see the analysis module
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisConfig {
+    #[serde(default = "default_gap_minutes")]
+    pub gap_minutes: u32, // Gaps during working hours longer than this are flagged, 0 turns it off
+    #[serde(default = "default_day_start")]
+    pub day_start: String, // Working hours as HH:MM
+    #[serde(default = "default_day_end")]
+    pub day_end: String,
+}
+
+fn default_gap_minutes() -> u32 {
+    30
+}
+
+fn default_day_start() -> String {
+    "09:00".to_string()
+}
+
+fn default_day_end() -> String {
+    "17:00".to_string()
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            gap_minutes: default_gap_minutes(),
+            day_start: default_day_start(),
+            day_end: default_day_end(),
+        }
+    }
+}
+
+impl AnalysisConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How plugin fragments are merged into one entry before importing
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeConfig {
+    #[serde(default = "default_merge_separator")]
+    pub separator: String, // Between the descriptions of the merged fragments
+    #[serde(default = "default_merge_gap_minutes")]
+    pub max_gap_minutes: u32, // Fragments further apart are not adjacent
+}
+
+fn default_merge_separator() -> String {
+    "; ".to_string()
+}
+
+fn default_merge_gap_minutes() -> u32 {
+    15
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        Self {
+            separator: default_merge_separator(),
+            max_gap_minutes: default_merge_gap_minutes(),
+        }
+    }
+}
+
+impl MergeConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Direction durations are rounded in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    #[default]
+    Up,
+    Nearest,
+    Down,
+}
+
+/// Where rounding is applied, totals and exports are always rounded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingApply {
+    #[default]
+    Totals,
+    Save, // Also moves the end time of saved entries
+}
+
+/// The rounding of one contact, after applying its override
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RoundingRule {
+    pub increment: u64, // Minutes, 0 doesn't round
+    pub mode: RoundingMode,
+    pub minimum: u64, // Shortest entry in minutes
+}
+
+impl RoundingRule {
+    pub fn is_active(&self) -> bool {
+        self.increment > 0 || self.minimum > 0
+    }
+
+    /// Round a duration in minutes to the increment, then raise it to the minimum.
+    /// Empty entries stay empty.
+    pub fn round(&self, minutes: u64) -> u64 {
+        if minutes == 0 {
+            return 0;
+        }
+        let rounded = match (self.increment, self.mode) {
+            (0, _) => minutes,
+            (increment, RoundingMode::Up) => minutes.div_ceil(increment) * increment,
+            (increment, RoundingMode::Nearest) => (minutes + increment / 2) / increment * increment,
+            (increment, RoundingMode::Down) => minutes / increment * increment,
+        };
+        rounded.max(self.minimum)
+    }
+}
+
+/// Rounding settings of a contact, unset values come from the general settings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundingOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub increment: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<RoundingMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<u64>,
+}
+
+/// Rounding of durations, for contracts that bill in fixed increments
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundingConfig {
+    #[serde(default)]
+    pub increment: u64,
+    #[serde(default)]
+    pub mode: RoundingMode,
+    #[serde(default)]
+    pub minimum: u64,
+    #[serde(default)]
+    pub apply: RoundingApply,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contacts: BTreeMap<String, RoundingOverride>, // Contact name or id to override
+}
+
+impl RoundingConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The rule of a contact, the contacts are matched on id or on name ignoring case
+    pub fn rule_for(&self, contact_id: Option<&str>, contact_name: &str) -> RoundingRule {
+        let contact = self.contacts.iter().find_map(|(key, contact)| {
+            let matches = Some(key.as_str()) == contact_id
+                || (!contact_name.is_empty() && key.eq_ignore_ascii_case(contact_name));
+            matches.then_some(contact)
+        });
+        RoundingRule {
+            increment: contact
+                .and_then(|contact| contact.increment)
+                .unwrap_or(self.increment),
+            mode: contact
+                .and_then(|contact| contact.mode)
+                .unwrap_or(self.mode),
+            minimum: contact
+                .and_then(|contact| contact.minimum)
+                .unwrap_or(self.minimum),
+        }
+    }
+}
+
+/// A regular expression of a mapping rule, compiled once when the configuration is loaded
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct MappingPattern {
+    source: String,
+    regex: Option<Regex>, // None when the pattern isn't a valid regular expression
+}
+
+impl MappingPattern {
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let regex = Regex::new(&source).ok();
+        Self { source, regex }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.regex.is_some()
+    }
+
+    /// Check if a name matches, an invalid pattern never matches
+    pub fn is_match(&self, name: &str) -> bool {
+        self.regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(name))
+    }
+}
+
+impl PartialEq for MappingPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for MappingPattern {}
+
+impl From<String> for MappingPattern {
+    fn from(source: String) -> Self {
+        Self::new(source)
+    }
+}
+
+impl From<MappingPattern> for String {
+    fn from(pattern: MappingPattern) -> Self {
+        pattern.source
+    }
+}
+
+/// Maps plugin entries to a MoneyBird contact and project when they are imported. The
+/// patterns are regular expressions, a rule without a pattern matches any name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>, // Plugin name, any plugin when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer: Option<MappingPattern>, // Pattern for the customer name of the plugin entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<MappingPattern>, // Pattern for the project name of the plugin entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_name: Option<String>, // Shown in the edit form
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>, // Shown in the edit form
+}
+
+impl MappingRule {
+    /// A rule that matches exactly these names, as learned from an import
+    pub fn exact(plugin: &str, customer: &str, project: &str) -> Self {
+        let pattern = |name: &str| {
+            (!name.is_empty()).then(|| MappingPattern::new(format!("^{}$", regex::escape(name))))
+        };
+        Self {
+            plugin: Some(plugin.to_string()),
+            customer: pattern(customer),
+            project: pattern(project),
+            ..Default::default()
+        }
+    }
+
+    /// Check if the rule applies to a plugin entry, invalid patterns never match
+    pub fn matches(&self, plugin: &str, customer: &str, project: &str) -> bool {
+        let pattern_matches = |pattern: &Option<MappingPattern>, name: &str| {
+            pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(name))
+        };
+        self.plugin.as_deref().is_none_or(|name| name == plugin)
+            && pattern_matches(&self.customer, customer)
+            && pattern_matches(&self.project, project)
+    }
+
+    /// The patterns that aren't valid regular expressions
+    pub fn invalid_patterns(&self) -> Vec<&str> {
+        [&self.customer, &self.project]
+            .into_iter()
+            .flatten()
+            .filter(|pattern| !pattern.is_valid())
+            .map(MappingPattern::as_str)
+            .collect()
+    }
+
+    /// Check if both rules apply to the same plugin entries
+    pub fn has_same_patterns(&self, other: &MappingRule) -> bool {
+        self.plugin == other.plugin
+            && self.customer == other.customer
+            && self.project == other.project
+    }
+}
+
+/// Checks on a time entry before the edit form saves it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidationConfig {
+    #[serde(default = "default_max_hours")]
+    pub max_hours: f64, // Longest entry in hours, 0 turns the check off
+    #[serde(default)]
+    pub require_project: bool,
+    #[serde(default = "default_check_overlaps")]
+    pub check_overlaps: bool, // With the other MoneyBird entries of the week
+}
+
+fn default_max_hours() -> f64 {
+    12.0
+}
+
+fn default_check_overlaps() -> bool {
+    true
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_hours: default_max_hours(),
+            require_project: false,
+            check_overlaps: default_check_overlaps(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Configuration {
     pub access_token: String,
@@ -28,6 +536,26 @@ pub struct Configuration {
     #[serde(default = "default_week_start")]
     pub week_starts_on: String,
     pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_search: Option<String>, // Name of the saved search applied at startup
+    #[serde(default)]
+    pub sort: TableSort,
+    #[serde(default, skip_serializing_if = "TableConfig::is_default")]
+    pub table: TableConfig,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<String, BTreeMap<String, Vec<String>>>, // Mode to action to keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub saved_searches: Vec<SavedSearch>,
+    #[serde(default, skip_serializing_if = "ValidationConfig::is_default")]
+    pub validation: ValidationConfig,
+    #[serde(default, skip_serializing_if = "RoundingConfig::is_default")]
+    pub rounding: RoundingConfig,
+    #[serde(default, skip_serializing_if = "AnalysisConfig::is_default")]
+    pub analysis: AnalysisConfig,
+    #[serde(default, skip_serializing_if = "MergeConfig::is_default")]
+    pub merge: MergeConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mappings: Vec<MappingRule>, // Checked in order, the first matching rule is used
 }
 
 fn default_week_start() -> String {
@@ -43,6 +571,16 @@ impl Default for Configuration {
             user_id: None,
             week_starts_on: default_week_start(),
             language: None,
+            default_search: None,
+            sort: TableSort::default(),
+            table: TableConfig::default(),
+            keybindings: BTreeMap::new(),
+            saved_searches: Vec::new(),
+            validation: ValidationConfig::default(),
+            rounding: RoundingConfig::default(),
+            analysis: AnalysisConfig::default(),
+            merge: MergeConfig::default(),
+            mappings: Vec::new(),
         }
     }
 }
@@ -55,6 +593,65 @@ impl Configuration {
     pub fn get_administration_id(&self) -> String {
         self.administration_id.clone().unwrap_or_default()
     }
+
+    /// Find a saved search by name, ignoring case
+    pub fn find_saved_search(&self, name: &str) -> Option<&SavedSearch> {
+        self.saved_searches
+            .iter()
+            .find(|search| search.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Save a search under a name, replacing the query of an existing search with that name
+    pub fn upsert_saved_search(&mut self, name: &str, query: &str) {
+        match self
+            .saved_searches
+            .iter_mut()
+            .find(|search| search.name.eq_ignore_ascii_case(name))
+        {
+            Some(search) => search.query = query.to_string(),
+            None => self.saved_searches.push(SavedSearch {
+                name: name.to_string(),
+                query: query.to_string(),
+            }),
+        }
+    }
+
+    /// Combine the mapping rules that apply to a plugin entry. The contact comes from the
+    /// first matching rule with a contact, the project from the first one with a project.
+    pub fn find_mapping(&self, plugin: &str, customer: &str, project: &str) -> Option<MappingRule> {
+        let matching: Vec<&MappingRule> = self
+            .mappings
+            .iter()
+            .filter(|rule| rule.matches(plugin, customer, project))
+            .collect();
+        let contact = matching.iter().find(|rule| rule.contact_id.is_some());
+        let project = matching.iter().find(|rule| rule.project_id.is_some());
+        if contact.is_none() && project.is_none() {
+            return None;
+        }
+        Some(MappingRule {
+            contact_id: contact.and_then(|rule| rule.contact_id.clone()),
+            contact_name: contact.and_then(|rule| rule.contact_name.clone()),
+            project_id: project.and_then(|rule| rule.project_id.clone()),
+            project_name: project.and_then(|rule| rule.project_name.clone()),
+            ..Default::default()
+        })
+    }
+
+    /// Add a learned mapping rule, replacing a rule with the same patterns. Learned rules
+    /// go first, so they win over broader rules that were written by hand.
+    pub fn learn_mapping(&mut self, rule: MappingRule) {
+        self.mappings
+            .retain(|existing| !existing.has_same_patterns(&rule));
+        self.mappings.insert(0, rule);
+    }
+
+    /// Get the pinned default search, if it still exists
+    pub fn get_default_search(&self) -> Option<&SavedSearch> {
+        self.default_search
+            .as_deref()
+            .and_then(|name| self.find_saved_search(name))
+    }
 }
 
 pub fn get_config() -> Config {
@@ -193,4 +790,232 @@ mod tests {
         }
         // If None is returned, that's also valid if no supported language was found
     }
+
+    #[test]
+    fn test_saved_searches() {
+        let mut config: Configuration = toml::from_str(
+            r#"
+            access_token = "token"
+            api_url = "https://moneybird.com/api/v2"
+            default_search = "acme"
+
+            [[saved_searches]]
+            name = "Acme"
+            query = "client:acme"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.get_default_search().unwrap().query, "client:acme");
+        assert_eq!(config.sort, TableSort::default());
+
+        config.upsert_saved_search("ACME", "client:acme billable:yes");
+        config.upsert_saved_search("Meetings", "meeting");
+        assert_eq!(config.saved_searches.len(), 2);
+        assert_eq!(
+            config.find_saved_search("acme").unwrap().query,
+            "client:acme billable:yes"
+        );
+
+        // Saved searches are written after the plain values
+        let written = toml::to_string_pretty(&config).unwrap();
+        assert!(written.contains("[[saved_searches]]"));
+        assert!(Configuration::default().get_default_search().is_none());
+    }
+
+    #[test]
+    fn test_table_sort() {
+        let config: Configuration = toml::from_str(
+            r#"
+            access_token = "token"
+            api_url = "https://moneybird.com/api/v2"
+
+            [sort]
+            column = "duration"
+            direction = "descending"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.sort.column, SortColumn::Duration);
+        assert_eq!(config.sort.direction, SortDirection::Descending);
+        assert_eq!(SortColumn::Source.next(), SortColumn::Date);
+        assert_eq!(
+            SortDirection::Ascending.toggle().toggle(),
+            SortDirection::Ascending
+        );
+    }
+
+    #[test]
+    fn test_table_columns() {
+        let config: Configuration = toml::from_str(
+            r#"
+            access_token = "token"
+            api_url = "https://moneybird.com/api/v2"
+
+            [table]
+            columns = [
+                { column = "date" },
+                { column = "duration", priority = 20 },
+                { column = "client", max_width = 20 },
+                { column = "description", percentage = 50 },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        let columns = config.table.get_columns();
+        assert_eq!(columns.len(), 4);
+        assert_eq!(columns[1].get_priority(), 20);
+        assert_eq!(columns[2].max_width, Some(20));
+        assert_eq!(
+            columns[2].get_priority(),
+            TableColumnKind::Client.default_priority()
+        );
+        assert_eq!(columns[3].percentage, Some(50));
+
+        // The default columns aren't written to the configuration file
+        let written = toml::to_string_pretty(&Configuration::default()).unwrap();
+        assert!(!written.contains("[table]"));
+        let written = toml::to_string_pretty(&config).unwrap();
+        assert!(written.contains("max_width = 20"));
+
+        let empty = TableConfig {
+            columns: Vec::new(),
+        };
+        assert_eq!(empty.get_columns(), TableConfig::default().columns);
+    }
+
+    #[test]
+    fn test_validation() {
+        let config: Configuration = toml::from_str(
+            r#"
+            access_token = "token"
+            api_url = "https://moneybird.com/api/v2"
+
+            [validation]
+            require_project = true
+            "#,
+        )
+        .unwrap();
+        assert!(config.validation.require_project);
+        assert_eq!(config.validation.max_hours, 12.0);
+        assert!(config.validation.check_overlaps);
+
+        let written = toml::to_string_pretty(&Configuration::default()).unwrap();
+        assert!(!written.contains("[validation]"));
+    }
+
+    #[test]
+    fn test_mappings() {
+        let mut config: Configuration = toml::from_str(
+            r#"
+            access_token = "token"
+            api_url = "https://moneybird.com/api/v2"
+
+            [[mappings]]
+            plugin = "gitlab"
+            project = "^acme/"
+            contact_id = "1"
+            contact_name = "Acme BV"
+
+            [[mappings]]
+            customer = "(?i)globex"
+            project_id = "2"
+
+            [[mappings]]
+            customer = "(unclosed"
+            contact_id = "3"
+            "#,
+        )
+        .unwrap();
+
+        let rule = config.find_mapping("gitlab", "", "acme/webshop").unwrap();
+        assert_eq!(rule.contact_id.as_deref(), Some("1"));
+        assert!(config.find_mapping("toggl", "", "acme/webshop").is_none());
+        let rule = config.find_mapping("toggl", "GLOBEX Inc", "").unwrap();
+        assert_eq!(rule.project_id.as_deref(), Some("2"));
+        // The contact and the project can come from different rules
+        let rule = config
+            .find_mapping("gitlab", "Globex", "acme/webshop")
+            .unwrap();
+        assert_eq!(rule.contact_id.as_deref(), Some("1"));
+        assert_eq!(rule.project_id.as_deref(), Some("2"));
+        assert!(config.find_mapping("toggl", "(unclosed", "").is_none());
+        assert_eq!(config.mappings[2].invalid_patterns(), ["(unclosed"]);
+
+        // Patterns are written back as they were read
+        let written = toml::to_string(&config.mappings[1]).unwrap();
+        assert!(written.contains(r#"customer = "(?i)globex""#));
+
+        // A learned rule matches the exact names and replaces an earlier one
+        let learned = MappingRule {
+            contact_id: Some("4".to_string()),
+            ..MappingRule::exact("gitlab", "", "acme/webshop (old)")
+        };
+        config.learn_mapping(learned.clone());
+        config.learn_mapping(learned);
+        assert_eq!(config.mappings.len(), 4);
+        let rule = config
+            .find_mapping("gitlab", "", "acme/webshop (old)")
+            .unwrap();
+        assert_eq!(rule.contact_id.as_deref(), Some("4"));
+        assert!(config.mappings[0].customer.is_none());
+    }
+
+    #[test]
+    fn test_rounding_rule() {
+        let rule = |increment, mode, minimum| RoundingRule {
+            increment,
+            mode,
+            minimum,
+        };
+        assert_eq!(rule(15, RoundingMode::Up, 0).round(61), 75);
+        assert_eq!(rule(15, RoundingMode::Up, 0).round(60), 60);
+        assert_eq!(rule(15, RoundingMode::Nearest, 0).round(67), 60);
+        assert_eq!(rule(15, RoundingMode::Nearest, 0).round(68), 75);
+        assert_eq!(rule(6, RoundingMode::Down, 0).round(59), 54);
+        assert_eq!(rule(6, RoundingMode::Down, 15).round(7), 15);
+        assert_eq!(rule(0, RoundingMode::Up, 30).round(20), 30);
+        assert_eq!(rule(15, RoundingMode::Up, 15).round(0), 0);
+        assert!(!RoundingRule::default().is_active());
+    }
+
+    #[test]
+    fn test_rounding_overrides() {
+        let config: Configuration = toml::from_str(
+            r#"
+            access_token = "token"
+            api_url = "https://moneybird.com/api/v2"
+
+            [rounding]
+            increment = 15
+            minimum = 15
+            apply = "save"
+
+            [rounding.contacts."Acme BV"]
+            increment = 6
+            mode = "nearest"
+
+            [rounding.contacts."42"]
+            minimum = 0
+            "#,
+        )
+        .unwrap();
+        let rounding = &config.rounding;
+        assert_eq!(rounding.apply, RoundingApply::Save);
+
+        let acme = rounding.rule_for(None, "acme bv");
+        assert_eq!(acme.increment, 6);
+        assert_eq!(acme.mode, RoundingMode::Nearest);
+        assert_eq!(acme.minimum, 15);
+
+        let by_id = rounding.rule_for(Some("42"), "Other");
+        assert_eq!(by_id.increment, 15);
+        assert_eq!(by_id.minimum, 0);
+        assert_eq!(rounding.rule_for(None, "Other").mode, RoundingMode::Up);
+
+        let written = toml::to_string_pretty(&Configuration::default()).unwrap();
+        assert!(!written.contains("[rounding]"));
+    }
 }
@@ -1,5 +1,8 @@
 use crate::model::TimeEntryForTable;
-use chrono::{DateTime, Datelike, ParseError, Timelike, Utc, Weekday};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, ParseError, Timelike, Utc,
+    Weekday,
+};
 use chrono_tz::Tz;
 use ratatui::{style::Style, text::Span};
 use rust_i18n::t;
@@ -169,6 +172,219 @@ pub fn get_week_number(week_offset: i32, timezone: &str, week_starts_on: &str) -
     (iso_week.week() as i32, iso_week.year())
 }
 
+/// Number of weeks from the week containing `from` to the week containing `to`
+fn weeks_between(from: NaiveDate, to: NaiveDate, week_starts_on: Weekday) -> i32 {
+    let week_start = |date: NaiveDate| {
+        let days_from_week_start = (7 + date.weekday().num_days_from_monday() as i64
+            - week_starts_on.num_days_from_monday() as i64)
+            % 7;
+        date - chrono::Duration::days(days_from_week_start)
+    };
+    ((week_start(to) - week_start(from)).num_days() / 7) as i32
+}
+
+/// Get today's date in the given timezone
+fn get_today(timezone: &str) -> NaiveDate {
+    Utc::now()
+        .with_timezone(&get_timezone(timezone))
+        .date_naive()
+}
+
+/// Get the current date and time in the given timezone
+pub fn get_now(timezone: &str) -> NaiveDateTime {
+    Utc::now()
+        .with_timezone(&get_timezone(timezone))
+        .naive_local()
+}
+
+/// Get the week offset of the week containing the given date
+pub fn get_week_offset_for_date(date: NaiveDate, timezone: &str, week_starts_on: &str) -> i32 {
+    weeks_between(get_today(timezone), date, string_to_weekday(week_starts_on))
+}
+
+/// Parse an ISO week like `2025-W14`, `2025W14` or `W14` for the current year into its Monday
+fn parse_iso_week(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (year, week) = input.split_once(['w', 'W'])?;
+    let year = match year.trim_end_matches('-') {
+        "" => today.iso_week().year(),
+        year => year.parse().ok()?,
+    };
+    NaiveDate::from_isoywd_opt(year, week.parse().ok()?, Weekday::Mon)
+}
+
+/// Parse a relative offset like `-3w`, `+2d`, `-1m` or `1y` from today
+fn parse_relative_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (unit_idx, _) = input.char_indices().last()?;
+    let (amount, unit) = input.split_at(unit_idx);
+    let amount: i32 = amount.strip_prefix('+').unwrap_or(amount).parse().ok()?;
+    let months = |months: i32| {
+        let delta = chrono::Months::new(months.unsigned_abs());
+        if months < 0 {
+            today.checked_sub_months(delta)
+        } else {
+            today.checked_add_months(delta)
+        }
+    };
+    match unit {
+        "d" => today.checked_add_signed(chrono::Duration::days(amount as i64)),
+        "w" => today.checked_add_signed(chrono::Duration::weeks(amount as i64)),
+        "m" => months(amount),
+        "y" => months(amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// Parse a go to target into a date in the target week: an ISO week (`2025-W14`, `W14`),
+/// a date (`2025-03-01`), a relative offset (`-3w`, `+2d`, `-1m`, `-1y`) or a phrase
+/// like `today`, `last week`, `next week`, `this month`, `last month` or `next month`.
+/// The months go to their first day.
+pub fn parse_go_to_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.to_lowercase();
+    let first_of_month = |date: NaiveDate| date.with_day(1);
+    match input
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .as_str()
+    {
+        "" => None,
+        "today" | "this week" => Some(today),
+        "yesterday" => today.pred_opt(),
+        "tomorrow" => today.succ_opt(),
+        "last week" => parse_relative_date("-1w", today),
+        "next week" => parse_relative_date("+1w", today),
+        "this month" => first_of_month(today),
+        "last month" => first_of_month(parse_relative_date("-1m", today)?),
+        "next month" => first_of_month(parse_relative_date("+1m", today)?),
+        input => NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .ok()
+            .or_else(|| parse_iso_week(input, today))
+            .or_else(|| parse_relative_date(input, today)),
+    }
+}
+
+/// Get the week offset for a go to target, see `parse_go_to_date`
+pub fn get_week_offset_for_go_to(input: &str, timezone: &str, week_starts_on: &str) -> Option<i32> {
+    let today = get_today(timezone);
+    let date = parse_go_to_date(input, today)?;
+    Some(weeks_between(
+        today,
+        date,
+        string_to_weekday(week_starts_on),
+    ))
+}
+
+/// Get the week offset for a week, where a bare number is a week of the current year
+pub fn get_week_offset_for_week(input: &str, timezone: &str, week_starts_on: &str) -> Option<i32> {
+    let input = input.trim();
+    if !input.is_empty() && input.chars().all(|c| c.is_ascii_digit()) {
+        get_week_offset_for_go_to(&format!("W{}", input), timezone, week_starts_on)
+    } else {
+        get_week_offset_for_go_to(input, timezone, week_starts_on)
+    }
+}
+
+/// Parse a time typed in the edit form: `9`, `930`, `9:30`, `9.30`, `09:30` or `now`
+pub fn parse_time_input(input: &str, now: NaiveTime) -> Option<NaiveTime> {
+    let input = input.trim().to_lowercase();
+    if input == "now" {
+        return now.with_second(0)?.with_nanosecond(0);
+    }
+    let (hours, minutes) = match input.split_once([':', '.']) {
+        Some((hours, minutes)) if minutes.len() == 2 => (hours, minutes),
+        Some(_) => return None,
+        // A bare hour, or the minutes are the last two digits
+        None if input.len() <= 2 => (input.as_str(), "0"),
+        None => input.split_at(input.len() - 2),
+    };
+    if hours.is_empty() || !(hours.chars().chain(minutes.chars())).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    NaiveTime::from_hms_opt(hours.parse().ok()?, minutes.parse().ok()?, 0)
+}
+
+/// Parse a duration into minutes: `90`, `45m`, `1h`, `1.5h`, `1,5u`, `1h30m`, `1h30`, `1:30`
+/// or `+1:30`. Used by the `duration` search field and the end time in the edit form.
+pub fn parse_duration_minutes(input: &str) -> Option<u64> {
+    let input = input.trim().to_lowercase();
+    let input = input.strip_prefix('+').unwrap_or(&input);
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Some((hours, minutes)) = input.split_once(':') {
+        let hours: u64 = hours.parse().ok()?;
+        let minutes: u64 = minutes.parse().ok().filter(|minutes| *minutes < 60)?;
+        return hours.checked_mul(60)?.checked_add(minutes);
+    }
+
+    let mut total = 0.0;
+    let mut number = String::new();
+    let mut seen_hours = false;
+    let mut seen_minutes = false;
+    for c in input.chars() {
+        match c {
+            _ if seen_minutes => return None,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            'h' | 'u' if !number.is_empty() && !seen_hours => {
+                total += number.parse::<f64>().ok()? * 60.0;
+                number.clear();
+                seen_hours = true;
+            }
+            'm' if !number.is_empty() => seen_minutes = true,
+            _ => return None,
+        }
+    }
+
+    // The number left over is minutes, also without an `m` as in `1h30` or `90`
+    if !number.is_empty() {
+        let minutes = number.parse::<f64>().ok()?;
+        if seen_hours && minutes >= 60.0 {
+            return None;
+        }
+        total += minutes;
+    }
+
+    (total < 1e8).then(|| total.round() as u64)
+}
+
+/// Parse a date typed in the edit form. Next to everything `parse_go_to_date` accepts,
+/// a weekday (`mon`, `friday`) is the last such day, today included.
+pub fn parse_date_input(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let weekday = match input.trim().to_lowercase().as_str() {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    };
+    match weekday {
+        Some(weekday) => {
+            let days_back =
+                (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+            today.checked_sub_signed(Duration::days(days_back as i64))
+        }
+        None => parse_go_to_date(input, today),
+    }
+}
+
+/// Move a time by a number of 15 minute steps, snapping to the quarter first
+pub fn step_quarter_hour(datetime: NaiveDateTime, steps: i32) -> NaiveDateTime {
+    let minutes = datetime.minute() as i64;
+    let snapped =
+        datetime - Duration::minutes(minutes % 15) - Duration::seconds(datetime.second() as i64);
+    // Stepping down from 09:07 ends at 09:00, stepping up at 09:15
+    let steps = if steps < 0 && minutes % 15 != 0 {
+        steps as i64 + 1
+    } else {
+        steps as i64
+    };
+    snapped + Duration::minutes(15 * steps)
+}
+
 /// Get a relative week description for title display
 pub fn get_title_week_description(week_offset: i32) -> String {
     if week_offset == 0 {
@@ -599,4 +815,134 @@ mod tests {
             assert_eq!(prev_year, year, "Years should match");
         }
     }
+
+    #[test]
+    fn test_weeks_between() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        // Wednesday 2025-03-05 to Monday 2025-03-10 is the next week
+        assert_eq!(
+            weeks_between(date(2025, 3, 5), date(2025, 3, 10), Weekday::Mon),
+            1
+        );
+        // With weeks starting on Tuesday, Monday still belongs to the same week
+        assert_eq!(
+            weeks_between(date(2025, 3, 5), date(2025, 3, 10), Weekday::Tue),
+            0
+        );
+        // Across the year boundary
+        assert_eq!(
+            weeks_between(date(2025, 1, 2), date(2024, 12, 20), Weekday::Mon),
+            -2
+        );
+        assert_eq!(
+            weeks_between(date(2025, 1, 2), date(2025, 1, 2), Weekday::Sun),
+            0
+        );
+    }
+
+    #[test]
+    fn test_parse_go_to_date() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        let today = date(2025, 1, 15).unwrap();
+        assert_eq!(parse_go_to_date("2025-03-01", today), date(2025, 3, 1));
+        assert_eq!(parse_go_to_date("2024-W52", today), date(2024, 12, 23));
+        assert_eq!(parse_go_to_date("w1", today), date(2024, 12, 30));
+        assert_eq!(parse_go_to_date("-3w", today), date(2024, 12, 25));
+        assert_eq!(parse_go_to_date("+2d", today), date(2025, 1, 17));
+        assert_eq!(parse_go_to_date("-1y", today), date(2024, 1, 15));
+        assert_eq!(parse_go_to_date("Last  Month", today), date(2024, 12, 1));
+        assert_eq!(parse_go_to_date("next week", today), date(2025, 1, 22));
+        assert_eq!(parse_go_to_date("2025-W54", today), None);
+        assert_eq!(parse_go_to_date("3x", today), None);
+        assert_eq!(parse_go_to_date("", today), None);
+    }
+
+    #[test]
+    fn test_parse_time_input() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        let now = NaiveTime::from_hms_opt(14, 27, 31).unwrap();
+        assert_eq!(parse_time_input("9", now), time(9, 0));
+        assert_eq!(parse_time_input("930", now), time(9, 30));
+        assert_eq!(parse_time_input("1745", now), time(17, 45));
+        assert_eq!(parse_time_input("9.30", now), time(9, 30));
+        assert_eq!(parse_time_input(" 09:05 ", now), time(9, 5));
+        assert_eq!(parse_time_input("Now", now), time(14, 27));
+        assert_eq!(parse_time_input("24", now), None);
+        assert_eq!(parse_time_input("9:5", now), None);
+        assert_eq!(parse_time_input("9:60", now), None);
+        assert_eq!(parse_time_input("1h30", now), None);
+        assert_eq!(parse_time_input("", now), None);
+    }
+
+    #[test]
+    fn test_parse_duration_minutes() {
+        assert_eq!(parse_duration_minutes("90"), Some(90));
+        assert_eq!(parse_duration_minutes("45m"), Some(45));
+        assert_eq!(parse_duration_minutes("1h"), Some(60));
+        assert_eq!(parse_duration_minutes("2h"), Some(120));
+        assert_eq!(parse_duration_minutes("1.5h"), Some(90));
+        assert_eq!(parse_duration_minutes("1,5u"), Some(90));
+        assert_eq!(parse_duration_minutes("1h30m"), Some(90));
+        assert_eq!(parse_duration_minutes("1h30"), Some(90));
+        assert_eq!(parse_duration_minutes("1:30"), Some(90));
+        assert_eq!(parse_duration_minutes("+1:30"), Some(90));
+        assert_eq!(parse_duration_minutes("0m"), Some(0));
+        assert_eq!(parse_duration_minutes("1h75"), None);
+        assert_eq!(parse_duration_minutes("1:75"), None);
+        assert_eq!(parse_duration_minutes("45m30"), None);
+        assert_eq!(parse_duration_minutes("1x"), None);
+        assert_eq!(parse_duration_minutes("h"), None);
+        assert_eq!(parse_duration_minutes(""), None);
+    }
+
+    #[test]
+    fn test_parse_date_input() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        // A Wednesday
+        let today = date(2025, 1, 15).unwrap();
+        assert_eq!(parse_date_input("yesterday", today), date(2025, 1, 14));
+        assert_eq!(parse_date_input("mon", today), date(2025, 1, 13));
+        assert_eq!(parse_date_input("Wednesday", today), date(2025, 1, 15));
+        assert_eq!(parse_date_input("thu", today), date(2025, 1, 9));
+        assert_eq!(parse_date_input("2025-02-01", today), date(2025, 2, 1));
+        assert_eq!(parse_date_input("-2d", today), date(2025, 1, 13));
+        assert_eq!(parse_date_input("2025-02-30", today), None);
+    }
+
+    #[test]
+    fn test_step_quarter_hour() {
+        let at = |h, m| {
+            NaiveDate::from_ymd_opt(2025, 1, 15)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+        };
+        assert_eq!(step_quarter_hour(at(9, 0), 1), at(9, 15));
+        assert_eq!(step_quarter_hour(at(9, 0), -1), at(8, 45));
+        assert_eq!(step_quarter_hour(at(9, 7), 1), at(9, 15));
+        assert_eq!(step_quarter_hour(at(9, 7), -1), at(9, 0));
+        assert_eq!(
+            step_quarter_hour(at(23, 50), 1),
+            at(0, 0) + Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn test_get_week_offset_for_go_to() {
+        let (week, year) = get_week_number(0, "UTC", "monday");
+        let this_week = format!("{}-W{:02}", year, week);
+        assert_eq!(
+            get_week_offset_for_go_to(&this_week, "UTC", "monday"),
+            Some(0)
+        );
+        assert_eq!(
+            get_week_offset_for_go_to("last week", "UTC", "monday"),
+            Some(-1)
+        );
+        assert_eq!(
+            get_week_offset_for_go_to("-20w", "UTC", "monday"),
+            Some(-20)
+        );
+        assert_eq!(get_week_offset_for_go_to("W54", "UTC", "monday"), None);
+    }
 }
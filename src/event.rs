@@ -1,12 +1,14 @@
+use crate::keybindings::{Action, KeyMode};
 use crate::{ui, AppModel};
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 use rust_i18n::t;
 use std::time::Duration;
 
 #[derive(PartialEq, Clone)]
 pub enum Message {
     AutocompleteClearInput,
+    AutocompleteHide,
     AutocompleteKeyPress(KeyEvent),
     AutocompleteNextItem,
     AutocompletePreviousItem,
@@ -15,8 +17,33 @@ pub enum Message {
     AutocompleteResultsProject(Vec<crate::moneybird::types::Project>),
     AutocompleteSelect,
 
+    CommandPaletteComplete,
+    CommandPaletteExecute,
+    CommandPaletteHide,
+    CommandPaletteKeyPress(KeyEvent),
+    CommandPaletteSelectNext,
+    CommandPaletteSelectPrevious,
+    CommandPaletteShow,
+
+    BatchImportApplyCandidate,
+    BatchImportCandidateNext,
+    BatchImportCandidatePrevious,
+    BatchImportConfirm,
+    BatchImportEdit(crate::model::BatchImportTarget),
+    BatchImportEditCancel,
+    BatchImportHide,
+    BatchImportInputKeyPress(KeyEvent),
+    BatchImportMerge,
+    BatchImportSearchCandidates,
+    BatchImportSelectNext,
+    BatchImportSelectPrevious,
+    BatchImportShow,
+    BatchImportToggleAll,
+    BatchImportToggleCheck,
+
     ConfirmModal(String),
     DismissModal(String, bool),
+    AlternativeModal(String),
 
     // Debug message for plugin development
     DebugPluginResponse(String),
@@ -27,17 +54,28 @@ pub enum Message {
     EditTimeEntryKeyPress(KeyEvent),
     EditTimeEntryNextField,
     EditTimeEntryPreviousField,
+    EditTimeEntryStepTime(i32), // Number of 15 minute steps
     EditTimeEntrySelectContact,
     EditTimeEntrySelectProject,
 
     EditSave,
     EditCancel,
 
+    ExecuteBatchImport,
+    ExecuteDeleteSavedSearch(String),
     ExecuteDeleteTimeEntry(String),
     ExecuteExport,
+    ExecuteImportTimeEntry(Option<String>), // MoneyBird id to update, None imports a new entry
+    ExecuteLearnMappings(Vec<crate::config::MappingRule>),
+    ExecuteMergeOverlap(String, String), // Ids of the overlapping entries
+    ExecuteSubmitWeek,
+    ExecuteTrimOverlap(String, String),
+    ExecuteUnlockWeek,
 
     None, // Placeholder for no action needed
 
+    PluginEntriesLoaded, // The plugins answered the fetch started on refresh
+    PluginNotifications(Vec<crate::plugin::PluginNotification>),
     PluginViewActivate, // Activate the plugin view
     PluginViewShow,
     PluginViewHide,
@@ -48,13 +86,36 @@ pub enum Message {
 
     Quit,
 
+    SavedSearchApply(usize),
+    SavedSearchDelete,
+    SavedSearchNameCancel,
+    SavedSearchNameKeyPress(KeyEvent),
+    SavedSearchNameShow,
+    SavedSearchPickerHide,
+    SavedSearchPickerShow,
+    SavedSearchSave,
+    SavedSearchSelectNext,
+    SavedSearchSelectPrevious,
+    SavedSearchTogglePin,
+
     TimeEntryClearSearch,
     TimeEntryCreate,
     TimeEntryCurrentWeek,
     TimeEntryDelete,
     TimeEntryExport,
+    TimeEntryFilterCycleState,
+    TimeEntryFilterToggleContact,
+    TimeEntryFilterToggleProject,
+    TimeEntryFixConflict,
+    TimeEntryGoToShow,
+    TimeEntryGoToWeek(i32), // Week offset to navigate to
+    TimeEntryGroupCycle,
+    TimeEntryGroupToggle,
+    TimeEntryGroupToggleAll,
+    TimeEntryMergeFragments,
     TimeEntryNextWeek,
     TimeEntryPreviousWeek,
+    TimeEntryRedo,
     TimeEntryRefresh,
     TimeEntrySearchHide,
     TimeEntrySearchKeyPress(KeyEvent),
@@ -62,12 +123,19 @@ pub enum Message {
     TimeEntrySelectNext,
     TimeEntrySelectPrevious,
     TimeEntrySelectRow(usize),
+    TimeEntrySortBy(crate::config::SortColumn),
+    TimeEntrySortNextColumn,
+    TimeEntrySortToggleDirection,
+    TimeEntrySubmitWeek,
+    TimeEntryUndo,
 
     ToggleLogPanel,
 
     UserConfirmSelection,
     UserSelectNext,
     UserSelectPrevious,
+    UserSwitcherHide,
+    UserSwitcherShow,
 
     // Import a plugin time entry to Moneybird
     ImportTimeEntry,
@@ -138,6 +206,23 @@ pub fn handle_event(model: &mut AppModel) -> Result<Option<Message>> {
             _ => Ok(None), // Ignore other event types
         }
     } else {
+        // Plugins can send notifications at any time
+        if let Some(plugin_manager) = model.plugin_manager.as_mut() {
+            let notifications = plugin_manager.take_notifications();
+            if !notifications.is_empty() {
+                return Ok(Some(Message::PluginNotifications(notifications)));
+            }
+        }
+
+        // Plugin entries fetched in the background are added once all plugins answered
+        if model
+            .plugin_fetch
+            .as_ref()
+            .is_some_and(|fetch| fetch.is_finished())
+        {
+            return Ok(Some(Message::PluginEntriesLoaded));
+        }
+
         // Poll timed out - check for autocomplete debounce completion
         if model.edit_state.active {
             let should_refresh = match model.edit_state.selected_field {
@@ -149,6 +234,17 @@ pub fn handle_event(model: &mut AppModel) -> Result<Option<Message>> {
                     .edit_state
                     .contact_autocomplete
                     .check_debounce_timeout(),
+                // Descriptions are only suggested by plugins
+                crate::model::EditField::Description => {
+                    model
+                        .plugin_manager
+                        .as_ref()
+                        .is_some_and(|plugin_manager| plugin_manager.has_suggestions())
+                        && model
+                            .edit_state
+                            .description_autocomplete
+                            .check_debounce_timeout()
+                }
                 _ => false, // Not in an autocomplete field
             };
 
@@ -161,37 +257,64 @@ pub fn handle_event(model: &mut AppModel) -> Result<Option<Message>> {
                 return Ok(Some(Message::AutocompleteRefresh));
             }
         }
+
+        // The batch import searches the contacts once typing pauses
+        let batch_import_state = &model.batch_import_state;
+        let should_search = match batch_import_state.editing {
+            Some(crate::model::BatchImportTarget::Contact) => batch_import_state
+                .contact_autocomplete
+                .check_debounce_timeout(),
+            Some(crate::model::BatchImportTarget::Project) => batch_import_state
+                .project_autocomplete
+                .check_debounce_timeout(),
+            None => false,
+        };
+        if should_search {
+            return Ok(Some(Message::BatchImportSearchCandidates));
+        }
         Ok(None)
     }
 }
 
 fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
     // Toggle log panel key pressed, this works in any mode
-    if key.code == KeyCode::F(12) {
+    if model.keybindings.action(KeyMode::Global, &key) == Some(Action::ToggleLogPanel) {
         model.log_notice(t!("event_toggle_log_panel"));
         return Some(Message::ToggleLogPanel);
     }
 
+    // --- User Switcher Handling (reuses the user selection list) ---
+    if model.user_switcher_active {
+        return match model.keybindings.action(KeyMode::Users, &key) {
+            Some(Action::SelectPrevious) => Some(Message::UserSelectPrevious),
+            Some(Action::SelectNext) => Some(Message::UserSelectNext),
+            Some(Action::Confirm) => Some(Message::UserConfirmSelection),
+            Some(Action::Close) => Some(Message::UserSwitcherHide),
+            Some(Action::Quit) => Some(Message::Quit), // Allow quitting
+            _ => None,                                 // Ignore other keys in this mode
+        };
+    }
+
     // --- User Selection Mode Handling ---
     if model.user_selection_active {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => return Some(Message::UserSelectPrevious),
-            KeyCode::Down | KeyCode::Char('j') => return Some(Message::UserSelectNext),
-            KeyCode::Enter => return Some(Message::UserConfirmSelection),
-            KeyCode::Char('q') => return Some(Message::Quit), // Allow quitting
-            _ => return None,                                 // Ignore other keys in this mode
-        }
+        return match model.keybindings.action(KeyMode::Users, &key) {
+            Some(Action::SelectPrevious) => Some(Message::UserSelectPrevious),
+            Some(Action::SelectNext) => Some(Message::UserSelectNext),
+            Some(Action::Confirm) => Some(Message::UserConfirmSelection),
+            Some(Action::Quit) => Some(Message::Quit), // Allow quitting
+            _ => None, // A user has to be selected, so there is nothing to close
+        };
     }
 
     // --- Plugin View Mode Handling ---
     if model.plugin_view_state.active {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => return Some(Message::PluginViewSelectPrevious),
-            KeyCode::Down | KeyCode::Char('j') => return Some(Message::PluginViewSelectNext),
-            KeyCode::Esc | KeyCode::Char('p') => return Some(Message::PluginViewHide),
-            KeyCode::Char('q') => return Some(Message::Quit), // Allow quitting
-            KeyCode::Char(' ') => return Some(Message::PluginToggleActivation), // Space to toggle
-            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        match model.keybindings.action(KeyMode::Plugins, &key) {
+            Some(Action::SelectPrevious) => return Some(Message::PluginViewSelectPrevious),
+            Some(Action::SelectNext) => return Some(Message::PluginViewSelectNext),
+            Some(Action::Close) => return Some(Message::PluginViewHide),
+            Some(Action::Quit) => return Some(Message::Quit), // Allow quitting
+            Some(Action::ToggleActivation) => return Some(Message::PluginToggleActivation),
+            Some(Action::Debug) => {
                 // Debug selected plugin
                 if let Some(idx) = model.plugin_view_state.selected_index {
                     let plugins = if let Some(manager) = &model.plugin_manager {
@@ -222,8 +345,8 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
             .top()
             .map(|modal| (modal.id.clone().unwrap_or_default(), modal.modal_type));
 
-        match key.code {
-            KeyCode::Enter | KeyCode::Char('y') => {
+        match model.keybindings.action(KeyMode::Modal, &key) {
+            Some(Action::Confirm) => {
                 if let Some((modal_id, modal_type)) = modal_info {
                     model.log_debug(t!(
                         "event_confirming_modal",
@@ -238,7 +361,7 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
                 }
                 return None;
             }
-            KeyCode::Esc | KeyCode::Char('n') => {
+            Some(Action::Cancel) => {
                 if let Some((modal_id, _)) = modal_info {
                     model.log_debug(t!(
                         "event_cancelling_modal",
@@ -249,7 +372,18 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
                 }
                 return Some(Message::DismissModal(String::new(), true));
             }
-            KeyCode::Char(' ') | KeyCode::Tab | KeyCode::BackTab => {
+            Some(Action::Alternative) => {
+                if let Some((modal_id, _)) = modal_info {
+                    model.log_debug(t!(
+                        "event_confirming_modal",
+                        modal_id = modal_id,
+                        key = format!("{:?}", key)
+                    ));
+                    return Some(Message::AlternativeModal(modal_id));
+                }
+                return None;
+            }
+            Some(Action::Dismiss) => {
                 if let Some((modal_id, _)) = modal_info {
                     model.log_debug(t!(
                         "event_dismissing_modal",
@@ -264,21 +398,130 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
         }
     }
 
+    // --- Batch Import Handling ---
+    if model.batch_import_state.active {
+        // Picking a contact or project takes the keys until it is applied or cancelled
+        if model.batch_import_state.editing.is_some() {
+            return match key.code {
+                KeyCode::Enter => Some(Message::BatchImportApplyCandidate),
+                KeyCode::Esc => Some(Message::BatchImportEditCancel),
+                KeyCode::Down => Some(Message::BatchImportCandidateNext),
+                KeyCode::Up => Some(Message::BatchImportCandidatePrevious),
+                _ => Some(Message::BatchImportInputKeyPress(key)),
+            };
+        }
+        return match model.keybindings.action(KeyMode::BatchImport, &key) {
+            Some(Action::SelectPrevious) => Some(Message::BatchImportSelectPrevious),
+            Some(Action::SelectNext) => Some(Message::BatchImportSelectNext),
+            Some(Action::Check) => Some(Message::BatchImportToggleCheck),
+            Some(Action::CheckAll) => Some(Message::BatchImportToggleAll),
+            Some(Action::EditContact) => Some(Message::BatchImportEdit(
+                crate::model::BatchImportTarget::Contact,
+            )),
+            Some(Action::EditProject) => Some(Message::BatchImportEdit(
+                crate::model::BatchImportTarget::Project,
+            )),
+            Some(Action::MergeFragments) => Some(Message::BatchImportMerge),
+            Some(Action::Confirm) => Some(Message::BatchImportConfirm),
+            Some(Action::Close) => Some(Message::BatchImportHide),
+            Some(Action::Quit) => Some(Message::Quit),
+            _ => None,
+        };
+    }
+
+    // --- Command Palette Handling ---
+    if model.command_palette_state.active {
+        return match model.keybindings.action(KeyMode::Palette, &key) {
+            Some(Action::Confirm) => Some(Message::CommandPaletteExecute),
+            Some(Action::Complete) => Some(Message::CommandPaletteComplete),
+            Some(Action::Close) => Some(Message::CommandPaletteHide),
+            Some(Action::SelectNext) => Some(Message::CommandPaletteSelectNext),
+            Some(Action::SelectPrevious) => Some(Message::CommandPaletteSelectPrevious),
+            _ => Some(Message::CommandPaletteKeyPress(key)),
+        };
+    }
+
+    // --- Saved Search Picker Handling ---
+    if model.saved_search_state.picker_active {
+        return match model.keybindings.action(KeyMode::SavedSearches, &key) {
+            Some(Action::SelectPrevious) => Some(Message::SavedSearchSelectPrevious),
+            Some(Action::SelectNext) => Some(Message::SavedSearchSelectNext),
+            Some(Action::Apply) => model
+                .saved_search_state
+                .list_state
+                .selected()
+                .map(Message::SavedSearchApply),
+            Some(Action::Pin) => Some(Message::SavedSearchTogglePin),
+            Some(Action::Delete) => Some(Message::SavedSearchDelete),
+            Some(Action::Close) => Some(Message::SavedSearchPickerHide),
+            Some(Action::Quit) => Some(Message::Quit),
+            _ => match key.code {
+                KeyCode::Char(c @ '1'..='9') => {
+                    Some(Message::SavedSearchApply(c as usize - '1' as usize))
+                }
+                _ => None,
+            },
+        };
+    }
+
+    // --- Naming a search before saving it ---
+    if model.saved_search_state.name_input.is_some() {
+        return match key.code {
+            KeyCode::Enter => Some(Message::SavedSearchSave),
+            KeyCode::Esc => Some(Message::SavedSearchNameCancel),
+            _ => Some(Message::SavedSearchNameKeyPress(key)),
+        };
+    }
+
     // --- Refactored Edit State Key Handling (Regular Edit or Import Edit) ---
     if model.edit_state.active {
         let edit_state = &model.edit_state;
-
-        match key.code {
-            // --- Global Edit Keys ---
-            KeyCode::Char('s') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+        let is_autocomplete_field = matches!(
+            edit_state.selected_field,
+            crate::model::EditField::Project | crate::model::EditField::Contact
+        );
+        let is_time_field = matches!(
+            edit_state.selected_field,
+            crate::model::EditField::StartTime | crate::model::EditField::EndTime
+        );
+        let is_suggesting_description = edit_state.selected_field
+            == crate::model::EditField::Description
+            && edit_state.description_autocomplete.is_dropdown_visible;
+
+        // --- Global Edit Keys ---
+        match model.keybindings.action(KeyMode::Edit, &key) {
+            Some(Action::Save) => {
                 model.log_debug(t!("event_saving_edit"));
-                Some(Message::EditSave)
+                return Some(Message::EditSave);
+            }
+            // Closing the description suggestions keeps the edit open
+            Some(Action::Cancel) if is_suggesting_description => {
+                return Some(Message::AutocompleteHide)
+            }
+            Some(Action::Cancel) => return Some(Message::EditCancel),
+            Some(Action::NextField) => return Some(Message::EditTimeEntryNextField),
+            Some(Action::PreviousField) => return Some(Message::EditTimeEntryPreviousField),
+            Some(Action::ClearInput) if is_autocomplete_field => {
+                return Some(Message::AutocompleteClearInput)
+            }
+            Some(Action::ClearInput) => return Some(Message::EditTimeEntryKeyPress(key)),
+            Some(Action::IncreaseTime) if is_time_field => {
+                return Some(Message::EditTimeEntryStepTime(1))
             }
+            Some(Action::DecreaseTime) if is_time_field => {
+                return Some(Message::EditTimeEntryStepTime(-1))
+            }
+            _ => {}
+        }
+
+        match key.code {
             KeyCode::Enter => {
                 match model.edit_state.selected_field {
                     crate::model::EditField::Description => {
                         if key.modifiers.contains(event::KeyModifiers::SHIFT) {
                             Some(Message::EditTimeEntryKeyPress(key)) // Let the textarea handle it
+                        } else if is_suggesting_description {
+                            Some(Message::AutocompleteSelect)
                         } else {
                             None
                         }
@@ -300,8 +543,6 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
                     _ => None, // Default for other fields
                 }
             }
-            KeyCode::Tab => Some(Message::EditTimeEntryNextField),
-            KeyCode::BackTab => Some(Message::EditTimeEntryPreviousField),
 
             // --- Keys with Field-Dependent Behavior ---
             KeyCode::Up => {
@@ -319,6 +560,7 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
                             None // No action if dropdown not visible
                         }
                     }
+                    _ if is_suggesting_description => Some(Message::AutocompletePreviousItem),
                     _ => Some(Message::EditTimeEntryKeyPress(key)),
                 }
             }
@@ -339,15 +581,7 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
                             None
                         }
                     }
-                    _ => Some(Message::EditTimeEntryKeyPress(key)),
-                }
-            }
-            KeyCode::Esc => Some(Message::EditCancel),
-            KeyCode::Char('u') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                match edit_state.selected_field {
-                    crate::model::EditField::Project | crate::model::EditField::Contact => {
-                        Some(Message::AutocompleteClearInput)
-                    }
+                    _ if is_suggesting_description => Some(Message::AutocompleteNextItem),
                     _ => Some(Message::EditTimeEntryKeyPress(key)),
                 }
             }
@@ -374,47 +608,73 @@ fn handle_key(key: event::KeyEvent, model: &mut AppModel) -> Option<Message> {
     } else {
         // --- Handling for Non-Edit State ---
         if model.search_state.active {
-            match key.code {
-                KeyCode::Char('u') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                    Some(Message::TimeEntryClearSearch)
-                }
-                KeyCode::Char('x') => Some(Message::TimeEntryExport),
-                KeyCode::F(4) => Some(Message::EditTimeEntry),
-                KeyCode::Up => Some(Message::TimeEntrySelectPrevious),
-                KeyCode::Down => Some(Message::TimeEntrySelectNext),
-                KeyCode::Left => Some(Message::TimeEntryPreviousWeek),
-                KeyCode::Right => Some(Message::TimeEntryNextWeek),
-                KeyCode::Esc => Some(Message::TimeEntrySearchHide),
-                _ => Some(Message::TimeEntrySearchKeyPress(key)),
-            }
+            model
+                .keybindings
+                .action(KeyMode::Search, &key)
+                .and_then(|action| action_message(KeyMode::Search, action))
+                .or(Some(Message::TimeEntrySearchKeyPress(key)))
         } else {
-            match key.code {
-                KeyCode::Char('x') => Some(Message::TimeEntryExport),
-                KeyCode::F(4) => Some(Message::EditTimeEntry),
-                KeyCode::Char('h') | KeyCode::Left => Some(Message::TimeEntryPreviousWeek),
-                KeyCode::Char('j') | KeyCode::Down => Some(Message::TimeEntrySelectNext),
-                KeyCode::Char('k') | KeyCode::Up => Some(Message::TimeEntrySelectPrevious),
-                KeyCode::Char('l') | KeyCode::Right => Some(Message::TimeEntryNextWeek),
-                KeyCode::Char('p') => Some(Message::PluginViewShow),
-                KeyCode::Char('i') => Some(Message::ImportTimeEntry),
-                KeyCode::Char('q') => Some(Message::Quit),
-                KeyCode::Char('e') | KeyCode::Char(' ') | KeyCode::Enter => {
-                    Some(Message::EditTimeEntry)
-                }
-                KeyCode::Char('c') => Some(Message::TimeEntryCreate),
-                KeyCode::Char('t') => Some(Message::TimeEntryCurrentWeek),
-                KeyCode::Char('r') => Some(Message::TimeEntryRefresh),
-                KeyCode::Char('f') | KeyCode::Char('/') => Some(Message::TimeEntrySearchShow),
-                KeyCode::Char('d') | KeyCode::Delete => Some(Message::TimeEntryDelete),
-                _ => None,
+            match model.keybindings.action(KeyMode::Normal, &key) {
+                Some(action) => action_message(KeyMode::Normal, action),
+                None => match key.code {
+                    // The saved searches are always on the number keys
+                    KeyCode::Char(c @ '1'..='9') => {
+                        Some(Message::SavedSearchApply(c as usize - '1' as usize))
+                    }
+                    _ => None,
+                },
             }
         }
     }
 }
 
+/// The message of an action in the time entry view, used for the key bindings and the
+/// command palette
+pub(crate) fn action_message(mode: KeyMode, action: Action) -> Option<Message> {
+    let message = match (mode, action) {
+        (KeyMode::Search, Action::Close) => Message::TimeEntrySearchHide,
+        (KeyMode::Search, Action::ClearSearch) => Message::TimeEntryClearSearch,
+        (KeyMode::Search, Action::SaveSearch) => Message::SavedSearchNameShow,
+        (_, Action::Export) => Message::TimeEntryExport,
+        (_, Action::PreviousWeek) => Message::TimeEntryPreviousWeek,
+        (_, Action::NextWeek) => Message::TimeEntryNextWeek,
+        (_, Action::CurrentWeek) => Message::TimeEntryCurrentWeek,
+        (_, Action::SelectNext) => Message::TimeEntrySelectNext,
+        (_, Action::SelectPrevious) => Message::TimeEntrySelectPrevious,
+        (_, Action::Plugins) => Message::PluginViewShow,
+        (_, Action::Import) => Message::ImportTimeEntry,
+        (_, Action::Quit) => Message::Quit,
+        (_, Action::Edit) => Message::EditTimeEntry,
+        (_, Action::Create) => Message::TimeEntryCreate,
+        (_, Action::Refresh) => Message::TimeEntryRefresh,
+        (_, Action::Search) => Message::TimeEntrySearchShow,
+        (_, Action::Delete) => Message::TimeEntryDelete,
+        (_, Action::CycleState) => Message::TimeEntryFilterCycleState,
+        (_, Action::FilterContact) => Message::TimeEntryFilterToggleContact,
+        (_, Action::FilterProject) => Message::TimeEntryFilterToggleProject,
+        (_, Action::SwitchUser) => Message::UserSwitcherShow,
+        (_, Action::SubmitWeek) => Message::TimeEntrySubmitWeek,
+        (_, Action::SavedSearches) => Message::SavedSearchPickerShow,
+        (_, Action::SortNextColumn) => Message::TimeEntrySortNextColumn,
+        (_, Action::SortDirection) => Message::TimeEntrySortToggleDirection,
+        (_, Action::Group) => Message::TimeEntryGroupCycle,
+        (_, Action::ToggleGroup) => Message::TimeEntryGroupToggle,
+        (_, Action::ToggleAllGroups) => Message::TimeEntryGroupToggleAll,
+        (_, Action::CommandPalette) => Message::CommandPaletteShow,
+        (_, Action::GoTo) => Message::TimeEntryGoToShow,
+        (_, Action::Undo) => Message::TimeEntryUndo,
+        (_, Action::Redo) => Message::TimeEntryRedo,
+        (_, Action::FixConflict) => Message::TimeEntryFixConflict,
+        (_, Action::BatchImport) => Message::BatchImportShow,
+        (_, Action::MergeFragments) => Message::TimeEntryMergeFragments,
+        _ => return None,
+    };
+    Some(message)
+}
+
 fn handle_mouse(mouse: event::MouseEvent, model: &mut AppModel) -> Option<Message> {
-    if !model.modal_stack.is_empty() {
-        // Ignore mouse events when a modal is open
+    if !model.modal_stack.is_empty() || model.command_palette_state.active {
+        // Ignore mouse events when a modal or the command palette is open
         return None;
     }
 
@@ -443,6 +703,15 @@ fn handle_mouse(mouse: event::MouseEvent, model: &mut AppModel) -> Option<Messag
         return None;
     }
 
+    // The batch import only scrolls with the mouse
+    if model.batch_import_state.active {
+        return match mouse.kind {
+            MouseEventKind::ScrollDown => Some(Message::BatchImportSelectNext),
+            MouseEventKind::ScrollUp => Some(Message::BatchImportSelectPrevious),
+            _ => None,
+        };
+    }
+
     // Handle plugin view mouse events
     if model.plugin_view_state.active {
         match mouse.kind {
@@ -477,7 +746,7 @@ fn handle_mouse(mouse: event::MouseEvent, model: &mut AppModel) -> Option<Messag
     }
 
     // Handle user selection view mouse events
-    if model.user_selection_active {
+    if model.user_selection_active || model.user_switcher_active {
         match mouse.kind {
             MouseEventKind::ScrollDown => return Some(Message::UserSelectPrevious),
             MouseEventKind::ScrollUp => return Some(Message::UserSelectNext),
@@ -488,6 +757,15 @@ fn handle_mouse(mouse: event::MouseEvent, model: &mut AppModel) -> Option<Messag
         }
     }
 
+    // Handle saved search picker mouse events
+    if model.saved_search_state.picker_active {
+        return match mouse.kind {
+            MouseEventKind::ScrollDown => Some(Message::SavedSearchSelectPrevious),
+            MouseEventKind::ScrollUp => Some(Message::SavedSearchSelectNext),
+            _ => None, // Ignore other mouse events in the picker
+        };
+    }
+
     // Handle normal mode clicks time entry table
     match mouse.kind {
         MouseEventKind::ScrollDown => Some(Message::TimeEntrySelectPrevious),
@@ -499,13 +777,35 @@ fn handle_mouse(mouse: event::MouseEvent, model: &mut AppModel) -> Option<Messag
                     x: mouse.column,
                     y: mouse.row,
                 }) {
+                    // The header row sits right below the top border
+                    if mouse.row == table_area.y + 1 {
+                        return model
+                            .table_header_columns
+                            .iter()
+                            .find(|(start, end, _)| (*start..*end).contains(&mouse.column))
+                            .map(|(_, _, column)| Message::TimeEntrySortBy(*column));
+                    }
+
+                    // Grouped tables scroll over the visible rows including the headers
+                    let (offset, row_count) =
+                        if model.group_state.group_by == crate::model::GroupBy::None {
+                            (
+                                model.time_entry_table_state.offset(),
+                                model.time_entries_for_table.len(),
+                            )
+                        } else {
+                            (
+                                model.group_state.table_state.offset(),
+                                model.table_rows().len(),
+                            )
+                        };
+
                     // Adjust for table header (1 row) and top border (1 row)
                     let relative_row = mouse.row.saturating_sub(table_area.y + 2);
-                    let selected_index =
-                        model.time_entry_table_state.offset() + relative_row as usize;
+                    let selected_index = offset + relative_row as usize;
 
                     // Ensure the calculated index is within the bounds of the data
-                    if selected_index < model.time_entries_for_table.len() {
+                    if selected_index < row_count {
                         model.log_debug(t!(
                             "event_mouse_click_detected",
                             row = relative_row.to_string(),
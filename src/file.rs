@@ -1,4 +1,4 @@
-use crate::{datetime, AppModel};
+use crate::{datetime, query::Query, AppModel, TimeEntryForTable};
 use rust_i18n::t;
 use std::fs::File;
 use std::io::Write;
@@ -11,6 +11,15 @@ pub async fn handle_export_command(
 ) -> color_eyre::Result<()> {
     println!("{}", t!("file_exporting_for_week", week_arg = week_arg));
 
+    // Validate the query before fetching anything
+    if let Err(err) = Query::parse(&query_arg) {
+        return Err(color_eyre::eyre::eyre!(t!(
+            "file_invalid_query",
+            query = query_arg,
+            error = err
+        )));
+    }
+
     // Get administration timezone, default to UTC if not set
     let admin_timezone_str = model
         .administration
@@ -18,22 +27,22 @@ pub async fn handle_export_command(
         .clone()
         .unwrap_or_else(|| "UTC".to_string());
 
-    // Get current week number to calculate offset
-    let (current_week_num, _) = datetime::get_week_number(
-        model.week_offset,
-        &admin_timezone_str,
-        &model.config.week_starts_on,
-    );
-
-    let target_week: i32;
-    // Calculate week offset based on requested week number
+    // Calculate week offset based on the requested week, which may be in another year
     if week_arg == "current week" {
-        target_week = current_week_num;
         model.week_offset = 0;
     } else {
-        target_week = week_arg.parse::<i32>().unwrap_or(current_week_num);
-        model.week_offset = target_week - current_week_num;
+        model.week_offset = datetime::get_week_offset_for_week(
+            &week_arg,
+            &admin_timezone_str,
+            &model.config.week_starts_on,
+        )
+        .ok_or_else(|| color_eyre::eyre::eyre!(t!("file_invalid_week", week = week_arg)))?;
     }
+    let (target_week, _) = datetime::get_week_number(
+        model.week_offset,
+        &admin_timezone_str,
+        &model.config.week_starts_on,
+    );
 
     // Fetch time entries for the specified week
     println!("{}", t!("file_fetching_for_week", target_week = target_week));
@@ -99,6 +108,15 @@ pub async fn handle_export_command(
 pub fn export_time_entries_to_csv(
     model: &AppModel,
     filename: &str,
+) -> color_eyre::Result<(), String> {
+    export_entries_to_csv(model, &model.time_entries_for_table, filename)
+}
+
+/// Export the given time entries to a CSV file
+pub fn export_entries_to_csv(
+    model: &AppModel,
+    entries: &[TimeEntryForTable],
+    filename: &str,
 ) -> color_eyre::Result<(), String> {
     // Create a new file
     let mut file = match File::create(filename) {
@@ -113,7 +131,7 @@ pub fn export_time_entries_to_csv(
     }
 
     // Only export Moneybird time entries
-    let moneybird_entries = model.time_entries_for_table
+    let moneybird_entries = entries
         .iter()
         .filter(|entry| entry.source.to_lowercase() == "moneybird");
 
@@ -133,9 +151,9 @@ pub fn export_time_entries_to_csv(
         let start_time = datetime::format_time(&entry.started_at, &admin_timezone_str);
         let end_time = datetime::format_time(&entry.ended_at, &admin_timezone_str);
 
-        // Calculate duration
-        let (hours, minutes) = datetime::calculate_duration(&entry.started_at, &entry.ended_at);
-        let duration = format!("{:02}:{:02}", hours, minutes);
+        // Calculate duration, rounded when the contact bills in increments
+        let rounded_minutes = model.rounded_minutes(entry);
+        let duration = format!("{:02}:{:02}", rounded_minutes / 60, rounded_minutes % 60);
 
         // Format CSV line (with proper escaping for any fields that might contain commas)
         let line = format!(
@@ -0,0 +1,135 @@
+// Scores used for fuzzy matching, an exact substring always beats a scattered match
+const SCORE_MATCH: i64 = 2;
+const BONUS_CONSECUTIVE: i64 = 3;
+const BONUS_WORD_START: i64 = 4;
+const BONUS_SUBSTRING: i64 = 8;
+const PENALTY_MISS: i64 = 4;
+const PENALTY_GAP_MAX: i64 = 3;
+
+/// Result of a successful fuzzy match
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FuzzyMatch {
+    pub(crate) score: i64,
+    pub(crate) positions: Vec<usize>, // Char indices of the matched characters in the text
+}
+
+fn lowercase_chars(text: &str) -> Vec<char> {
+    // Map char by char so the indices stay aligned with the original text
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+fn is_word_start(chars: &[char], idx: usize) -> bool {
+    idx == 0 || !chars[idx - 1].is_alphanumeric()
+}
+
+/// Fuzzy match `pattern` against `text`, case-insensitive
+///
+/// Exact substrings score best. Otherwise the pattern characters are matched in
+/// order, and about one in four pattern characters may be missing to allow for typos
+/// such as "acmee". Returns `None` when the text doesn't match well enough.
+pub(crate) fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let pattern = lowercase_chars(pattern.trim());
+    if pattern.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+
+    let chars = lowercase_chars(text);
+    let pattern_len = pattern.len() as i64;
+
+    // Exact substring
+    if let Some(start) = chars
+        .windows(pattern.len())
+        .position(|window| window == pattern.as_slice())
+    {
+        let mut score =
+            pattern_len * SCORE_MATCH + (pattern_len - 1) * BONUS_CONSECUTIVE + BONUS_SUBSTRING;
+        if is_word_start(&chars, start) {
+            score += BONUS_WORD_START;
+        }
+        return Some(FuzzyMatch {
+            score,
+            positions: (start..start + pattern.len()).collect(),
+        });
+    }
+
+    // Characters in order, skipping the ones that can't be found
+    let mut positions: Vec<usize> = Vec::new();
+    let mut score = 0;
+    let mut misses = 0;
+    let mut skipped = 0;
+    let mut next_idx = 0;
+    for pattern_char in &pattern {
+        let Some(offset) = chars[next_idx..].iter().position(|c| c == pattern_char) else {
+            misses += 1;
+            score -= PENALTY_MISS;
+            continue;
+        };
+
+        let idx = next_idx + offset;
+        score += SCORE_MATCH;
+        match positions.last() {
+            Some(&last) if last + 1 == idx => score += BONUS_CONSECUTIVE,
+            Some(&last) => {
+                skipped += idx - last - 1;
+                score -= ((idx - last - 1) as i64).min(PENALTY_GAP_MAX);
+            }
+            None => {}
+        }
+        if is_word_start(&chars, idx) {
+            score += BONUS_WORD_START;
+        }
+
+        positions.push(idx);
+        next_idx = idx + 1;
+    }
+
+    // Reject typos beyond the allowance and characters scattered all over the text
+    if positions.is_empty()
+        || misses > pattern.len() / 4
+        || skipped > pattern.len()
+        || score < pattern_len * SCORE_MATCH
+    {
+        return None;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_substring() {
+        let result = fuzzy_match("acme", "The Acme Company").unwrap();
+        assert_eq!(result.positions, vec![4, 5, 6, 7]);
+        assert!(fuzzy_match("", "anything").is_some());
+    }
+
+    #[test]
+    fn test_typo_tolerance() {
+        assert!(fuzzy_match("acmee", "Acme").is_some());
+        assert!(fuzzy_match("amce", "Acme").is_some());
+        assert!(fuzzy_match("acm", "Globex").is_none());
+        // Short patterns don't allow missing characters
+        assert!(fuzzy_match("acx", "Acme").is_none());
+    }
+
+    #[test]
+    fn test_scattered_matches_are_rejected() {
+        assert!(fuzzy_match("acme", "a big cat meets everyone").is_none());
+        assert!(fuzzy_match("wbshp", "Web shop").is_some());
+    }
+
+    #[test]
+    fn test_scores_prefer_better_matches() {
+        let exact = fuzzy_match("acme", "Acme").unwrap();
+        let typo = fuzzy_match("acmee", "Acme").unwrap();
+        let word_start = fuzzy_match("web", "Web shop").unwrap();
+        let inner = fuzzy_match("web", "Cobweb").unwrap();
+        assert!(exact.score > typo.score);
+        assert!(word_start.score > inner.score);
+    }
+}
@@ -0,0 +1,744 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rust_i18n::t;
+use std::collections::BTreeMap;
+
+/// Part of the app that has its own set of key bindings
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum KeyMode {
+    Global,
+    #[default]
+    Normal,
+    Search,
+    Edit,
+    Plugins,
+    Users,
+    SavedSearches,
+    Palette,
+    BatchImport,
+    Modal,
+}
+
+const MODES: [KeyMode; 10] = [
+    KeyMode::Global,
+    KeyMode::Normal,
+    KeyMode::Search,
+    KeyMode::Edit,
+    KeyMode::Plugins,
+    KeyMode::Users,
+    KeyMode::SavedSearches,
+    KeyMode::Palette,
+    KeyMode::BatchImport,
+    KeyMode::Modal,
+];
+
+impl KeyMode {
+    /// Name of the mode in the `[keybindings]` configuration section
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            KeyMode::Global => "global",
+            KeyMode::Normal => "normal",
+            KeyMode::Search => "search",
+            KeyMode::Edit => "edit",
+            KeyMode::Plugins => "plugins",
+            KeyMode::Users => "users",
+            KeyMode::SavedSearches => "saved_searches",
+            KeyMode::Palette => "palette",
+            KeyMode::BatchImport => "batch_import",
+            KeyMode::Modal => "modal",
+        }
+    }
+}
+
+/// Something a key binding can do
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Action {
+    ToggleLogPanel,
+    Quit,
+    SelectNext,
+    SelectPrevious,
+    PreviousWeek,
+    NextWeek,
+    CurrentWeek,
+    Edit,
+    Create,
+    Delete,
+    Export,
+    Import,
+    Plugins,
+    Refresh,
+    Search,
+    CycleState,
+    FilterContact,
+    FilterProject,
+    SwitchUser,
+    SubmitWeek,
+    SavedSearches,
+    SortNextColumn,
+    SortDirection,
+    Group,
+    ToggleGroup,
+    ToggleAllGroups,
+    ClearSearch,
+    SaveSearch,
+    Close,
+    Save,
+    Cancel,
+    NextField,
+    PreviousField,
+    ClearInput,
+    ToggleActivation,
+    Debug,
+    Confirm,
+    Apply,
+    Pin,
+    Dismiss,
+    CommandPalette,
+    Complete,
+    GoTo,
+    Undo,
+    Redo,
+    IncreaseTime,
+    DecreaseTime,
+    FixConflict,
+    Alternative,
+    BatchImport,
+    Check,
+    CheckAll,
+    EditContact,
+    EditProject,
+    MergeFragments,
+}
+
+impl Action {
+    /// Name of the action in the `[keybindings]` configuration section
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Action::ToggleLogPanel => "toggle_log_panel",
+            Action::Quit => "quit",
+            Action::SelectNext => "select_next",
+            Action::SelectPrevious => "select_previous",
+            Action::PreviousWeek => "previous_week",
+            Action::NextWeek => "next_week",
+            Action::CurrentWeek => "current_week",
+            Action::Edit => "edit",
+            Action::Create => "create",
+            Action::Delete => "delete",
+            Action::Export => "export",
+            Action::Import => "import",
+            Action::Plugins => "plugins",
+            Action::Refresh => "refresh",
+            Action::Search => "search",
+            Action::CycleState => "cycle_state",
+            Action::FilterContact => "filter_contact",
+            Action::FilterProject => "filter_project",
+            Action::SwitchUser => "switch_user",
+            Action::SubmitWeek => "submit_week",
+            Action::SavedSearches => "saved_searches",
+            Action::SortNextColumn => "sort_next_column",
+            Action::SortDirection => "sort_direction",
+            Action::Group => "group",
+            Action::ToggleGroup => "toggle_group",
+            Action::ToggleAllGroups => "toggle_all_groups",
+            Action::ClearSearch => "clear_search",
+            Action::SaveSearch => "save_search",
+            Action::Close => "close",
+            Action::Save => "save",
+            Action::Cancel => "cancel",
+            Action::NextField => "next_field",
+            Action::PreviousField => "previous_field",
+            Action::ClearInput => "clear_input",
+            Action::ToggleActivation => "toggle_activation",
+            Action::Debug => "debug",
+            Action::Confirm => "confirm",
+            Action::Apply => "apply",
+            Action::Pin => "pin",
+            Action::Dismiss => "dismiss",
+            Action::CommandPalette => "command_palette",
+            Action::Complete => "complete",
+            Action::GoTo => "go_to",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::IncreaseTime => "increase_time",
+            Action::DecreaseTime => "decrease_time",
+            Action::FixConflict => "fix_conflict",
+            Action::Alternative => "alternative",
+            Action::BatchImport => "batch_import",
+            Action::Check => "check",
+            Action::CheckAll => "check_all",
+            Action::EditContact => "edit_contact",
+            Action::EditProject => "edit_project",
+            Action::MergeFragments => "merge_fragments",
+        }
+    }
+
+    /// Translated description for the key binding help
+    pub(crate) fn description(&self) -> String {
+        match self {
+            Action::ToggleLogPanel => t!("keybinding_action_toggle_log_panel"),
+            Action::Quit => t!("keybinding_action_quit"),
+            Action::SelectNext => t!("keybinding_action_select_next"),
+            Action::SelectPrevious => t!("keybinding_action_select_previous"),
+            Action::PreviousWeek => t!("keybinding_action_previous_week"),
+            Action::NextWeek => t!("keybinding_action_next_week"),
+            Action::CurrentWeek => t!("keybinding_action_current_week"),
+            Action::Edit => t!("keybinding_action_edit"),
+            Action::Create => t!("keybinding_action_create"),
+            Action::Delete => t!("keybinding_action_delete"),
+            Action::Export => t!("keybinding_action_export"),
+            Action::Import => t!("keybinding_action_import"),
+            Action::Plugins => t!("keybinding_action_plugins"),
+            Action::Refresh => t!("keybinding_action_refresh"),
+            Action::Search => t!("keybinding_action_search"),
+            Action::CycleState => t!("keybinding_action_cycle_state"),
+            Action::FilterContact => t!("keybinding_action_filter_contact"),
+            Action::FilterProject => t!("keybinding_action_filter_project"),
+            Action::SwitchUser => t!("keybinding_action_switch_user"),
+            Action::SubmitWeek => t!("keybinding_action_submit_week"),
+            Action::SavedSearches => t!("keybinding_action_saved_searches"),
+            Action::SortNextColumn => t!("keybinding_action_sort_next_column"),
+            Action::SortDirection => t!("keybinding_action_sort_direction"),
+            Action::Group => t!("keybinding_action_group"),
+            Action::ToggleGroup => t!("keybinding_action_toggle_group"),
+            Action::ToggleAllGroups => t!("keybinding_action_toggle_all_groups"),
+            Action::ClearSearch => t!("keybinding_action_clear_search"),
+            Action::SaveSearch => t!("keybinding_action_save_search"),
+            Action::Close => t!("keybinding_action_close"),
+            Action::Save => t!("keybinding_action_save"),
+            Action::Cancel => t!("keybinding_action_cancel"),
+            Action::NextField => t!("keybinding_action_next_field"),
+            Action::PreviousField => t!("keybinding_action_previous_field"),
+            Action::ClearInput => t!("keybinding_action_clear_input"),
+            Action::ToggleActivation => t!("keybinding_action_toggle_activation"),
+            Action::Debug => t!("keybinding_action_debug"),
+            Action::Confirm => t!("keybinding_action_confirm"),
+            Action::Apply => t!("keybinding_action_apply"),
+            Action::Pin => t!("keybinding_action_pin"),
+            Action::Dismiss => t!("keybinding_action_dismiss"),
+            Action::CommandPalette => t!("keybinding_action_command_palette"),
+            Action::Complete => t!("keybinding_action_complete"),
+            Action::GoTo => t!("keybinding_action_go_to"),
+            Action::Undo => t!("keybinding_action_undo"),
+            Action::Redo => t!("keybinding_action_redo"),
+            Action::IncreaseTime => t!("keybinding_action_increase_time"),
+            Action::DecreaseTime => t!("keybinding_action_decrease_time"),
+            Action::FixConflict => t!("keybinding_action_fix_conflict"),
+            Action::Alternative => t!("keybinding_action_alternative"),
+            Action::BatchImport => t!("keybinding_action_batch_import"),
+            Action::Check => t!("keybinding_action_check"),
+            Action::CheckAll => t!("keybinding_action_check_all"),
+            Action::EditContact => t!("keybinding_action_edit_contact"),
+            Action::EditProject => t!("keybinding_action_edit_project"),
+            Action::MergeFragments => t!("keybinding_action_merge_fragments"),
+        }
+        .to_string()
+    }
+}
+
+/// The default bindings per mode, in the order they are listed in the help
+fn default_bindings() -> Vec<(KeyMode, Action, &'static [&'static str])> {
+    vec![
+        (KeyMode::Global, Action::ToggleLogPanel, &["f12"]),
+        (KeyMode::Normal, Action::PreviousWeek, &["left", "h"]),
+        (KeyMode::Normal, Action::NextWeek, &["right", "l"]),
+        (KeyMode::Normal, Action::CurrentWeek, &["t"]),
+        (KeyMode::Normal, Action::GoTo, &["G"]),
+        (KeyMode::Normal, Action::SelectNext, &["down", "j"]),
+        (KeyMode::Normal, Action::SelectPrevious, &["up", "k"]),
+        (KeyMode::Normal, Action::Search, &["f", "/"]),
+        (KeyMode::Normal, Action::CommandPalette, &[":", "ctrl+p"]),
+        (KeyMode::Normal, Action::SavedSearches, &["F"]),
+        (KeyMode::Normal, Action::SortNextColumn, &["o"]),
+        (KeyMode::Normal, Action::SortDirection, &["O"]),
+        (KeyMode::Normal, Action::Group, &["g"]),
+        (KeyMode::Normal, Action::ToggleGroup, &["z"]),
+        (KeyMode::Normal, Action::ToggleAllGroups, &["Z"]),
+        (KeyMode::Normal, Action::CycleState, &["s"]),
+        (KeyMode::Normal, Action::FilterContact, &["C"]),
+        (KeyMode::Normal, Action::FilterProject, &["P"]),
+        (KeyMode::Normal, Action::SwitchUser, &["U"]),
+        (KeyMode::Normal, Action::Create, &["c"]),
+        (
+            KeyMode::Normal,
+            Action::Edit,
+            &["e", "enter", "space", "f4"],
+        ),
+        (KeyMode::Normal, Action::Import, &["i"]),
+        (KeyMode::Normal, Action::BatchImport, &["I"]),
+        (KeyMode::Normal, Action::Delete, &["d", "delete"]),
+        (KeyMode::Normal, Action::Undo, &["u"]),
+        (KeyMode::Normal, Action::Redo, &["ctrl+r"]),
+        (KeyMode::Normal, Action::FixConflict, &["!"]),
+        (KeyMode::Normal, Action::MergeFragments, &["M"]),
+        (KeyMode::Normal, Action::Plugins, &["p"]),
+        (KeyMode::Normal, Action::Export, &["x"]),
+        (KeyMode::Normal, Action::SubmitWeek, &["S"]),
+        (KeyMode::Normal, Action::Refresh, &["r"]),
+        (KeyMode::Normal, Action::Quit, &["q"]),
+        (KeyMode::Search, Action::Close, &["esc"]),
+        (KeyMode::Search, Action::ClearSearch, &["ctrl+u"]),
+        (KeyMode::Search, Action::SaveSearch, &["ctrl+s"]),
+        (KeyMode::Search, Action::SelectNext, &["down"]),
+        (KeyMode::Search, Action::SelectPrevious, &["up"]),
+        (KeyMode::Search, Action::PreviousWeek, &["left"]),
+        (KeyMode::Search, Action::NextWeek, &["right"]),
+        (KeyMode::Search, Action::Edit, &["f4"]),
+        (KeyMode::Search, Action::Export, &["x"]),
+        (KeyMode::Search, Action::CommandPalette, &["ctrl+p"]),
+        (KeyMode::Edit, Action::NextField, &["tab"]),
+        (KeyMode::Edit, Action::PreviousField, &["backtab"]),
+        (KeyMode::Edit, Action::Save, &["ctrl+s"]),
+        (KeyMode::Edit, Action::Cancel, &["esc"]),
+        (KeyMode::Edit, Action::ClearInput, &["ctrl+u"]),
+        (KeyMode::Edit, Action::IncreaseTime, &["up"]),
+        (KeyMode::Edit, Action::DecreaseTime, &["down"]),
+        (KeyMode::Plugins, Action::Close, &["esc", "p"]),
+        (KeyMode::Plugins, Action::Quit, &["q"]),
+        (KeyMode::Plugins, Action::ToggleActivation, &["space"]),
+        (KeyMode::Plugins, Action::Debug, &["ctrl+d"]),
+        (KeyMode::Plugins, Action::SelectNext, &["down", "j"]),
+        (KeyMode::Plugins, Action::SelectPrevious, &["up", "k"]),
+        (KeyMode::Users, Action::Confirm, &["enter"]),
+        (KeyMode::Users, Action::Close, &["esc", "U"]),
+        (KeyMode::Users, Action::Quit, &["q"]),
+        (KeyMode::Users, Action::SelectNext, &["down", "j"]),
+        (KeyMode::Users, Action::SelectPrevious, &["up", "k"]),
+        (KeyMode::SavedSearches, Action::Apply, &["enter"]),
+        (KeyMode::SavedSearches, Action::Pin, &["*"]),
+        (KeyMode::SavedSearches, Action::Delete, &["d", "delete"]),
+        (KeyMode::SavedSearches, Action::Close, &["esc", "F"]),
+        (KeyMode::SavedSearches, Action::Quit, &["q"]),
+        (KeyMode::SavedSearches, Action::SelectNext, &["down", "j"]),
+        (KeyMode::SavedSearches, Action::SelectPrevious, &["up", "k"]),
+        (KeyMode::Palette, Action::Confirm, &["enter"]),
+        (KeyMode::Palette, Action::Complete, &["tab"]),
+        (KeyMode::Palette, Action::Close, &["esc"]),
+        (KeyMode::Palette, Action::SelectNext, &["down"]),
+        (KeyMode::Palette, Action::SelectPrevious, &["up"]),
+        (KeyMode::BatchImport, Action::Check, &["space"]),
+        (KeyMode::BatchImport, Action::CheckAll, &["a"]),
+        (KeyMode::BatchImport, Action::EditContact, &["c"]),
+        (KeyMode::BatchImport, Action::EditProject, &["p"]),
+        (KeyMode::BatchImport, Action::MergeFragments, &["m"]),
+        (KeyMode::BatchImport, Action::Confirm, &["enter"]),
+        (KeyMode::BatchImport, Action::Close, &["esc", "I"]),
+        (KeyMode::BatchImport, Action::Quit, &["q"]),
+        (KeyMode::BatchImport, Action::SelectNext, &["down", "j"]),
+        (KeyMode::BatchImport, Action::SelectPrevious, &["up", "k"]),
+        (KeyMode::Modal, Action::Cancel, &["esc", "n"]),
+        (KeyMode::Modal, Action::Confirm, &["enter", "y"]),
+        (KeyMode::Modal, Action::Alternative, &["a"]),
+        (
+            KeyMode::Modal,
+            Action::Dismiss,
+            &["space", "tab", "backtab"],
+        ),
+    ]
+}
+
+/// A key together with its modifiers, like `ctrl+s`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct KeyChord {
+    pub(crate) code: KeyCode,
+    pub(crate) modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Parse a chord such as `e`, `S`, `ctrl+s`, `shift+tab`, `f12` or `space`
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || t!("keybinding_invalid_key", key = value).to_string();
+
+        let value = value.trim();
+        // A lone plus is the plus key and not a separator
+        let (modifier_parts, key) = if value == "+" || value.ends_with("++") {
+            let prefix = value.strip_suffix('+').unwrap_or_default();
+            (prefix.strip_suffix('+').unwrap_or(prefix), "+")
+        } else {
+            match value.rsplit_once('+') {
+                Some((prefix, key)) => (prefix, key),
+                None => ("", value),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in modifier_parts.split('+').filter(|part| !part.is_empty()) {
+            modifiers |= match part.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(number) if (1..=24).contains(&number) => KeyCode::F(number),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+
+        // Shift is part of the character for letters and of the key for backtab
+        Ok(match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => KeyChord {
+                code: KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers: modifiers.difference(KeyModifiers::SHIFT),
+            },
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyChord {
+                code: KeyCode::BackTab,
+                modifiers: modifiers.difference(KeyModifiers::SHIFT),
+            },
+            code => KeyChord { code, modifiers },
+        })
+    }
+
+    /// Check if a key press triggers this chord
+    pub(crate) fn matches(&self, key: &KeyEvent) -> bool {
+        // Terminals differ in reporting shift for characters and backtab
+        let ignore_shift = matches!(self.code, KeyCode::Char(_) | KeyCode::BackTab);
+        let modifiers = |modifiers: KeyModifiers| {
+            if ignore_shift {
+                modifiers.difference(KeyModifiers::SHIFT)
+            } else {
+                modifiers
+            }
+        };
+
+        let code_matches = match (self.code, key.code) {
+            // Control chords are reported in either case
+            (KeyCode::Char(a), KeyCode::Char(b))
+                if self.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                a.eq_ignore_ascii_case(&b)
+            }
+            (a, b) => a == b,
+        };
+
+        code_matches && modifiers(self.modifiers) == modifiers(key.modifiers)
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    /// Format the chord the way the shortcut footers show keys, like `Ctrl+S` or `◀`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Up => write!(f, "▲"),
+            KeyCode::Down => write!(f, "▼"),
+            KeyCode::Left => write!(f, "◀"),
+            KeyCode::Right => write!(f, "▶"),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Binding {
+    mode: KeyMode,
+    action: Action,
+    chords: Vec<KeyChord>,
+}
+
+/// The active key bindings, the defaults with the overrides from the configuration
+#[derive(Clone, Debug)]
+pub(crate) struct KeyBindings {
+    bindings: Vec<Binding>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: default_bindings()
+                .into_iter()
+                .map(|(mode, action, keys)| Binding {
+                    mode,
+                    action,
+                    chords: keys
+                        .iter()
+                        .filter_map(|key| KeyChord::parse(key).ok())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Apply the `[keybindings]` configuration on top of the defaults. A configured action
+    /// replaces all default keys of that action, an empty list unbinds it. Returns the
+    /// bindings together with warnings for the entries that couldn't be used.
+    pub(crate) fn from_config(
+        config: &BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    ) -> (Self, Vec<String>) {
+        let mut key_bindings = Self::default();
+        let mut warnings = Vec::new();
+
+        for (mode_name, actions) in config {
+            let Some(mode) = MODES.iter().find(|mode| mode.as_str() == mode_name) else {
+                warnings.push(t!("keybinding_unknown_mode", mode = mode_name).to_string());
+                continue;
+            };
+
+            for (action_name, keys) in actions {
+                let binding = key_bindings.bindings.iter_mut().find(|binding| {
+                    binding.mode == *mode && binding.action.as_str() == action_name
+                });
+                let Some(binding) = binding else {
+                    warnings.push(
+                        t!(
+                            "keybinding_unknown_action",
+                            action = action_name,
+                            mode = mode_name
+                        )
+                        .to_string(),
+                    );
+                    continue;
+                };
+
+                binding.chords = keys
+                    .iter()
+                    .filter_map(|key| match KeyChord::parse(key) {
+                        Ok(chord) => Some(chord),
+                        Err(err) => {
+                            warnings.push(err);
+                            None
+                        }
+                    })
+                    .collect();
+            }
+        }
+
+        // The first binding in the list wins, so warn about keys that do two things
+        for (idx, binding) in key_bindings.bindings.iter().enumerate() {
+            for chord in &binding.chords {
+                let conflict = key_bindings.bindings[..idx].iter().find(|other| {
+                    (other.mode == binding.mode || other.mode == KeyMode::Global)
+                        && other.chords.contains(chord)
+                });
+                if let Some(other) = conflict {
+                    warnings.push(
+                        t!(
+                            "keybinding_conflict",
+                            key = chord.to_string(),
+                            mode = binding.mode.as_str(),
+                            action = binding.action.as_str(),
+                            other = other.action.as_str()
+                        )
+                        .to_string(),
+                    );
+                }
+            }
+        }
+
+        (key_bindings, warnings)
+    }
+
+    /// Find the action bound to a key press in a mode
+    pub(crate) fn action(&self, mode: KeyMode, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.mode == mode)
+            .find(|binding| binding.chords.iter().any(|chord| chord.matches(key)))
+            .map(|binding| binding.action)
+    }
+
+    /// Get the chords of an action in a mode
+    pub(crate) fn chords(&self, mode: KeyMode, action: Action) -> &[KeyChord] {
+        self.bindings
+            .iter()
+            .find(|binding| binding.mode == mode && binding.action == action)
+            .map(|binding| binding.chords.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The actions of a mode in help order, including the unbound ones
+    pub(crate) fn actions(&self, mode: KeyMode) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.mode == mode)
+            .map(|binding| binding.action)
+            .collect()
+    }
+
+    /// Label of the first key of an action for the shortcut footers, `-` when unbound
+    pub(crate) fn label(&self, mode: KeyMode, action: Action) -> String {
+        self.chords(mode, action)
+            .first()
+            .map(|chord| chord.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// Labels of two related actions, like `o/O` for the sort keys
+    pub(crate) fn label_pair(&self, mode: KeyMode, first: Action, second: Action) -> String {
+        format!("{}/{}", self.label(mode, first), self.label(mode, second))
+    }
+
+    /// Render the active bindings as Markdown tables, one per mode
+    pub(crate) fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        for mode in MODES {
+            output.push_str(&format!("### `{}`\n\n", mode.as_str()));
+            output.push_str(&format!(
+                "| {} | {} | {} |\n|---|---|---|\n",
+                t!("keybinding_header_action"),
+                t!("keybinding_header_keys"),
+                t!("keybinding_header_description")
+            ));
+            for binding in self.bindings.iter().filter(|binding| binding.mode == mode) {
+                let keys = binding
+                    .chords
+                    .iter()
+                    .map(|chord| format!("`{}`", chord))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                output.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    binding.action.as_str(),
+                    keys,
+                    binding.action.description()
+                ));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn test_parse_chords() {
+        let chord = KeyChord::parse("ctrl+s").unwrap();
+        assert_eq!(chord.code, KeyCode::Char('s'));
+        assert_eq!(chord.modifiers, KeyModifiers::CONTROL);
+        assert_eq!(chord.to_string(), "Ctrl+S");
+
+        assert_eq!(KeyChord::parse("shift+tab").unwrap().code, KeyCode::BackTab);
+        assert_eq!(KeyChord::parse("shift+a").unwrap().code, KeyCode::Char('A'));
+        assert_eq!(KeyChord::parse("F12").unwrap().code, KeyCode::F(12));
+        assert_eq!(KeyChord::parse("+").unwrap().code, KeyCode::Char('+'));
+        assert_eq!(
+            KeyChord::parse("alt++").unwrap().modifiers,
+            KeyModifiers::ALT
+        );
+        assert_eq!(KeyChord::parse("left").unwrap().to_string(), "◀");
+        assert!(KeyChord::parse("hyper+x").is_err());
+        assert!(KeyChord::parse("f99").is_err());
+    }
+
+    #[test]
+    fn test_chord_matches() {
+        let upper = KeyChord::parse("S").unwrap();
+        assert!(upper.matches(&key(KeyCode::Char('S'), KeyModifiers::SHIFT)));
+        assert!(upper.matches(&key(KeyCode::Char('S'), KeyModifiers::NONE)));
+        assert!(!upper.matches(&key(KeyCode::Char('s'), KeyModifiers::NONE)));
+
+        let ctrl = KeyChord::parse("ctrl+u").unwrap();
+        assert!(ctrl.matches(&key(KeyCode::Char('u'), KeyModifiers::CONTROL)));
+        assert!(!ctrl.matches(&key(KeyCode::Char('u'), KeyModifiers::NONE)));
+        assert!(!KeyChord::parse("u")
+            .unwrap()
+            .matches(&key(KeyCode::Char('u'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let mut normal = BTreeMap::new();
+        normal.insert("previous_week".to_string(), vec!["b".to_string()]);
+        normal.insert("export".to_string(), Vec::new());
+        normal.insert("save".to_string(), vec!["ctrl+s".to_string()]);
+        let mut config = BTreeMap::new();
+        config.insert("normal".to_string(), normal);
+        config.insert("unknown".to_string(), BTreeMap::new());
+
+        let (key_bindings, warnings) = KeyBindings::from_config(&config);
+        // The unknown mode and the save action that doesn't exist in normal mode
+        assert_eq!(warnings.len(), 2);
+
+        let b = key(KeyCode::Char('b'), KeyModifiers::NONE);
+        let h = key(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(
+            key_bindings.action(KeyMode::Normal, &b),
+            Some(Action::PreviousWeek)
+        );
+        assert_eq!(key_bindings.action(KeyMode::Normal, &h), None);
+        assert_eq!(key_bindings.label(KeyMode::Normal, Action::Export), "-");
+        assert_eq!(
+            key_bindings.label_pair(
+                KeyMode::Normal,
+                Action::SortNextColumn,
+                Action::SortDirection
+            ),
+            "o/O"
+        );
+    }
+
+    #[test]
+    fn test_default_conflicts() {
+        let mut config = BTreeMap::new();
+        let mut normal = BTreeMap::new();
+        normal.insert("create".to_string(), vec!["e".to_string()]);
+        config.insert("normal".to_string(), normal);
+
+        let (_, warnings) = KeyBindings::from_config(&BTreeMap::new());
+        assert!(warnings.is_empty());
+        let (_, warnings) = KeyBindings::from_config(&config);
+        assert_eq!(warnings.len(), 1);
+    }
+}
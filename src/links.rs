@@ -0,0 +1,135 @@
+use color_eyre::eyre::{eyre, Result};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Which MoneyBird entry each imported plugin entry became, so the same plugin entry
+/// isn't imported twice. Stored per administration, e.g. `links/<administration>.toml`:
+///
+/// ```toml
+/// [gitlab]
+/// "issue-42-note-7" = "431234567890123456"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct ImportLinks {
+    plugins: BTreeMap<String, BTreeMap<String, String>>, // Plugin name → entry id → MoneyBird id
+}
+
+impl ImportLinks {
+    /// The MoneyBird id a plugin entry was imported as
+    pub(crate) fn get(&self, plugin: &str, entry_id: &str) -> Option<&String> {
+        self.plugins.get(plugin)?.get(entry_id)
+    }
+
+    pub(crate) fn insert(&mut self, plugin: &str, entry_id: &str, moneybird_id: &str) {
+        self.plugins
+            .entry(plugin.to_string())
+            .or_default()
+            .insert(entry_id.to_string(), moneybird_id.to_string());
+    }
+
+    /// The plugin entries that were imported as a MoneyBird entry, as plugin name and
+    /// entry id
+    pub(crate) fn entries_for(&self, moneybird_id: &str) -> Vec<(String, String)> {
+        self.plugins
+            .iter()
+            .flat_map(|(plugin, entries)| {
+                entries
+                    .iter()
+                    .filter(|(_, linked_id)| *linked_id == moneybird_id)
+                    .map(move |(entry_id, _)| (plugin.clone(), entry_id.clone()))
+            })
+            .collect()
+    }
+
+    /// Remove the links to a MoneyBird entry that was deleted. Returns the plugin entries
+    /// that were linked to it, as plugin name and entry id.
+    pub(crate) fn remove_moneybird_id(&mut self, moneybird_id: &str) -> Vec<(String, String)> {
+        let removed = self.entries_for(moneybird_id);
+        for entries in self.plugins.values_mut() {
+            entries.retain(|_, linked_id| linked_id != moneybird_id);
+        }
+        self.plugins.retain(|_, entries| !entries.is_empty());
+        removed
+    }
+}
+
+/// Get the file with the links of an administration
+fn get_links_path(administration_id: &str) -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().ok_or_else(|| eyre!(t!("config_determine_dir_error")))?;
+    Ok(config_dir
+        .join("mot")
+        .join("links")
+        .join(format!("{}.toml", administration_id)))
+}
+
+/// Read the links of an administration, there are none before the first import
+pub(crate) fn read_links(administration_id: &str) -> ImportLinks {
+    get_links_path(administration_id)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write the links of an administration
+pub(crate) fn write_links(administration_id: &str, links: &ImportLinks) -> Result<()> {
+    let path = get_links_path(administration_id)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| {
+            eyre!(t!(
+                "links_write_error",
+                path = format!("{:?}", dir),
+                error = e
+            ))
+        })?;
+    }
+
+    let content = toml::to_string_pretty(links)?;
+    fs::write(&path, content).map_err(|e| {
+        eyre!(t!(
+            "links_write_error",
+            path = format!("{:?}", path),
+            error = e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_links() {
+        let mut links = ImportLinks::default();
+        links.insert("gitlab", "note-7", "100");
+        links.insert("gitlab", "note-8", "101");
+        links.insert("toggl", "note-7", "102");
+        assert_eq!(links.get("gitlab", "note-7"), Some(&"100".to_string()));
+        assert_eq!(links.get("toggl", "note-7"), Some(&"102".to_string()));
+        assert_eq!(links.get("toggl", "note-8"), None);
+        links.insert("gitlab", "note-9", "100");
+        assert_eq!(
+            links.entries_for("100"),
+            vec![
+                ("gitlab".to_string(), "note-7".to_string()),
+                ("gitlab".to_string(), "note-9".to_string())
+            ]
+        );
+        assert!(links.entries_for("999").is_empty());
+
+        assert_eq!(
+            links.remove_moneybird_id("102"),
+            vec![("toggl".to_string(), "note-7".to_string())]
+        );
+        assert_eq!(links.get("toggl", "note-7"), None);
+        assert!(links.remove_moneybird_id("102").is_empty());
+        assert_eq!(links.get("gitlab", "note-7"), Some(&"100".to_string()));
+
+        let content = toml::to_string_pretty(&links).unwrap();
+        assert_eq!(toml::from_str::<ImportLinks>(&content).unwrap(), links);
+    }
+}
@@ -0,0 +1,241 @@
+use color_eyre::eyre::{eyre, Result};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A submitted week. While the lock file exists, the week can't be changed from mot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct WeekLock {
+    pub(crate) administration_id: String,
+    pub(crate) user_id: String,
+    pub(crate) year: i32,
+    pub(crate) week: i32,
+    pub(crate) submitted_at: String, // RFC3339 timestamp of the submission
+    pub(crate) snapshot: PathBuf,    // CSV export of the week at the time of submission
+    pub(crate) checksum: String,     // SHA-256 of the snapshot file
+}
+
+/// Identifies a single week of a single user in an administration
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WeekKey {
+    pub(crate) administration_id: String,
+    pub(crate) user_id: String,
+    pub(crate) year: i32,
+    pub(crate) week: i32,
+}
+
+impl WeekKey {
+    /// Directory holding the lock files and snapshots of this user
+    fn dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.administration_id).join(&self.user_id)
+    }
+
+    /// Name of the lock file or snapshot of this week, e.g. `2025-W14.toml`
+    fn file_name(&self, extension: &str) -> String {
+        format!("{}-W{:02}.{}", self.year, self.week, extension)
+    }
+}
+
+/// The directory where submitted weeks are kept, laid out as
+/// `<root>/<administration>/<user>/2025-W14.toml` with the snapshot next to it
+#[derive(Clone, Debug)]
+pub(crate) struct LockStore {
+    root: PathBuf,
+}
+
+impl LockStore {
+    pub(crate) fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// The store in the mot configuration directory
+    pub(crate) fn in_config_dir() -> Result<Self> {
+        let config_dir =
+            dirs::config_dir().ok_or_else(|| eyre!(t!("config_determine_dir_error")))?;
+        Ok(Self::new(config_dir.join("mot").join("locks")))
+    }
+
+    /// Path of the lock file of a week
+    pub(crate) fn lock_path(&self, key: &WeekKey) -> PathBuf {
+        key.dir(&self.root).join(key.file_name("toml"))
+    }
+
+    /// Path of the export snapshot that belongs to the lock file of a week
+    pub(crate) fn snapshot_path(&self, key: &WeekKey) -> PathBuf {
+        key.dir(&self.root).join(key.file_name("csv"))
+    }
+
+    /// Read the lock for a week, if there is one
+    pub(crate) fn read_lock(&self, key: &WeekKey) -> Option<WeekLock> {
+        let content = fs::read_to_string(self.lock_path(key)).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    /// Check if a week has been submitted
+    pub(crate) fn is_week_locked(&self, key: &WeekKey) -> bool {
+        self.lock_path(key).exists()
+    }
+
+    /// Make sure the directory for the lock and snapshot of a week exists
+    pub(crate) fn prepare_lock_dir(&self, key: &WeekKey) -> Result<()> {
+        let dir = key.dir(&self.root);
+        fs::create_dir_all(&dir).map_err(|e| {
+            eyre!(t!(
+                "lock_create_dir_error",
+                path = format!("{:?}", dir),
+                error = e
+            ))
+        })
+    }
+
+    /// Write the lock file for a week, pointing to an already written snapshot
+    pub(crate) fn write_lock(&self, key: &WeekKey, submitted_at: String) -> Result<WeekLock> {
+        let snapshot = self.snapshot_path(key);
+        let lock = WeekLock {
+            administration_id: key.administration_id.clone(),
+            user_id: key.user_id.clone(),
+            year: key.year,
+            week: key.week,
+            submitted_at,
+            checksum: calculate_checksum(&snapshot)?,
+            snapshot,
+        };
+
+        let path = self.lock_path(key);
+        let content = toml::to_string_pretty(&lock)?;
+        fs::write(&path, content).map_err(|e| {
+            eyre!(t!(
+                "lock_write_error",
+                path = format!("{:?}", path),
+                error = e
+            ))
+        })?;
+
+        Ok(lock)
+    }
+
+    /// Remove the lock file of a week. The snapshot is kept for reference.
+    pub(crate) fn remove_lock(&self, key: &WeekKey) -> Result<()> {
+        let path = self.lock_path(key);
+        fs::remove_file(&path).map_err(|e| {
+            eyre!(t!(
+                "lock_remove_error",
+                path = format!("{:?}", path),
+                error = e
+            ))
+        })
+    }
+}
+
+/// Calculate the SHA-256 checksum of a file as a lowercase hex string
+pub(crate) fn calculate_checksum(path: &PathBuf) -> Result<String> {
+    let content = fs::read(path).map_err(|e| {
+        eyre!(t!(
+            "lock_read_snapshot_error",
+            path = format!("{:?}", path),
+            error = e
+        ))
+    })?;
+
+    Ok(Sha256::digest(&content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(user_id: &str, week: i32) -> WeekKey {
+        WeekKey {
+            administration_id: "123".to_string(),
+            user_id: user_id.to_string(),
+            year: 2025,
+            week,
+        }
+    }
+
+    /// A store in its own temporary directory, remove it with `remove_store`
+    fn temp_store(name: &str) -> LockStore {
+        LockStore::new(std::env::temp_dir().join(format!(
+            "mot-locks-{}-{}",
+            name,
+            std::process::id()
+        )))
+    }
+
+    fn remove_store(store: LockStore) {
+        fs::remove_dir_all(store.root).unwrap();
+    }
+
+    #[test]
+    fn test_week_key_paths() {
+        let store = LockStore::new(PathBuf::from("locks"));
+        let key = key("456", 4);
+        assert_eq!(
+            store.lock_path(&key),
+            PathBuf::from("locks/123/456/2025-W04.toml")
+        );
+        assert_eq!(
+            store.snapshot_path(&key),
+            PathBuf::from("locks/123/456/2025-W04.csv")
+        );
+    }
+
+    #[test]
+    fn test_checksum() {
+        let store = temp_store("checksum");
+        let key = key("456", 1);
+        store.prepare_lock_dir(&key).unwrap();
+        let snapshot = store.snapshot_path(&key);
+        fs::write(&snapshot, "abc").unwrap();
+        assert_eq!(
+            calculate_checksum(&snapshot).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        fs::remove_file(&snapshot).unwrap();
+        assert!(calculate_checksum(&snapshot).is_err());
+        remove_store(store);
+    }
+
+    #[test]
+    fn test_write_and_read_lock() {
+        let store = temp_store("lock");
+        let key = key("456", 14);
+        assert!(!store.is_week_locked(&key));
+        assert!(store.read_lock(&key).is_none());
+
+        // The lock needs the snapshot of the week to exist
+        store.prepare_lock_dir(&key).unwrap();
+        assert!(store
+            .write_lock(&key, "2025-04-04T17:00:00+02:00".to_string())
+            .is_err());
+        let snapshot = store.snapshot_path(&key);
+        fs::write(&snapshot, "date,description\n").unwrap();
+
+        let written = store
+            .write_lock(&key, "2025-04-04T17:00:00+02:00".to_string())
+            .unwrap();
+        assert!(store.is_week_locked(&key));
+        let read = store.read_lock(&key).unwrap();
+        assert_eq!(read.administration_id, "123");
+        assert_eq!(read.user_id, "456");
+        assert_eq!((read.year, read.week), (2025, 14));
+        assert_eq!(read.submitted_at, "2025-04-04T17:00:00+02:00");
+        assert_eq!(read.snapshot, snapshot);
+        assert_eq!(read.checksum, written.checksum);
+        assert_eq!(read.checksum, calculate_checksum(&snapshot).unwrap());
+
+        // Unlocking keeps the snapshot
+        store.remove_lock(&key).unwrap();
+        assert!(!store.is_week_locked(&key));
+        assert!(store.read_lock(&key).is_none());
+        assert!(snapshot.exists());
+        assert!(store.remove_lock(&key).is_err());
+        remove_store(store);
+    }
+}
@@ -2,18 +2,26 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod analysis;
 mod api;
 mod cmd;
 mod config;
 mod datetime;
 mod event;
 mod file;
+mod fuzzy;
+mod keybindings;
+mod links;
+mod lock;
 mod model;
 mod moneybird;
 mod moneybird_traits;
+mod palette;
 mod plugin;
+mod query;
 mod tui;
 mod ui;
+mod undo;
 mod update;
 
 // Import and initialize rust-i18n
@@ -39,6 +47,11 @@ async fn main() -> color_eyre::Result<()> {
         ..Default::default()
     };
     model.config = config::get_configuration();
+    model.client = api::create_moneybird_client(&model.config);
+    match lock::LockStore::in_config_dir() {
+        Ok(locks) => model.locks = Some(locks),
+        Err(err) => model.log_warning(err.to_string()),
+    }
 
     // Set locale with priority:
     // 1. Command line arguments (highest priority)
@@ -75,6 +88,46 @@ async fn main() -> color_eyre::Result<()> {
         model.log_notice(t!("notice_language_default", language = "en"));
     }
 
+    // Apply the configured key bindings on top of the defaults
+    let (keybindings, keybinding_warnings) =
+        keybindings::KeyBindings::from_config(&model.config.keybindings);
+    model.keybindings = keybindings;
+    for warning in keybinding_warnings {
+        model.log_warning(warning);
+    }
+
+    // Print the active key bindings and exit
+    if args.keybindings {
+        print!("{}", model.keybindings.to_markdown());
+        return Ok(());
+    }
+
+    // Apply server-side time entry filters from the command line
+    if let Some(state) = &args.state {
+        model.time_entry_filter.state = state
+            .parse()
+            .map_err(|err: String| eyre::eyre!(t!("cmd_invalid_state", error = err)))?;
+    }
+    model.time_entry_filter.contact_id = args.contact_id.clone();
+    model.time_entry_filter.project_id = args.project_id.clone();
+    model.time_entry_filter.user_id = args.user_id.clone();
+
+    // A saved search from the command line replaces the pinned default search
+    let saved_query = match &args.saved_query {
+        Some(name) => Some(
+            model
+                .config
+                .find_saved_search(name)
+                .ok_or_else(|| eyre::eyre!(t!("cmd_unknown_saved_query", name = name)))?
+                .query
+                .clone(),
+        ),
+        None => model
+            .config
+            .get_default_search()
+            .map(|search| search.query.clone()),
+    };
+
     // Check connectivity to the MoneyBird API but don't exit on failure
     model.log_notice(t!("notice_checking_api"));
     if let Err(err) = api::check_connectivity(&model.client).await {
@@ -242,6 +295,21 @@ async fn main() -> color_eyre::Result<()> {
                 "success_using_user_id",
                 user_id = model.config.user_id.clone().unwrap_or_default()
             ));
+
+            // Fetch the users anyway, they are needed to show who owns each time entry
+            let administration_id = model.administration.id.clone().unwrap_or_default();
+            if !administration_id.is_empty() {
+                match api::get_all_users(&model.client, &administration_id).await {
+                    Ok(users) => {
+                        model.log_success(t!("success_fetched_users", count = users.len()));
+                        model.users = users;
+                    }
+                    Err(err) => {
+                        // Not blocking, entries will be shown without user names
+                        model.log_warning(t!("error_fetch_users", error = err.to_string()));
+                    }
+                }
+            }
         }
     }
 
@@ -273,6 +341,12 @@ async fn main() -> color_eyre::Result<()> {
 
         // Get time entries for the current week
         api::get_time_entries(&mut model).await;
+        model.refresh_week_lock();
+
+        // Exports get the saved search as part of their query instead
+        if let Some(query) = saved_query.as_ref().filter(|_| !args.export) {
+            model.apply_search(query);
+        }
     }
 
     // Handle plugin debug command if provided
@@ -311,8 +385,15 @@ async fn main() -> color_eyre::Result<()> {
             return Err(eyre::eyre!("{}\n{}", modal.title, modal.message));
         } else {
             // Export time entries to a csv file using command-line options
-            return file::handle_export_command(&mut model, args.week.clone(), args.query.clone())
-                .await;
+            // Only an explicitly requested saved search is used for exports
+            let query = match (&args.saved_query, &saved_query) {
+                (Some(_), Some(saved)) if !args.query.is_empty() => {
+                    format!("({}) {}", saved, args.query)
+                }
+                (Some(_), Some(saved)) => saved.clone(),
+                _ => args.query.clone(),
+            };
+            return file::handle_export_command(&mut model, args.week.clone(), query).await;
         }
     }
 
@@ -377,12 +458,18 @@ fn view(model: &mut AppModel, frame: &mut Frame) {
     };
 
     // Render appropriate UI based on state
-    if model.user_selection_active {
-        // If user selection is active, render the user selection list
+    if model.user_selection_active || model.user_switcher_active {
+        // If user selection or the user switcher is active, render the user selection list
         ui::render_user_selection(model, main_area, frame);
+    } else if model.saved_search_state.picker_active {
+        // If the saved search picker is active, render the list of saved searches
+        ui::render_saved_searches(model, main_area, frame);
     } else if model.plugin_view_state.active {
         // If plugin view is active, render the plugins list
         ui::render_plugins(model, main_area, frame);
+    } else if model.batch_import_state.active {
+        // Review the plugin entries of the week before importing them together
+        ui::render_batch_import(model, main_area, frame);
     } else if model.edit_state.active {
         // When in edit mode, show the edit form
         ui::render_time_entry_edit(model, main_area, frame);
@@ -405,6 +492,11 @@ fn view(model: &mut AppModel, frame: &mut Frame) {
             render_time_entries_table(model, top, frame);
             render_time_entry_detail(model, bottom, frame);
         }
+
+        // The command palette floats over the time entries
+        if model.command_palette_state.active {
+            ui::render_command_palette(model, main_area, frame);
+        }
     }
 
     // Draw modal if active (always on top)
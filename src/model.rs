@@ -6,16 +6,29 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, ListState, Padding, TableState},
 };
 use rust_i18n::t;
+use std::collections::HashSet;
 use supports_color::ColorLevel;
 use tui_textarea::TextArea;
 
 use crate::{
-    config::Configuration,
+    analysis::{self, Analysis},
+    config::{
+        Configuration, MappingRule, RoundingRule, SortColumn, SortDirection, TableSort,
+        ValidationConfig,
+    },
+    datetime,
+    keybindings::{KeyBindings, KeyMode},
+    links::ImportLinks,
+    lock::{LockStore, WeekKey, WeekLock},
     moneybird::{
         self,
         types::{Administration, TimeEntry, User},
     },
+    palette::{self, PaletteMatch},
+    plugin::FetchedTimeEntries,
+    query::{Query, QueryContext},
     ui,
+    undo::UndoStack,
 };
 
 // Add the import for AutocompleteState
@@ -63,6 +76,186 @@ pub(crate) struct LogPanelState {
 pub(crate) struct SearchState {
     pub(crate) active: bool,
     pub(crate) text_input: TextArea<'static>,
+    pub(crate) error: Option<String>, // Parse error of the current query
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct SavedSearchState {
+    pub(crate) picker_active: bool,
+    pub(crate) list_state: ListState,
+    pub(crate) name_input: Option<TextArea<'static>>, // Set while naming the current search
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct CommandPaletteState {
+    pub(crate) active: bool,
+    pub(crate) mode: KeyMode, // Mode the palette was opened from, decides the commands
+    pub(crate) input: TextArea<'static>,
+    pub(crate) list_state: ListState,
+    pub(crate) error: Option<String>, // Why the last command couldn't run
+}
+
+/// What the input of the batch import changes for the selected entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BatchImportTarget {
+    Contact,
+    Project,
+}
+
+/// A plugin entry in the batch import review
+#[derive(Clone, Debug)]
+pub(crate) struct BatchImportItem {
+    pub(crate) entry: TimeEntryForTable,
+    pub(crate) checked: bool,
+    pub(crate) contact: Option<Contact>,
+    pub(crate) project: Option<Project>,
+    pub(crate) result: Option<Result<String, String>>, // Id of the created entry or why it failed
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct BatchImportState {
+    pub(crate) active: bool,
+    pub(crate) items: Vec<BatchImportItem>,
+    pub(crate) table_state: TableState,
+    pub(crate) editing: Option<BatchImportTarget>, // Set while picking a contact or project
+    pub(crate) contact_autocomplete: AutocompleteState<Contact>,
+    pub(crate) project_autocomplete: AutocompleteState<Project>,
+    // Rules learned from the picked contacts and projects, offered once the import is done
+    pub(crate) learned: Vec<(String, MappingRule)>,
+}
+
+/// Time entry state as understood by the MoneyBird `state:` filter
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum TimeEntryStateFilter {
+    #[default]
+    All,
+    Open,
+    Billed,
+    NonBillable,
+}
+
+impl TimeEntryStateFilter {
+    /// The value MoneyBird expects in the filter parameter
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            TimeEntryStateFilter::All => "all",
+            TimeEntryStateFilter::Open => "open",
+            TimeEntryStateFilter::Billed => "billed",
+            TimeEntryStateFilter::NonBillable => "non_billable",
+        }
+    }
+
+    /// Translated label for display in the UI
+    pub(crate) fn label(&self) -> String {
+        match self {
+            TimeEntryStateFilter::All => t!("ui_filter_state_all").to_string(),
+            TimeEntryStateFilter::Open => t!("ui_filter_state_open").to_string(),
+            TimeEntryStateFilter::Billed => t!("ui_filter_state_billed").to_string(),
+            TimeEntryStateFilter::NonBillable => t!("ui_filter_state_non_billable").to_string(),
+        }
+    }
+
+    /// Cycle to the next state, used by the quick toggle key
+    pub(crate) fn next(self) -> Self {
+        match self {
+            TimeEntryStateFilter::All => TimeEntryStateFilter::Open,
+            TimeEntryStateFilter::Open => TimeEntryStateFilter::Billed,
+            TimeEntryStateFilter::Billed => TimeEntryStateFilter::NonBillable,
+            TimeEntryStateFilter::NonBillable => TimeEntryStateFilter::All,
+        }
+    }
+}
+
+impl std::str::FromStr for TimeEntryStateFilter {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().replace('-', "_").as_str() {
+            "all" => Ok(TimeEntryStateFilter::All),
+            "open" => Ok(TimeEntryStateFilter::Open),
+            "billed" => Ok(TimeEntryStateFilter::Billed),
+            "non_billable" | "nonbillable" => Ok(TimeEntryStateFilter::NonBillable),
+            _ => Err(t!("model_invalid_state_filter", state = value).to_string()),
+        }
+    }
+}
+
+/// Filters that are sent to the MoneyBird API together with the week period.
+/// The local search in `filter_items` is applied on top of the returned entries.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TimeEntryFilter {
+    pub(crate) state: TimeEntryStateFilter,
+    pub(crate) contact_id: Option<String>,
+    pub(crate) project_id: Option<String>,
+    pub(crate) user_id: Option<String>,
+}
+
+impl TimeEntryFilter {
+    /// Check if any filter besides the period is active
+    pub(crate) fn is_active(&self) -> bool {
+        self.state != TimeEntryStateFilter::All
+            || self.contact_id.is_some()
+            || self.project_id.is_some()
+            || self.user_id.is_some()
+    }
+}
+
+/// Grouping of the time entry table, `None` shows the flat list
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum GroupBy {
+    #[default]
+    None,
+    Day,
+    Client,
+    Project,
+    Source,
+}
+
+impl GroupBy {
+    /// Cycle to the next grouping, used by the quick toggle key
+    pub(crate) fn next(self) -> Self {
+        match self {
+            GroupBy::None => GroupBy::Day,
+            GroupBy::Day => GroupBy::Client,
+            GroupBy::Client => GroupBy::Project,
+            GroupBy::Project => GroupBy::Source,
+            GroupBy::Source => GroupBy::None,
+        }
+    }
+
+    /// Translated label for display in the UI
+    pub(crate) fn label(&self) -> String {
+        match self {
+            GroupBy::None => t!("ui_group_by_none").to_string(),
+            GroupBy::Day => t!("ui_group_by_day").to_string(),
+            GroupBy::Client => t!("ui_group_by_client").to_string(),
+            GroupBy::Project => t!("ui_group_by_project").to_string(),
+            GroupBy::Source => t!("ui_group_by_source").to_string(),
+        }
+    }
+}
+
+/// A visible row of the time entry table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TableRow {
+    Group {
+        key: String,
+        label: String,
+        count: usize,
+        minutes: u64, // Subtotal of the MoneyBird entries, like the week total
+        collapsed: bool,
+    },
+    Entry(usize), // Index in `time_entries_for_table`
+}
+
+/// Tree-like state of the grouped table. The selected entry stays in
+/// `time_entry_table_state`, this state tracks the visible rows including the headers.
+#[derive(Clone, Default)]
+pub(crate) struct GroupState {
+    pub(crate) group_by: GroupBy,
+    pub(crate) collapsed: HashSet<String>, // Keys of the collapsed groups
+    pub(crate) selected_group: Option<String>, // Key of the selected header when no entry is selected
+    pub(crate) table_state: TableState,        // Selection and scroll offset of the visible rows
 }
 
 #[derive(Debug, Clone, Default)]
@@ -72,6 +265,25 @@ pub struct PluginViewState {
     pub plugin_list_state: ListState,
 }
 
+/// How far a plugin is with something that takes a while, as it reported
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PluginProgress {
+    pub(crate) message: String,
+    pub(crate) current: Option<u64>,
+    pub(crate) total: Option<u64>,
+}
+
+impl PluginProgress {
+    /// The message with the steps done, e.g. "Fetching issues 3/10"
+    pub(crate) fn label(&self) -> String {
+        match (self.current, self.total) {
+            (Some(current), Some(total)) => format!("{} {}/{}", self.message, current, total),
+            (Some(current), None) => format!("{} {}", self.message, current),
+            _ => self.message.clone(),
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 pub(crate) struct ModalStack {
     pub(crate) modals: Vec<ui::ModalData>,
@@ -93,6 +305,11 @@ impl ModalStack {
         self.modals.last()
     }
 
+    /// Get the top modal to change it in place
+    pub(crate) fn top_mut(&mut self) -> Option<&mut ui::ModalData> {
+        self.modals.last_mut()
+    }
+
     /// Check if the stack is empty
     pub(crate) fn is_empty(&self) -> bool {
         self.modals.is_empty()
@@ -210,6 +427,33 @@ pub(crate) struct TimeEntryForTable {
     pub source: String,              // Plugin name or "moneybird"
     pub icon: Option<String>,        // Custom icon from plugin manifest
     pub plugin_name: Option<String>, // Matched plugin name for consistency
+    pub user: String,                // Name of the MoneyBird user, empty for plugin entries
+    pub user_id: Option<String>,     // ID of the MoneyBird user, None for plugin entries
+    pub contact_id: Option<String>,  // ID of the MoneyBird contact, None for plugin entries
+    pub tags: Vec<String>,           // Tags from the plugin, MoneyBird entries have none
+    pub linked_id: Option<String>,   // MoneyBird entry a plugin entry was imported as
+    pub merged_ids: Vec<String>,     // Plugin entries this entry was merged from
+}
+
+impl TimeEntryForTable {
+    /// Ids of the plugin entries this entry stands for, all fragments after a merge
+    pub(crate) fn plugin_entry_ids(&self) -> Vec<&str> {
+        if self.merged_ids.is_empty() {
+            vec![self.id.as_str()]
+        } else {
+            self.merged_ids.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Name the import links and mapping rules of a plugin entry are kept under: the
+    /// plugin name, or the source for plugins that don't set one. None for MoneyBird entries.
+    pub(crate) fn plugin_key(&self) -> Option<&str> {
+        match &self.plugin_name {
+            Some(plugin_name) => Some(plugin_name),
+            None if self.source == "moneybird" => None,
+            None => Some(&self.source),
+        }
+    }
 }
 
 /// Type of edit operation
@@ -243,48 +487,164 @@ pub struct EditState {
     // For import operation
     pub original_entry: Option<TimeEntryForTable>,
 
+    // Contact and project an import matched, corrections to them can be learned
+    pub(crate) prefilled_contact_id: Option<String>,
+    pub(crate) prefilled_project_id: Option<String>,
+
     // Autocomplete state for project selection
     pub(crate) project_autocomplete: AutocompleteState<Project>,
 
     // Autocomplete state for contact selection
     pub(crate) contact_autocomplete: AutocompleteState<Contact>,
 
+    // Suggestions from plugins for the description, its input follows the editor
+    pub(crate) description_autocomplete: AutocompleteState<String>,
+
     // Areas of each field for click detection
     pub(crate) field_areas: std::collections::HashMap<EditField, Rect>,
+
+    // Date and time input that could not be understood, shown at the field
+    pub(crate) input_errors: std::collections::HashMap<EditField, String>,
+
+    // Problems that block saving, see `EditState::validate`
+    pub(crate) validation_errors: std::collections::HashMap<EditField, String>,
+
+    // Fields focus has left, their problems show before the first save attempt
+    pub(crate) touched_fields: HashSet<EditField>,
+    pub(crate) save_attempted: bool,
 }
 
 impl EditState {
-    /// Convert the EditState to a TimeEntry for updating, interpreting input times in the provided timezone
-    pub fn try_into_time_entry(&self, timezone: &str) -> crate::moneybird::types::TimeEntry {
-        // Get the timezone object, fallback to UTC
-        let tz: Tz = timezone.parse().unwrap_or(chrono_tz::UTC);
+    /// The start as entered, once the date and time fields hold a valid date and time
+    pub(crate) fn start_datetime(&self) -> Option<NaiveDateTime> {
+        Self::parse_datetime(&self.start_date, &self.start_time)
+    }
 
-        // Parse the local date and time strings to create NaiveDateTime objects
-        let start_date =
-            NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d").unwrap_or_else(|_| {
-                // Fallback to today if parsing fails (Consider a better fallback?)
-                Utc::now().with_timezone(&tz).date_naive()
-            });
+    /// The end as entered, once the date and time fields hold a valid date and time
+    pub(crate) fn end_datetime(&self) -> Option<NaiveDateTime> {
+        Self::parse_datetime(&self.end_date, &self.end_time)
+    }
 
-        let start_time =
-            NaiveTime::parse_from_str(&self.start_time, "%H:%M").unwrap_or_else(|_| {
-                // Fallback to midnight if parsing fails
-                NaiveTime::from_hms_opt(0, 0, 0).unwrap()
-            });
+    fn parse_datetime(date: &str, time: &str) -> Option<NaiveDateTime> {
+        let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+        let time = NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+        Some(NaiveDateTime::new(date, time))
+    }
 
-        let end_date = NaiveDate::parse_from_str(&self.end_date, "%Y-%m-%d").unwrap_or_else(|_| {
-            // Fallback to today if parsing fails (Consider a better fallback?)
-            Utc::now().with_timezone(&tz).date_naive()
-        });
+    /// The problems that block saving the entry, by field. The entry may not overlap the
+    /// MoneyBird entries of the user among `entries`.
+    pub(crate) fn validate(
+        &self,
+        validation: &ValidationConfig,
+        entries: &[TimeEntryForTable],
+        user_id: Option<&str>,
+        timezone: &str,
+    ) -> std::collections::HashMap<EditField, String> {
+        let mut errors = std::collections::HashMap::new();
+
+        if self.description.trim().is_empty() {
+            errors.insert(
+                EditField::Description,
+                t!("edit_description_required").to_string(),
+            );
+        }
+        if validation.require_project && self.project_id.is_none() {
+            errors.insert(EditField::Project, t!("edit_project_required").to_string());
+        }
 
-        let end_time = NaiveTime::parse_from_str(&self.end_time, "%H:%M").unwrap_or_else(|_| {
-            // Fallback to midnight if parsing fails
-            NaiveTime::from_hms_opt(0, 0, 0).unwrap()
-        });
+        for (field, value) in [
+            (EditField::StartDate, &self.start_date),
+            (EditField::EndDate, &self.end_date),
+        ] {
+            if NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").is_err() {
+                errors.insert(field, t!("edit_invalid_date").to_string());
+            }
+        }
+        for (field, value) in [
+            (EditField::StartTime, &self.start_time),
+            (EditField::EndTime, &self.end_time),
+        ] {
+            if NaiveTime::parse_from_str(value.trim(), "%H:%M").is_err() {
+                errors.insert(field, t!("edit_invalid_time").to_string());
+            }
+        }
+
+        if let (Some(start), Some(end)) = (self.start_datetime(), self.end_datetime()) {
+            let hours = (end - start).num_minutes() as f64 / 60.0;
+            if end <= start {
+                errors.insert(EditField::EndTime, t!("edit_end_before_start").to_string());
+            } else if validation.max_hours > 0.0 && hours > validation.max_hours {
+                errors.insert(
+                    EditField::EndTime,
+                    t!("edit_too_long", hours = validation.max_hours).to_string(),
+                );
+            } else if validation.check_overlaps {
+                if let Some(other) = self.overlapping_entry(entries, user_id, start, end, timezone)
+                {
+                    errors.insert(
+                        EditField::StartTime,
+                        t!(
+                            "edit_overlaps",
+                            start = datetime::format_time(&other.started_at, timezone),
+                            end = datetime::format_time(&other.ended_at, timezone)
+                        )
+                        .to_string(),
+                    );
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Find a MoneyBird entry of the user that overlaps the given local start and end,
+    /// skipping the entry that is being edited
+    fn overlapping_entry<'a>(
+        &self,
+        entries: &'a [TimeEntryForTable],
+        user_id: Option<&str>,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        timezone: &str,
+    ) -> Option<&'a TimeEntryForTable> {
+        let local = |value: &str| {
+            datetime::parse_iso_datetime(value, timezone).map(|value| value.naive_local())
+        };
+        entries
+            .iter()
+            .filter(|entry| entry.source == "moneybird")
+            .filter(|entry| Some(entry.id.as_str()) != self.time_entry_id.as_deref())
+            .filter(|entry| user_id.is_none() || entry.user_id.as_deref() == user_id)
+            .find(
+                |entry| match (local(&entry.started_at), local(&entry.ended_at)) {
+                    (Some(other_start), Some(other_end)) => start < other_end && other_start < end,
+                    _ => false,
+                },
+            )
+    }
+
+    /// Convert the EditState to a TimeEntry for updating, interpreting input times in the provided timezone.
+    /// A rounding rule moves the end time so the duration is rounded. Fails with a translated
+    /// message when a date or time can't be parsed.
+    pub fn try_into_time_entry(
+        &self,
+        timezone: &str,
+        rounding: Option<RoundingRule>,
+    ) -> Result<crate::moneybird::types::TimeEntry, String> {
+        // Get the timezone object, fallback to UTC
+        let tz: Tz = timezone.parse().unwrap_or(chrono_tz::UTC);
 
         // Combine date and time into NaiveDateTime
-        let start_naive = NaiveDateTime::new(start_date, start_time);
-        let end_naive = NaiveDateTime::new(end_date, end_time);
+        let start_naive = self
+            .start_datetime()
+            .ok_or_else(|| t!("edit_invalid_start").to_string())?;
+        let mut end_naive = self
+            .end_datetime()
+            .ok_or_else(|| t!("edit_invalid_end").to_string())?;
+        if let Some(rule) = rounding.filter(|rule| rule.is_active() && end_naive > start_naive) {
+            let minutes = (end_naive - start_naive).num_minutes() as u64;
+            end_naive = start_naive + chrono::Duration::minutes(rule.round(minutes) as i64);
+        }
 
         // Interpret the NaiveDateTime using the administration's timezone
         let start_in_admin_tz = tz
@@ -308,7 +668,7 @@ impl EditState {
         let started_at = start_in_admin_tz.with_timezone(&Utc).to_rfc3339();
         let ended_at = end_in_admin_tz.with_timezone(&Utc).to_rfc3339();
 
-        crate::moneybird::types::TimeEntry {
+        Ok(crate::moneybird::types::TimeEntry {
             id: self.time_entry_id.clone(),
             description: Some(self.description.clone()),
             project_id: self.project_id.clone(),
@@ -364,7 +724,28 @@ impl EditState {
             paused_duration: None,
             updated_at: None,
             user_id: None,
-        }
+        })
+    }
+
+    /// The error to show under a field, input that could not be understood comes first
+    pub(crate) fn field_error(&self, field: EditField) -> Option<&String> {
+        self.input_errors.get(&field).or_else(|| {
+            if self.save_attempted || self.touched_fields.contains(&field) {
+                self.validation_errors.get(&field)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Number of fields to fix before the entry can be saved
+    pub(crate) fn error_count(&self) -> usize {
+        let fields: HashSet<&EditField> = self
+            .input_errors
+            .keys()
+            .chain(self.validation_errors.keys())
+            .collect();
+        fields.len()
     }
 
     /// Check if this is in import mode
@@ -408,6 +789,7 @@ impl Default for EditField {
 
 pub(crate) struct AppModel {
     pub config: Configuration,
+    pub keybindings: KeyBindings, // Active key bindings, the defaults with the configured overrides
     pub running_state: RunningState,
     pub client: moneybird::Client,
     pub administration: Administration,
@@ -420,7 +802,19 @@ pub(crate) struct AppModel {
     pub users: Vec<User>,
     pub user_selection_active: bool,
     pub user_selection_state: ListState,
+    pub user_switcher_active: bool, // Reuses the user selection list to pick whose entries to show
     pub search_state: SearchState,
+    pub saved_search_state: SavedSearchState,
+    pub command_palette_state: CommandPaletteState,
+    pub batch_import_state: BatchImportState,
+    pub group_state: GroupState,
+    pub time_entry_filter: TimeEntryFilter,
+    pub week_lock: Option<WeekLock>, // Set when the viewed week has been submitted
+    pub(crate) locks: Option<LockStore>, // Where submitted weeks are kept, set once the configuration is loaded
+    pub undo_stack: UndoStack,
+    pub import_links: ImportLinks, // Plugin entries that were imported, by MoneyBird id
+    pub deleted_links: ImportLinks, // Links of deleted entries, restored when a delete is undone
+    pub analysis: Analysis,        // Overlaps, gaps and duplicates of the loaded entries
     pub appearance: Appearance,
     pub week_offset: i32, // How many weeks from current (0 = current, -1 = previous, 1 = next)
     pub modal_stack: ModalStack,
@@ -430,20 +824,25 @@ pub(crate) struct AppModel {
     pub modal_interaction: ModalInteraction,
     pub key_debounce: KeyDebounce,
     pub table_area: Option<Rect>,
+    pub table_header_columns: Vec<(u16, u16, SortColumn)>, // Clickable header ranges (x start, x end)
     pub edit_form_area: Option<Rect>,
     // Plugin system
     pub plugin_manager: Option<crate::plugin::PluginManager>,
     pub plugin_entries: Vec<TimeEntryForTable>,
     pub plugin_view_state: PluginViewState,
     pub plugin_list_area: Option<Rect>,
+    pub(crate) plugin_progress: std::collections::BTreeMap<String, PluginProgress>, // By plugin name
+    pub(crate) plugin_fetch: Option<tokio::task::JoinHandle<FetchedTimeEntries>>, // Plugin entries being fetched
 }
 
 impl Default for AppModel {
     fn default() -> Self {
         Self {
             config: Configuration::default(),
+            keybindings: KeyBindings::default(),
             running_state: RunningState::default(),
-            client: crate::api::create_moneybird_client(&crate::config::get_configuration()),
+            // Replaced once the configuration is loaded, so tests don't read it
+            client: crate::api::create_moneybird_client(&Configuration::default()),
             administration: Administration::default(),
             time_entries: Vec::new(),
             time_entries_for_table_backup: Vec::new(),
@@ -454,7 +853,19 @@ impl Default for AppModel {
             users: Vec::new(),
             user_selection_active: false,
             user_selection_state: ListState::default(),
+            user_switcher_active: false,
             search_state: SearchState::default(),
+            saved_search_state: SavedSearchState::default(),
+            command_palette_state: CommandPaletteState::default(),
+            batch_import_state: BatchImportState::default(),
+            group_state: GroupState::default(),
+            time_entry_filter: TimeEntryFilter::default(),
+            week_lock: None,
+            locks: None,
+            undo_stack: UndoStack::default(),
+            import_links: ImportLinks::default(),
+            deleted_links: ImportLinks::default(),
+            analysis: Analysis::default(),
             appearance: Appearance::default(),
             week_offset: 0,
             modal_stack: ModalStack::default(),
@@ -464,11 +875,14 @@ impl Default for AppModel {
             modal_interaction: ModalInteraction::new(300), // 300ms cooldown for modals
             key_debounce: KeyDebounce::new(200),           // 200ms cooldown for keypresses
             table_area: None,
+            table_header_columns: Vec::new(),
             edit_form_area: None,
             plugin_manager: None,
             plugin_entries: Vec::new(),
             plugin_view_state: PluginViewState::default(),
             plugin_list_area: None,
+            plugin_progress: std::collections::BTreeMap::new(),
+            plugin_fetch: None,
         }
     }
 }
@@ -483,7 +897,243 @@ pub(crate) enum Mode {
     Unspecified,
 }
 
+/// Compare two time entries for the table sort, ties are broken on the start time
+fn compare_time_entries(
+    a: &TimeEntryForTable,
+    b: &TimeEntryForTable,
+    sort: TableSort,
+) -> std::cmp::Ordering {
+    let by_start = || match (
+        DateTime::parse_from_rfc3339(&a.started_at),
+        DateTime::parse_from_rfc3339(&b.started_at),
+    ) {
+        (Ok(a_start), Ok(b_start)) => a_start.cmp(&b_start),
+        _ => a.started_at.cmp(&b.started_at),
+    };
+    let duration = |entry: &TimeEntryForTable| {
+        let (hours, minutes) = datetime::calculate_duration(&entry.started_at, &entry.ended_at);
+        hours * 60 + minutes
+    };
+
+    let ordering = match sort.column {
+        SortColumn::Date => by_start(),
+        SortColumn::Client => a
+            .customer
+            .to_lowercase()
+            .cmp(&b.customer.to_lowercase())
+            .then_with(by_start),
+        SortColumn::Project => a
+            .project
+            .to_lowercase()
+            .cmp(&b.project.to_lowercase())
+            .then_with(by_start),
+        SortColumn::Duration => duration(a).cmp(&duration(b)).then_with(by_start),
+        SortColumn::Source => a
+            .source
+            .to_lowercase()
+            .cmp(&b.source.to_lowercase())
+            .then_with(by_start),
+    };
+
+    match sort.direction {
+        SortDirection::Ascending => ordering,
+        SortDirection::Descending => ordering.reverse(),
+    }
+}
+
 impl AppModel {
+    /// Build the lock key of the viewed week for a user, defaulting to the
+    /// filtered user and then the configured user
+    pub(crate) fn week_key(&self, user_id: Option<String>) -> WeekKey {
+        self.week_key_at(self.week_offset, user_id)
+    }
+
+    /// Build the lock key of the week at `week_offset` from the current week
+    pub(crate) fn week_key_at(&self, week_offset: i32, user_id: Option<String>) -> WeekKey {
+        let admin_timezone_str = self
+            .administration
+            .time_zone
+            .clone()
+            .unwrap_or_else(|| "UTC".to_string());
+        let (week, year) = datetime::get_week_number(
+            week_offset,
+            &admin_timezone_str,
+            &self.config.week_starts_on,
+        );
+
+        WeekKey {
+            administration_id: self.administration.id.clone().unwrap_or_default(),
+            user_id: user_id
+                .or_else(|| self.time_entry_filter.user_id.clone())
+                .or_else(|| self.config.user_id.clone())
+                .unwrap_or_default(),
+            year,
+            week,
+        }
+    }
+
+    /// Look for overlaps, gaps and duplicates in all loaded entries, not only the ones a
+    /// search shows, so hidden entries still count
+    pub(crate) fn analyze_time_entries(&mut self) {
+        let admin_timezone_str = self
+            .administration
+            .time_zone
+            .clone()
+            .unwrap_or_else(|| "UTC".to_string());
+        self.analysis = analysis::analyze(
+            &self.time_entries_for_table_backup,
+            &admin_timezone_str,
+            &self.config.analysis,
+        );
+    }
+
+    /// Mark the plugin entries that were imported as one of the loaded MoneyBird entries.
+    /// Links to entries that aren't loaded, like deleted ones, are left alone.
+    pub(crate) fn link_plugin_entries(&mut self) {
+        let moneybird_ids: HashSet<String> = self
+            .time_entries
+            .iter()
+            .filter_map(|entry| entry.id.clone())
+            .collect();
+        let entries = self
+            .time_entries_for_table
+            .iter_mut()
+            .chain(self.time_entries_for_table_backup.iter_mut());
+        for entry in entries {
+            let linked_id = entry.plugin_key().and_then(|plugin| {
+                self.import_links
+                    .get(plugin, &entry.id)
+                    .filter(|moneybird_id| moneybird_ids.contains(*moneybird_id))
+                    .cloned()
+            });
+            entry.linked_id = linked_id;
+        }
+    }
+
+    /// Merge the selected plugin entry with its adjacent fragments into one entry, until
+    /// the next refresh. Returns how many entries were merged, 0 when there were none.
+    pub(crate) fn merge_plugin_fragments(&mut self, id: &str) -> usize {
+        let Some(selected) = self
+            .time_entries_for_table_backup
+            .iter()
+            .position(|entry| entry.id == id && entry.source != "moneybird")
+        else {
+            return 0;
+        };
+        let indices = analysis::adjacent_fragments(
+            &self.time_entries_for_table_backup,
+            selected,
+            self.config.merge.max_gap_minutes,
+        );
+        let fragments: Vec<&TimeEntryForTable> = indices
+            .iter()
+            .map(|idx| &self.time_entries_for_table_backup[*idx])
+            .collect();
+        let Some(merged) = analysis::merge_fragments(&fragments, &self.config.merge.separator)
+        else {
+            return 0;
+        };
+
+        let merged_id = merged.id.clone();
+        analysis::replace_fragments(&mut self.time_entries_for_table_backup, &indices, merged);
+
+        // Keep the merged entry selected while sorting the table again
+        self.time_entries_for_table = self.time_entries_for_table_backup.clone();
+        if let Some(idx) = self
+            .time_entries_for_table
+            .iter()
+            .position(|entry| entry.id == merged_id)
+        {
+            self.time_entry_table_state.select(Some(idx));
+        }
+        self.sort_time_entries();
+        self.analyze_time_entries();
+        indices.len()
+    }
+
+    /// Find a loaded entry by id, MoneyBird entries first
+    pub(crate) fn find_loaded_entry(&self, id: &str) -> Option<&TimeEntryForTable> {
+        self.time_entries_for_table_backup
+            .iter()
+            .filter(|entry| entry.id == id)
+            .min_by_key(|entry| entry.source != "moneybird")
+    }
+
+    /// Duration of an entry in minutes, rounded with the rule of its contact
+    pub(crate) fn rounded_minutes(&self, entry: &TimeEntryForTable) -> u64 {
+        let (hours, minutes) = datetime::calculate_duration(&entry.started_at, &entry.ended_at);
+        self.config
+            .rounding
+            .rule_for(entry.contact_id.as_deref(), &entry.customer)
+            .round(hours * 60 + minutes)
+    }
+
+    /// Check the entry in the edit form, the errors show under the fields and block saving
+    pub(crate) fn validate_edit(&mut self) {
+        let admin_timezone_str = self
+            .administration
+            .time_zone
+            .clone()
+            .unwrap_or_else(|| "UTC".to_string());
+        // An edited entry is checked against the entries of its owner, who can be a
+        // colleague when showing the whole team. New entries are for the configured user.
+        let user_id = self
+            .edit_state
+            .time_entry_id
+            .as_deref()
+            .and_then(|id| {
+                self.time_entries
+                    .iter()
+                    .find(|entry| entry.id.as_deref() == Some(id))
+            })
+            .and_then(|entry| entry.user_id.as_deref())
+            .or(self.config.user_id.as_deref());
+        let errors = self.edit_state.validate(
+            &self.config.validation,
+            &self.time_entries_for_table_backup,
+            user_id,
+            &admin_timezone_str,
+        );
+        self.edit_state.validation_errors = errors;
+    }
+
+    /// Build the lock key of the week a MoneyBird time entry starts in
+    pub(crate) fn week_key_for_entry(&self, entry: &TimeEntry) -> WeekKey {
+        let admin_timezone_str = self
+            .administration
+            .time_zone
+            .clone()
+            .unwrap_or_else(|| "UTC".to_string());
+        let week_offset = entry
+            .started_at
+            .as_deref()
+            .and_then(|started_at| datetime::parse_iso_datetime(started_at, &admin_timezone_str))
+            .map(|started_at| {
+                datetime::get_week_offset_for_date(
+                    started_at.date_naive(),
+                    &admin_timezone_str,
+                    &self.config.week_starts_on,
+                )
+            })
+            .unwrap_or(self.week_offset);
+        self.week_key_at(week_offset, entry.user_id.clone())
+    }
+
+    /// Check if the plugins are still being asked for their entries
+    pub(crate) fn loading_plugin_entries(&self) -> bool {
+        self.plugin_fetch.is_some()
+    }
+
+    /// The lock of a week, if it has been submitted
+    pub(crate) fn read_week_lock(&self, key: &WeekKey) -> Option<WeekLock> {
+        self.locks.as_ref()?.read_lock(key)
+    }
+
+    /// Reload the lock state of the viewed week
+    pub(crate) fn refresh_week_lock(&mut self) {
+        self.week_lock = self.read_week_lock(&self.week_key(None));
+    }
+
     // Helper method to ensure selection is valid
     pub(crate) fn ensure_valid_selection(&mut self) {
         if let Some(selected_idx) = self.time_entry_table_state.selected() {
@@ -575,6 +1225,255 @@ impl AppModel {
         false
     }
 
+    /// Sort all time entries on the configured column and refresh the visible rows,
+    /// keeping the selected entry selected
+    pub(crate) fn sort_time_entries(&mut self) {
+        let selected_id = self
+            .time_entry_table_state
+            .selected()
+            .and_then(|idx| self.time_entries_for_table.get(idx))
+            .map(|entry| entry.id.clone());
+
+        let sort = self.config.sort;
+        self.time_entries_for_table_backup
+            .sort_by(|a, b| compare_time_entries(a, b, sort));
+
+        // Searching sorts on score first, and on this order for equal scores
+        if self.search_state.active {
+            self.filter_items();
+        } else {
+            self.time_entries_for_table = self.time_entries_for_table_backup.clone();
+        }
+
+        if let Some(selected_id) = selected_id {
+            if let Some(idx) = self
+                .time_entries_for_table
+                .iter()
+                .position(|entry| entry.id == selected_id)
+            {
+                self.time_entry_table_state.select(Some(idx));
+            }
+        }
+        self.ensure_valid_selection();
+    }
+
+    /// Key and label of the group a time entry belongs to
+    fn group_of(&self, entry: &TimeEntryForTable) -> (String, String) {
+        let admin_timezone_str = self
+            .administration
+            .time_zone
+            .clone()
+            .unwrap_or_else(|| "UTC".to_string());
+        let label_or_none = |value: &str| {
+            if value.is_empty() {
+                t!("ui_group_empty").to_string()
+            } else {
+                value.to_string()
+            }
+        };
+
+        match self.group_state.group_by {
+            GroupBy::None => (String::new(), String::new()),
+            GroupBy::Day => (
+                datetime::format_date_iso(&entry.started_at, &admin_timezone_str),
+                datetime::format_date(&entry.started_at, &admin_timezone_str),
+            ),
+            GroupBy::Client => (
+                entry.customer.to_lowercase(),
+                label_or_none(&entry.customer),
+            ),
+            GroupBy::Project => (entry.project.to_lowercase(), label_or_none(&entry.project)),
+            GroupBy::Source => (entry.source.to_lowercase(), label_or_none(&entry.source)),
+        }
+    }
+
+    /// The input of the command palette
+    pub(crate) fn palette_input(&self) -> String {
+        self.command_palette_state.input.lines().join(" ")
+    }
+
+    /// The palette commands that match the input, best match first
+    pub(crate) fn palette_matches(&self) -> Vec<PaletteMatch> {
+        let commands = palette::commands(&self.keybindings, self.command_palette_state.mode);
+        palette::filter_commands(&commands, &self.palette_input())
+    }
+
+    /// Build the visible table rows. Groups appear in the order of their first entry,
+    /// so the table sort decides both the group order and the order within a group.
+    pub(crate) fn table_rows(&self) -> Vec<TableRow> {
+        if self.group_state.group_by == GroupBy::None {
+            return (0..self.time_entries_for_table.len())
+                .map(TableRow::Entry)
+                .collect();
+        }
+
+        let mut groups: Vec<(String, String, Vec<usize>)> = Vec::new();
+        for (idx, entry) in self.time_entries_for_table.iter().enumerate() {
+            let (key, label) = self.group_of(entry);
+            match groups
+                .iter_mut()
+                .find(|(group_key, _, _)| *group_key == key)
+            {
+                Some((_, _, indices)) => indices.push(idx),
+                None => groups.push((key, label, vec![idx])),
+            }
+        }
+
+        let mut rows = Vec::new();
+        for (key, label, indices) in groups {
+            let minutes = indices
+                .iter()
+                .map(|&idx| &self.time_entries_for_table[idx])
+                .filter(|entry| entry.source.to_lowercase() == "moneybird")
+                .map(|entry| self.rounded_minutes(entry))
+                .sum();
+            let collapsed = self.group_state.collapsed.contains(&key);
+            rows.push(TableRow::Group {
+                key,
+                label,
+                count: indices.len(),
+                minutes,
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(indices.into_iter().map(TableRow::Entry));
+            }
+        }
+
+        rows
+    }
+
+    /// Position of the selection in `rows`, the header stands in for an entry of a collapsed group
+    pub(crate) fn selected_table_row(&self, rows: &[TableRow]) -> Option<usize> {
+        let group_key = match self.time_entry_table_state.selected() {
+            Some(selected_idx) => {
+                if let Some(pos) = rows
+                    .iter()
+                    .position(|row| *row == TableRow::Entry(selected_idx))
+                {
+                    return Some(pos);
+                }
+                self.time_entries_for_table
+                    .get(selected_idx)
+                    .map(|entry| self.group_of(entry).0)
+            }
+            None => self.group_state.selected_group.clone(),
+        }?;
+
+        rows.iter()
+            .position(|row| matches!(row, TableRow::Group { key, .. } if *key == group_key))
+    }
+
+    /// Select a visible row, either an entry or a group header
+    pub(crate) fn select_table_row(&mut self, rows: &[TableRow], row_idx: usize) {
+        match rows.get(row_idx) {
+            Some(TableRow::Entry(idx)) => {
+                self.time_entry_table_state.select(Some(*idx));
+                self.group_state.selected_group = None;
+            }
+            Some(TableRow::Group { key, .. }) => {
+                self.time_entry_table_state.select(None);
+                self.group_state.selected_group = Some(key.clone());
+            }
+            None => {}
+        }
+    }
+
+    /// Check if a group header is selected instead of a visible entry. That's the case when
+    /// a header was selected, or when the selected entry is hidden in a collapsed group.
+    pub(crate) fn is_group_header_selected(&self) -> bool {
+        if self.group_state.group_by == GroupBy::None {
+            return false;
+        }
+
+        match self.time_entry_table_state.selected() {
+            Some(selected_idx) => self
+                .time_entries_for_table
+                .get(selected_idx)
+                .is_some_and(|entry| self.group_state.collapsed.contains(&self.group_of(entry).0)),
+            None => self.group_state.selected_group.is_some(),
+        }
+    }
+
+    /// Switch the grouping, a selected header falls back to the first entry of its group
+    pub(crate) fn set_group_by(&mut self, group_by: GroupBy) {
+        if self.time_entry_table_state.selected().is_none()
+            && !self.time_entries_for_table.is_empty()
+        {
+            let first_in_group = self.group_state.selected_group.as_ref().and_then(|key| {
+                self.time_entries_for_table
+                    .iter()
+                    .position(|entry| self.group_of(entry).0 == *key)
+            });
+            self.time_entry_table_state
+                .select(Some(first_in_group.unwrap_or(0)));
+        }
+
+        // Group keys differ per grouping, so start with everything expanded
+        self.group_state.group_by = group_by;
+        self.group_state.collapsed.clear();
+        self.group_state.selected_group = None;
+        self.group_state.table_state = TableState::default();
+    }
+
+    /// Collapse or expand the group of the selected row. Returns the group label and
+    /// whether the group is collapsed now.
+    pub(crate) fn toggle_selected_group(&mut self) -> Option<(String, bool)> {
+        let rows = self.table_rows();
+        let (key, label) = match rows.get(self.selected_table_row(&rows)?)? {
+            TableRow::Group { key, label, .. } => (key.clone(), label.clone()),
+            TableRow::Entry(idx) => self.group_of(&self.time_entries_for_table[*idx]),
+        };
+
+        let collapsed = !self.group_state.collapsed.remove(&key);
+        if collapsed {
+            // Entries of a collapsed group can't be selected, so select its header
+            self.group_state.collapsed.insert(key.clone());
+            self.time_entry_table_state.select(None);
+            self.group_state.selected_group = Some(key);
+        }
+
+        Some((label, collapsed))
+    }
+
+    /// Expand all groups when any is collapsed, otherwise collapse all of them.
+    /// Returns whether the groups are collapsed now.
+    pub(crate) fn toggle_all_groups(&mut self) -> bool {
+        if !self.group_state.collapsed.is_empty() {
+            self.group_state.collapsed.clear();
+            return false;
+        }
+
+        self.group_state.collapsed = self
+            .time_entries_for_table
+            .iter()
+            .map(|entry| self.group_of(entry).0)
+            .collect();
+
+        // Move the selection from the entry to its header
+        let selected_key = self
+            .time_entry_table_state
+            .selected()
+            .and_then(|idx| self.time_entries_for_table.get(idx))
+            .map(|entry| self.group_of(entry).0);
+        if let Some(key) = selected_key {
+            self.group_state.selected_group = Some(key);
+            self.time_entry_table_state.select(None);
+        }
+
+        true
+    }
+
+    /// Open the search input with the given query and filter the table
+    pub(crate) fn apply_search(&mut self, query: &str) {
+        let mut text_input = TextArea::new(vec![query.to_string()]);
+        text_input.move_cursor(tui_textarea::CursorMove::End);
+
+        self.search_state.active = true;
+        self.search_state.text_input = text_input;
+        self.filter_items();
+    }
+
     // Filter items based on query
     pub(crate) fn filter_items(&mut self) {
         // Get the current search text and filter items
@@ -588,67 +1487,417 @@ impl AppModel {
 
         // Reset to full list for each query. The user may have entered a non mat
         self.time_entries_for_table = self.time_entries_for_table_backup.clone();
+        self.search_state.error = None;
 
         self.ensure_valid_selection();
 
+        // Keep the full list while the query can't be parsed, e.g. halfway typing `duration>`
+        let query = match Query::parse(&query) {
+            Ok(query) => query,
+            Err(err) => {
+                self.search_state.error = Some(err.message);
+                return;
+            }
+        };
+
         if query.is_empty() {
             return;
         }
 
-        // Convert query to lowercase for case-insensitive matching
-        let query_lower = query.to_lowercase();
+        let admin_timezone_str = self
+            .administration
+            .time_zone
+            .clone()
+            .unwrap_or_else(|| "UTC".to_string());
+        let context = QueryContext::new(&admin_timezone_str);
 
-        // Filter items with direct string matching:
-        // - All matches must contain the query string (case insensitive)
-        // - Score is calculated based on which fields match:
-        //   - client: 4 points
-        //   - project: 2 points
-        //   - description: 1 point
-        let scored_items: Vec<(TimeEntryForTable, i32)> = self
+        // Score the matching items on their client (4), project (2) and description (1)
+        let mut scored_items: Vec<(TimeEntryForTable, i64)> = self
             .time_entries_for_table
             .iter()
-            .filter_map(|item| {
-                let customer_lower = item.customer.to_lowercase();
-                let project_lower = item.project.to_lowercase();
-                let description_lower = item.description.to_lowercase();
-
-                // Simple contains matching
-                let client_match = customer_lower.contains(&query_lower);
-                let project_match = project_lower.contains(&query_lower);
-                let desc_match = description_lower.contains(&query_lower);
-
-                // Calculate score based on which fields match
-                let mut score = 0;
-                if client_match {
-                    score += 4;
-                }
-                if project_match {
-                    score += 2;
-                }
-                if desc_match {
-                    score += 1;
-                }
-
-                // Only include items with at least one match
-                if score > 0 {
-                    Some((item.clone(), score))
-                } else {
-                    None
-                }
-            })
+            .filter(|item| query.matches(item, &context))
+            .map(|item| (item.clone(), query.score(item)))
             .collect();
 
-        if self.time_entries_for_table.len() > scored_items.len() {
+        // Best matches first, the configured sort decides between equally good matches.
+        // Without text terms every score is 0, so only the sort is left.
+        let sort = self.config.sort;
+        scored_items.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| compare_time_entries(a, b, sort))
+        });
+        let filtered_items: Vec<TimeEntryForTable> =
+            scored_items.into_iter().map(|(item, _)| item).collect();
+
+        if self.time_entries_for_table.len() > filtered_items.len() {
             self.log_success(format!(
                 "Filtered items from {} to {}",
                 self.time_entries_for_table.len(),
-                scored_items.len()
+                filtered_items.len()
             ));
         }
 
-        // Extract just the items
-        self.time_entries_for_table = scored_items.into_iter().map(|(item, _)| item).collect();
+        self.time_entries_for_table = filtered_items;
 
         self.ensure_valid_selection();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit_state(start_time: &str, end_time: &str) -> EditState {
+        EditState {
+            description: "Standup".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: start_time.to_string(),
+            end_date: "2025-04-07".to_string(),
+            end_time: end_time.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn moneybird_entry(id: &str, user_id: &str, start: &str, end: &str) -> TimeEntryForTable {
+        TimeEntryForTable {
+            id: id.to_string(),
+            started_at: format!("2025-04-07T{}:00Z", start),
+            ended_at: format!("2025-04-07T{}:00Z", end),
+            source: "moneybird".to_string(),
+            user_id: Some(user_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn grouped_entry(customer: &str, source: &str, start: &str, end: &str) -> TimeEntryForTable {
+        TimeEntryForTable {
+            customer: customer.to_string(),
+            source: source.to_string(),
+            started_at: format!("2025-04-07T{}:00Z", start),
+            ended_at: format!("2025-04-07T{}:00Z", end),
+            ..Default::default()
+        }
+    }
+
+    /// Entries of Acme, Beta and acme again, grouped by client
+    fn grouped_model() -> AppModel {
+        AppModel {
+            time_entries_for_table: vec![
+                grouped_entry("Acme", "moneybird", "09:00", "10:00"),
+                grouped_entry("Beta", "gitlab", "10:00", "11:00"),
+                grouped_entry("acme", "moneybird", "11:00", "12:30"),
+            ],
+            group_state: GroupState {
+                group_by: GroupBy::Client,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn group_row(key: &str, label: &str, count: usize, minutes: u64, collapsed: bool) -> TableRow {
+        TableRow::Group {
+            key: key.to_string(),
+            label: label.to_string(),
+            count,
+            minutes,
+            collapsed,
+        }
+    }
+
+    #[test]
+    fn test_table_rows() {
+        let mut model = grouped_model();
+        // Only MoneyBird entries count towards the subtotal
+        assert_eq!(
+            model.table_rows(),
+            vec![
+                group_row("acme", "Acme", 2, 150, false),
+                TableRow::Entry(0),
+                TableRow::Entry(2),
+                group_row("beta", "Beta", 1, 0, false),
+                TableRow::Entry(1),
+            ]
+        );
+
+        model.group_state.collapsed.insert("acme".to_string());
+        assert_eq!(
+            model.table_rows(),
+            vec![
+                group_row("acme", "Acme", 2, 150, true),
+                group_row("beta", "Beta", 1, 0, false),
+                TableRow::Entry(1),
+            ]
+        );
+
+        model.group_state.group_by = GroupBy::None;
+        assert_eq!(
+            model.table_rows(),
+            vec![TableRow::Entry(0), TableRow::Entry(1), TableRow::Entry(2)]
+        );
+    }
+
+    #[test]
+    fn test_select_table_row() {
+        let mut model = grouped_model();
+        let rows = model.table_rows();
+
+        model.select_table_row(&rows, 3);
+        assert_eq!(model.time_entry_table_state.selected(), None);
+        assert_eq!(model.group_state.selected_group.as_deref(), Some("beta"));
+        assert_eq!(model.selected_table_row(&rows), Some(3));
+        assert!(model.is_group_header_selected());
+
+        model.select_table_row(&rows, 2);
+        assert_eq!(model.time_entry_table_state.selected(), Some(2));
+        assert_eq!(model.group_state.selected_group, None);
+        assert_eq!(model.selected_table_row(&rows), Some(2));
+        assert!(!model.is_group_header_selected());
+
+        // Rows past the end leave the selection alone
+        model.select_table_row(&rows, rows.len());
+        assert_eq!(model.time_entry_table_state.selected(), Some(2));
+
+        // An entry hidden in a collapsed group is shown by its header
+        model.group_state.collapsed.insert("acme".to_string());
+        let rows = model.table_rows();
+        assert_eq!(model.selected_table_row(&rows), Some(0));
+        assert!(model.is_group_header_selected());
+    }
+
+    #[test]
+    fn test_toggle_selected_group() {
+        let mut model = grouped_model();
+        model.time_entry_table_state.select(Some(0));
+
+        // Collapsing the group of the selected entry selects its header
+        assert_eq!(
+            model.toggle_selected_group(),
+            Some(("Acme".to_string(), true))
+        );
+        assert_eq!(model.time_entry_table_state.selected(), None);
+        assert_eq!(model.group_state.selected_group.as_deref(), Some("acme"));
+        assert!(model.is_group_header_selected());
+        assert_eq!(model.table_rows().len(), 3);
+
+        assert_eq!(
+            model.toggle_selected_group(),
+            Some(("Acme".to_string(), false))
+        );
+        assert!(model.group_state.collapsed.is_empty());
+        assert_eq!(model.table_rows().len(), 5);
+        assert!(model.is_group_header_selected());
+
+        model.group_state.selected_group = None;
+        assert_eq!(model.toggle_selected_group(), None);
+    }
+
+    fn error_fields(errors: std::collections::HashMap<EditField, String>) -> Vec<EditField> {
+        let mut fields: Vec<EditField> = errors.into_keys().collect();
+        fields.sort_by_key(|field| format!("{:?}", field));
+        fields
+    }
+
+    #[test]
+    fn test_validate_end_before_start() {
+        let validation = ValidationConfig::default();
+        let validate = |edit_state: EditState| {
+            error_fields(edit_state.validate(&validation, &[], None, "UTC"))
+        };
+        assert!(validate(edit_state("09:00", "10:00")).is_empty());
+        assert_eq!(
+            validate(edit_state("10:00", "09:00")),
+            vec![EditField::EndTime]
+        );
+        assert_eq!(
+            validate(edit_state("10:00", "10:00")),
+            vec![EditField::EndTime]
+        );
+
+        // An end on the next day is fine
+        let mut overnight = edit_state("22:00", "01:00");
+        overnight.end_date = "2025-04-08".to_string();
+        assert!(validate(overnight).is_empty());
+
+        let mut unreadable = edit_state("9", "10:00");
+        unreadable.description = " ".to_string();
+        assert_eq!(
+            validate(unreadable),
+            vec![EditField::Description, EditField::StartTime]
+        );
+    }
+
+    #[test]
+    fn test_validate_max_hours() {
+        let mut validation = ValidationConfig {
+            max_hours: 8.0,
+            ..Default::default()
+        };
+        let validate = |validation: &ValidationConfig, end_time: &str| {
+            error_fields(edit_state("08:00", end_time).validate(validation, &[], None, "UTC"))
+        };
+        assert!(validate(&validation, "16:00").is_empty());
+        assert_eq!(validate(&validation, "16:01"), vec![EditField::EndTime]);
+
+        // 0 turns the check off
+        validation.max_hours = 0.0;
+        assert!(validate(&validation, "23:00").is_empty());
+    }
+
+    #[test]
+    fn test_validate_require_project() {
+        let mut validation = ValidationConfig::default();
+        let mut edit_state = edit_state("09:00", "10:00");
+        assert!(edit_state
+            .validate(&validation, &[], None, "UTC")
+            .is_empty());
+
+        validation.require_project = true;
+        assert_eq!(
+            error_fields(edit_state.validate(&validation, &[], None, "UTC")),
+            vec![EditField::Project]
+        );
+        edit_state.project_id = Some("42".to_string());
+        assert!(edit_state
+            .validate(&validation, &[], None, "UTC")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_overlaps() {
+        let mut validation = ValidationConfig::default();
+        let entries = vec![
+            moneybird_entry("1", "7", "09:00", "10:00"),
+            moneybird_entry("2", "8", "11:00", "12:00"),
+            TimeEntryForTable {
+                source: "gitlab".to_string(),
+                ..moneybird_entry("3", "7", "13:00", "14:00")
+            },
+        ];
+        let overlaps = |edit_state: &EditState, validation: &ValidationConfig| {
+            error_fields(edit_state.validate(validation, &entries, Some("7"), "UTC"))
+                == vec![EditField::StartTime]
+        };
+
+        assert!(overlaps(&edit_state("09:30", "10:30"), &validation));
+        assert!(overlaps(&edit_state("08:00", "11:00"), &validation));
+        // Touching entries don't overlap
+        assert!(!overlaps(&edit_state("10:00", "11:00"), &validation));
+        // Entries of other users and plugin entries don't count
+        assert!(!overlaps(&edit_state("11:30", "12:30"), &validation));
+        assert!(!overlaps(&edit_state("13:30", "14:30"), &validation));
+
+        // The entry being edited doesn't overlap itself
+        let mut editing = edit_state("09:15", "10:15");
+        editing.time_entry_id = Some("1".to_string());
+        assert!(!overlaps(&editing, &validation));
+
+        validation.check_overlaps = false;
+        assert!(!overlaps(&edit_state("09:30", "10:30"), &validation));
+    }
+
+    #[test]
+    fn test_validate_edit_checks_entries_of_the_owner() {
+        let mut model = AppModel {
+            time_entries: vec![TimeEntry {
+                id: Some("2".to_string()),
+                user_id: Some("8".to_string()),
+                ..Default::default()
+            }],
+            time_entries_for_table_backup: vec![
+                moneybird_entry("1", "7", "09:00", "10:00"),
+                moneybird_entry("2", "8", "11:00", "12:00"),
+                moneybird_entry("3", "8", "13:00", "14:00"),
+            ],
+            ..Default::default()
+        };
+        model.config.user_id = Some("7".to_string());
+
+        // A new entry is checked against the entries of the configured user
+        model.edit_state = edit_state("09:30", "13:30");
+        model.validate_edit();
+        assert!(model
+            .edit_state
+            .validation_errors
+            .contains_key(&EditField::StartTime));
+        model.edit_state = edit_state("13:15", "13:45");
+        model.validate_edit();
+        assert!(model.edit_state.validation_errors.is_empty());
+
+        // An entry of a colleague is checked against their entries
+        model.edit_state = edit_state("09:30", "10:30");
+        model.edit_state.time_entry_id = Some("2".to_string());
+        model.validate_edit();
+        assert!(model.edit_state.validation_errors.is_empty());
+        model.edit_state = edit_state("13:15", "13:45");
+        model.edit_state.time_entry_id = Some("2".to_string());
+        model.validate_edit();
+        assert!(model
+            .edit_state
+            .validation_errors
+            .contains_key(&EditField::StartTime));
+    }
+
+    fn searched_ids(model: &mut AppModel, query: &str) -> Vec<String> {
+        model.search_state.text_input = TextArea::new(vec![query.to_string()]);
+        model.filter_items();
+        model
+            .time_entries_for_table
+            .iter()
+            .map(|entry| entry.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_items_sorts_on_score() {
+        let entry = |id: &str, customer: &str, description: &str, start: &str| TimeEntryForTable {
+            id: id.to_string(),
+            description: description.to_string(),
+            ..grouped_entry(customer, "moneybird", start, "12:00")
+        };
+        let mut model = AppModel {
+            time_entries_for_table_backup: vec![
+                entry("1", "Beta", "Call acme", "09:00"),
+                entry("2", "Acme", "Standup", "10:00"),
+                entry("3", "Gamma", "Review", "10:00"),
+                entry("4", "Beta", "Mail acme", "11:00"),
+            ],
+            ..Default::default()
+        };
+
+        // The client match goes first, the equally good description matches keep the sort
+        assert_eq!(searched_ids(&mut model, "acme"), vec!["2", "1", "4"]);
+
+        model.config.sort.direction = SortDirection::Descending;
+        assert_eq!(searched_ids(&mut model, "acme"), vec!["2", "4", "1"]);
+
+        // Without text terms all scores are equal, so only the sort is left
+        assert_eq!(
+            searched_ids(&mut model, "-client:gamma"),
+            vec!["4", "2", "1"]
+        );
+    }
+
+    #[test]
+    fn test_state_filter_from_str() {
+        let parse = |value: &str| value.parse::<TimeEntryStateFilter>();
+        assert_eq!(parse("all"), Ok(TimeEntryStateFilter::All));
+        assert_eq!(parse(" Open "), Ok(TimeEntryStateFilter::Open));
+        assert_eq!(parse("billed"), Ok(TimeEntryStateFilter::Billed));
+        assert_eq!(parse("non-billable"), Ok(TimeEntryStateFilter::NonBillable));
+        assert_eq!(parse("nonbillable"), Ok(TimeEntryStateFilter::NonBillable));
+        assert_eq!(
+            parse("paid"),
+            Err(t!("model_invalid_state_filter", state = "paid").to_string())
+        );
+
+        // Every state parses back from the value sent to MoneyBird
+        let mut state = TimeEntryStateFilter::All;
+        for _ in 0..4 {
+            assert_eq!(parse(state.as_str()), Ok(state));
+            state = state.next();
+        }
+        assert_eq!(state, TimeEntryStateFilter::All);
+    }
+}
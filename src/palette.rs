@@ -0,0 +1,188 @@
+use crate::datetime;
+use crate::event::{self, Message};
+use crate::fuzzy::fuzzy_match;
+use crate::keybindings::{Action, KeyBindings, KeyMode};
+use rust_i18n::t;
+
+/// Something that can be run from the command palette
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Command {
+    Action(Action), // Any action of the mode the palette was opened from
+    Week,           // `week 42` or `week 2024-W52`
+    GoTo,           // `goto 2025-03-01`, `goto -3w` or `goto last month`
+}
+
+impl Command {
+    /// Name to type in the palette, the actions use their key binding name
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Command::Action(action) => action.as_str(),
+            Command::Week => "week",
+            Command::GoTo => "goto",
+        }
+    }
+
+    /// Placeholder for the argument, shown after the name
+    pub(crate) fn argument(&self) -> Option<&'static str> {
+        match self {
+            Command::Action(Action::Export) => Some("[csv]"),
+            Command::Week => Some("<week>"),
+            Command::GoTo => Some("<week|date|-3w>"),
+            Command::Action(_) => None,
+        }
+    }
+
+    /// Whether the command can't run without an argument
+    pub(crate) fn requires_argument(&self) -> bool {
+        matches!(self, Command::Week | Command::GoTo)
+    }
+
+    /// Translated description for the palette list
+    pub(crate) fn description(&self) -> String {
+        match self {
+            Command::Action(action) => action.description(),
+            Command::Week => t!("palette_command_week").to_string(),
+            Command::GoTo => t!("palette_command_goto").to_string(),
+        }
+    }
+}
+
+/// A command that matches the palette input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PaletteMatch {
+    pub(crate) command: Command,
+    pub(crate) positions: Vec<usize>, // Matched characters of the name
+}
+
+/// The commands of a mode, its actions in help order followed by the ones with an argument
+pub(crate) fn commands(key_bindings: &KeyBindings, mode: KeyMode) -> Vec<Command> {
+    key_bindings
+        .actions(mode)
+        .into_iter()
+        // These open the palette, the go to action as the goto command
+        .filter(|action| !matches!(action, Action::CommandPalette | Action::GoTo))
+        .map(Command::Action)
+        .chain([Command::Week, Command::GoTo])
+        .collect()
+}
+
+/// Split the input into the command name and its argument, a leading `:` is ignored
+pub(crate) fn split_input(input: &str) -> (&str, &str) {
+    let input = input.trim().trim_start_matches(':').trim_start();
+    match input.split_once(char::is_whitespace) {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (input, ""),
+    }
+}
+
+/// Fuzzy match the commands against the name in the input, best match first. The
+/// description is matched as well, so `sort` also finds the sort direction.
+pub(crate) fn filter_commands(commands: &[Command], input: &str) -> Vec<PaletteMatch> {
+    let (name, _) = split_input(input);
+
+    let mut scored: Vec<(bool, i64, usize, PaletteMatch)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, command)| {
+            let (score, positions) = match fuzzy_match(name, command.name()) {
+                Some(found) => (found.score, found.positions),
+                // Only the name is shown highlighted
+                None => (fuzzy_match(name, &command.description())?.score, Vec::new()),
+            };
+            let exact = command.name() == name;
+            Some((
+                exact,
+                score,
+                idx,
+                PaletteMatch {
+                    command: *command,
+                    positions,
+                },
+            ))
+        })
+        .collect();
+
+    // An exact name goes first, otherwise keep the help order for equal scores
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+    scored.into_iter().map(|(_, _, _, found)| found).collect()
+}
+
+/// Turn a command and its argument into the message to run
+pub(crate) fn command_message(
+    command: Command,
+    argument: &str,
+    mode: KeyMode,
+    timezone: &str,
+    week_starts_on: &str,
+) -> Result<Message, String> {
+    match command {
+        Command::Action(Action::Export) => {
+            // CSV is the only export format for now
+            if argument.is_empty() || argument.eq_ignore_ascii_case("csv") {
+                Ok(Message::TimeEntryExport)
+            } else {
+                Err(t!("palette_unknown_export_format", format = argument).to_string())
+            }
+        }
+        Command::Action(action) => {
+            if !argument.is_empty() {
+                return Err(t!("palette_unexpected_argument", command = command.name()).to_string());
+            }
+            event::action_message(mode, action)
+                .ok_or_else(|| t!("palette_unknown_command", command = command.name()).to_string())
+        }
+        Command::Week => datetime::get_week_offset_for_week(argument, timezone, week_starts_on)
+            .map(Message::TimeEntryGoToWeek)
+            .ok_or_else(|| t!("palette_invalid_week", week = argument).to_string()),
+        Command::GoTo => datetime::get_week_offset_for_go_to(argument, timezone, week_starts_on)
+            .map(Message::TimeEntryGoToWeek)
+            .ok_or_else(|| t!("palette_invalid_go_to", target = argument).to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_input() {
+        assert_eq!(split_input(":week 42"), ("week", "42"));
+        assert_eq!(split_input("  goto   2025-03-01 "), ("goto", "2025-03-01"));
+        assert_eq!(split_input("export"), ("export", ""));
+        assert_eq!(split_input(""), ("", ""));
+    }
+
+    #[test]
+    fn test_filter_commands() {
+        let commands = commands(&KeyBindings::default(), KeyMode::Normal);
+        assert!(!commands.contains(&Command::Action(Action::CommandPalette)));
+        assert_eq!(filter_commands(&commands, "").len(), commands.len());
+
+        // The exact name wins over the other week commands
+        let matches = filter_commands(&commands, "week 42");
+        assert_eq!(matches[0].command, Command::Week);
+
+        let matches = filter_commands(&commands, "nxtwk");
+        assert_eq!(matches[0].command, Command::Action(Action::NextWeek));
+        assert!(filter_commands(&commands, "zzzz").is_empty());
+    }
+
+    #[test]
+    fn test_command_message() {
+        let message = |command, argument| {
+            command_message(command, argument, KeyMode::Normal, "UTC", "monday")
+        };
+        assert!(message(Command::Action(Action::Export), "csv") == Ok(Message::TimeEntryExport));
+        assert!(message(Command::Action(Action::Export), "pdf").is_err());
+        assert!(message(Command::Action(Action::Quit), "now").is_err());
+        assert!(message(Command::Week, "54").is_err());
+        assert!(message(Command::GoTo, "2025-13-01").is_err());
+        assert!(message(Command::GoTo, "last month").is_ok());
+        assert!(message(Command::Week, "2024-W52") == message(Command::GoTo, "2024-12-23"));
+        assert!(message(Command::Week, "1").is_ok());
+        assert!(
+            message(Command::Action(Action::ClearSearch), "").is_err(),
+            "clearing the search only works from the search bar"
+        );
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 use std::fs;
+use std::future::Future;
 use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
@@ -21,7 +22,10 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::{mpsc, oneshot};
 
-use crate::model::TimeEntryForTable;
+use crate::model::{LogSeverity, TimeEntryForTable};
+
+/// Entries fetched from the plugins, with the errors of the plugins that failed by name
+pub(crate) type FetchedTimeEntries = (Vec<PluginTimeEntry>, Vec<(String, String)>);
 
 /// Time entry model shared between host and plugins
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -52,6 +56,12 @@ impl From<PluginTimeEntry> for TimeEntryForTable {
             icon: None, // Icons will be applied by the app based on plugin_name
             source: entry.source,
             plugin_name: entry.plugin_name, // Pass through plugin_name directly
+            user: String::new(),            // Plugin entries don't belong to a MoneyBird user
+            user_id: None,
+            contact_id: None,
+            tags: entry.tags,
+            linked_id: None, // Set once the links with MoneyBird entries are known
+            merged_ids: Vec::new(),
         }
     }
 }
@@ -60,6 +70,214 @@ impl From<PluginTimeEntry> for TimeEntryForTable {
 const INITIALIZE_METHOD: &str = "initialize";
 const GET_TIME_ENTRIES_METHOD: &str = "get_time_entries";
 const SHUTDOWN_METHOD: &str = "shutdown";
+const ON_TIME_ENTRY_IMPORTED_METHOD: &str = "on_time_entry_imported";
+const ON_TIME_ENTRY_UPDATED_METHOD: &str = "on_time_entry_updated";
+const ON_TIME_ENTRY_DELETED_METHOD: &str = "on_time_entry_deleted";
+const SUGGEST_DESCRIPTIONS_METHOD: &str = "suggest_descriptions";
+
+// Notifications plugins can send at any time, without an id
+const LOG_NOTIFICATION: &str = "log";
+const PROGRESS_NOTIFICATION: &str = "progress";
+const NOTIFY_NOTIFICATION: &str = "notify";
+
+/// How long the edit form waits for a plugin to suggest descriptions before skipping it
+const SUGGESTION_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long a plugin gets to handle a change to an imported entry before it is reported
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Protocol version the host speaks, sent along with the initialize request
+const PROTOCOL_VERSION: u32 = 2;
+/// Oldest protocol version the host still understands
+const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// What a plugin supports, as returned by its initialize response
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PluginCapabilities {
+    pub protocol_version: u32,
+    pub methods: Vec<String>,
+    pub writes: bool,
+    /// Reserved for plugins that can search their entries, the host doesn't ask yet
+    pub search: bool,
+    pub suggestions: bool,
+}
+
+impl PluginCapabilities {
+    /// Plugins of the first protocol version answer initialize with `true` and only
+    /// provide time entries
+    fn legacy() -> Self {
+        Self {
+            protocol_version: 1,
+            methods: vec![
+                INITIALIZE_METHOD.to_string(),
+                GET_TIME_ENTRIES_METHOD.to_string(),
+                SHUTDOWN_METHOD.to_string(),
+            ],
+            ..Self::default()
+        }
+    }
+
+    /// Check if the plugin handles an RPC method
+    pub fn supports(&self, method: &str) -> bool {
+        self.methods.iter().any(|supported| supported == method)
+    }
+}
+
+/// What happened to a MoneyBird entry that plugin entries were imported as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeEntryEvent {
+    Imported,
+    Updated,
+    Deleted,
+}
+
+impl TimeEntryEvent {
+    /// The RPC method that tells a plugin about the event
+    fn method(self) -> &'static str {
+        match self {
+            TimeEntryEvent::Imported => ON_TIME_ENTRY_IMPORTED_METHOD,
+            TimeEntryEvent::Updated => ON_TIME_ENTRY_UPDATED_METHOD,
+            TimeEntryEvent::Deleted => ON_TIME_ENTRY_DELETED_METHOD,
+        }
+    }
+}
+
+/// A message a plugin sent on its own, tagged with the name of the plugin
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PluginNotification {
+    /// A line for the log panel
+    Log {
+        plugin: String,
+        severity: LogSeverity,
+        message: String,
+    },
+    /// How far the plugin is with something that takes a while
+    Progress {
+        plugin: String,
+        message: String,
+        current: Option<u64>,
+        total: Option<u64>,
+        done: bool,
+    },
+    /// Something the user should see right away
+    Notify {
+        plugin: String,
+        severity: LogSeverity,
+        title: Option<String>,
+        message: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct LogParams {
+    #[serde(default)]
+    level: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProgressParams {
+    #[serde(default)]
+    message: String,
+    current: Option<u64>,
+    total: Option<u64>,
+    #[serde(default)]
+    done: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotifyParams {
+    #[serde(default)]
+    level: String,
+    title: Option<String>,
+    message: String,
+}
+
+/// Map the level of a plugin notification to a log severity, notice when unknown
+fn severity_from_level(level: &str) -> LogSeverity {
+    match level.to_lowercase().as_str() {
+        "debug" | "trace" => LogSeverity::Debug,
+        "success" => LogSeverity::Success,
+        "warning" | "warn" => LogSeverity::Warning,
+        "error" => LogSeverity::Error,
+        _ => LogSeverity::Notice,
+    }
+}
+
+/// Read a notification from a message without an id, None for unknown methods or params
+fn parse_notification(
+    plugin: &str,
+    message: &serde_json::Map<String, Value>,
+) -> Option<PluginNotification> {
+    let method = message.get("method")?.as_str()?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let plugin = plugin.to_string();
+    match method {
+        LOG_NOTIFICATION => {
+            let params: LogParams = serde_json::from_value(params).ok()?;
+            Some(PluginNotification::Log {
+                plugin,
+                severity: severity_from_level(&params.level),
+                message: params.message,
+            })
+        }
+        PROGRESS_NOTIFICATION => {
+            let params: ProgressParams = serde_json::from_value(params).ok()?;
+            Some(PluginNotification::Progress {
+                plugin,
+                message: params.message,
+                current: params.current,
+                total: params.total,
+                done: params.done,
+            })
+        }
+        NOTIFY_NOTIFICATION => {
+            let params: NotifyParams = serde_json::from_value(params).ok()?;
+            Some(PluginNotification::Notify {
+                plugin,
+                severity: severity_from_level(&params.level),
+                title: params.title,
+                message: params.message,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Response to the initialize request, a bare bool for protocol version 1
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum InitializeResponse {
+    Accepted(bool),
+    Capabilities(PluginCapabilities),
+}
+
+/// Get the capabilities from an initialize response, or why the plugin can't be used
+fn negotiate(name: &str, response: InitializeResponse) -> Result<PluginCapabilities, String> {
+    match response {
+        InitializeResponse::Accepted(true) => Ok(PluginCapabilities::legacy()),
+        InitializeResponse::Accepted(false) => Err(t!(
+            "plugin_init_rejected",
+            name = name,
+            error = "Plugin explicitly rejected initialization (returned false)"
+        )
+        .to_string()),
+        InitializeResponse::Capabilities(capabilities)
+            if !(MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION)
+                .contains(&capabilities.protocol_version) =>
+        {
+            Err(t!(
+                "plugin_protocol_incompatible",
+                name = name,
+                version = capabilities.protocol_version,
+                min = MIN_PROTOCOL_VERSION,
+                max = PROTOCOL_VERSION
+            )
+            .to_string())
+        }
+        InitializeResponse::Capabilities(capabilities) => Ok(capabilities),
+    }
+}
 
 /// Plugin manifest structure
 #[derive(Debug, Deserialize)]
@@ -93,6 +311,8 @@ pub struct PluginInfo {
     pub enabled: bool,
     pub initialized: bool,
     pub icon: Option<String>,
+    pub capabilities: Option<PluginCapabilities>, // Known once initialized
+    pub error: Option<String>,                    // Why the plugin couldn't be initialized
 }
 
 /// Represents the communication channel with a plugin process.
@@ -109,7 +329,11 @@ struct JsonRpcRequestWithCallback {
 }
 
 impl PluginRpcChannel {
-    async fn new(mut process: Child) -> eyre::Result<Self> {
+    async fn new(
+        mut process: Child,
+        plugin_name: String,
+        notifications: mpsc::UnboundedSender<PluginNotification>,
+    ) -> eyre::Result<Self> {
         let stdin = process
             .stdin
             .take()
@@ -161,9 +385,11 @@ impl PluginRpcChannel {
                                                 } else {
                                                      log::error!("Invalid response ID type: {:?}", id_val);
                                                 }
+                                            } else if let Some(notification) = parse_notification(&plugin_name, response) {
+                                                // Picked up by the main loop
+                                                let _ = notifications.send(notification);
                                             } else {
-                                                 // Could be a notification from the plugin, log it?
-                                                 log::debug!("Received notification or non-standard message from plugin: {}", line_buffer.trim());
+                                                 log::debug!("Received unknown notification or non-standard message from plugin: {}", line_buffer.trim());
                                             }
                                         } else {
                                             log::error!("Received non-object JSON from plugin: {}", line_buffer.trim());
@@ -248,31 +474,66 @@ impl PluginRpcChannel {
         method: &str,
         params: P,
     ) -> eyre::Result<R> {
-        let params_value =
-            serde_json::to_value(params).map_err(|e| eyre!("Failed to serialize params: {}", e))?;
+        send_request(&self.tx, method, params).await
+    }
+}
 
-        let (callback_tx, callback_rx) = oneshot::channel();
+/// Send a request to the communication task of a plugin and wait for the response
+async fn send_request<P: Serialize, R: for<'de> Deserialize<'de>>(
+    tx: &mpsc::Sender<JsonRpcRequestWithCallback>,
+    method: &str,
+    params: P,
+) -> eyre::Result<R> {
+    let params_value =
+        serde_json::to_value(params).map_err(|e| eyre!("Failed to serialize params: {}", e))?;
 
-        let req = JsonRpcRequestWithCallback {
-            method: method.to_string(),
-            params: params_value,
-            callback: callback_tx,
-        };
+    let (callback_tx, callback_rx) = oneshot::channel();
 
-        self.tx
-            .send(req)
-            .await
-            .map_err(|_| eyre!("Failed to send request to plugin communication task"))?;
+    let req = JsonRpcRequestWithCallback {
+        method: method.to_string(),
+        params: params_value,
+        callback: callback_tx,
+    };
 
-        // Wait for the response from the communication task
-        let result_value = callback_rx
-            .await
-            .map_err(|_| eyre!("Plugin response channel closed prematurely"))??; // Double ?? to propagate eyre::Result
+    tx.send(req)
+        .await
+        .map_err(|_| eyre!("Failed to send request to plugin communication task"))?;
 
-        // Deserialize the result Value into the expected type R
-        serde_json::from_value(result_value)
-            .map_err(|e| eyre!("Failed to deserialize plugin response: {}", e))
-    }
+    receive_response(callback_rx).await
+}
+
+/// Queue a request for the communication task of a plugin without waiting for it, so
+/// requests queued one after another reach the plugin in that order
+fn queue_request(
+    tx: &mpsc::Sender<JsonRpcRequestWithCallback>,
+    method: &str,
+    params: Value,
+) -> eyre::Result<oneshot::Receiver<eyre::Result<Value>>> {
+    let (callback_tx, callback_rx) = oneshot::channel();
+
+    let req = JsonRpcRequestWithCallback {
+        method: method.to_string(),
+        params,
+        callback: callback_tx,
+    };
+
+    tx.try_send(req)
+        .map_err(|_| eyre!("Failed to send request to plugin communication task"))?;
+    Ok(callback_rx)
+}
+
+/// Wait for the response to a request and deserialize it
+async fn receive_response<R: for<'de> Deserialize<'de>>(
+    callback_rx: oneshot::Receiver<eyre::Result<Value>>,
+) -> eyre::Result<R> {
+    // Wait for the response from the communication task
+    let result_value = callback_rx
+        .await
+        .map_err(|_| eyre!("Plugin response channel closed prematurely"))??; // Double ?? to propagate eyre::Result
+
+    // Deserialize the result Value into the expected type R
+    serde_json::from_value(result_value)
+        .map_err(|e| eyre!("Failed to deserialize plugin response: {}", e))
 }
 
 /// Loaded plugin instance
@@ -282,10 +543,32 @@ struct Plugin {
     directory: PathBuf, // Store the directory path where the plugin was loaded from
 }
 
+impl Plugin {
+    /// Check if the plugin is initialized and handles an RPC method
+    fn supports(&self, method: &str) -> bool {
+        self.info
+            .capabilities
+            .as_ref()
+            .is_some_and(|capabilities| capabilities.supports(method))
+    }
+
+    /// Check if the plugin is enabled and told it can suggest descriptions
+    fn suggests_descriptions(&self) -> bool {
+        let suggestions = self
+            .info
+            .capabilities
+            .as_ref()
+            .is_some_and(|capabilities| capabilities.suggestions);
+        self.info.enabled && suggestions && self.supports(SUGGEST_DESCRIPTIONS_METHOD)
+    }
+}
+
 /// Plugin system manager
 pub struct PluginManager {
     plugins: HashMap<String, Plugin>,
     plugins_dir: PathBuf,
+    notification_tx: mpsc::UnboundedSender<PluginNotification>,
+    notification_rx: mpsc::UnboundedReceiver<PluginNotification>,
 }
 
 impl PluginManager {
@@ -296,10 +579,13 @@ impl PluginManager {
             dirs::config_dir().ok_or_else(|| eyre!("Could not determine config directory"))?;
 
         let plugins_dir = config_dir.join("mot").join("plugins");
+        let (notification_tx, notification_rx) = mpsc::unbounded_channel();
 
         Ok(Self {
             plugins: HashMap::new(),
             plugins_dir,
+            notification_tx,
+            notification_rx,
         })
     }
 
@@ -479,10 +765,17 @@ impl PluginManager {
             enabled,            // Use the value read from config.toml
             initialized: false, // Start as not initialized
             icon: manifest.plugin.icon.clone(),
+            capabilities: None,
+            error: None,
         };
 
         // Create the RPC channel
-        let channel = PluginRpcChannel::new(process).await?;
+        let channel = PluginRpcChannel::new(
+            process,
+            manifest.plugin.name.clone(),
+            self.notification_tx.clone(),
+        )
+        .await?;
 
         // Create plugin instance
         let plugin = Plugin {
@@ -543,22 +836,31 @@ impl PluginManager {
                 match fs::read_to_string(&config_path) {
                     Ok(_) => {
                         // Config file is readable, proceed with initialization
-                        let params = serde_json::json!({ "config_path": config_path });
+                        let params = serde_json::json!({
+                            "config_path": config_path,
+                            "protocol_version": PROTOCOL_VERSION
+                        });
                         match plugin
                             .channel
-                            .request::<Value, bool>(INITIALIZE_METHOD, params)
+                            .request::<Value, InitializeResponse>(INITIALIZE_METHOD, params)
                             .await
                         {
-                            Ok(true) => {
-                                plugin.info.initialized = true;
-                                results.push((plugin_name.clone(), Ok(())));
-                            }
-                            Ok(false) => {
-                                // Plugin returned false - indicates initialization failure in the plugin itself
-                                results.push((plugin_name.clone(),
-                                            Err(t!("plugin_init_rejected", name = plugin_name, 
-                                                error = "Plugin explicitly rejected initialization (returned false)").to_string())));
-                            }
+                            Ok(response) => match negotiate(&plugin_name, response) {
+                                Ok(capabilities) => {
+                                    plugin.info.initialized = true;
+                                    plugin.info.capabilities = Some(capabilities);
+                                    results.push((plugin_name.clone(), Ok(())));
+                                }
+                                Err(err) => {
+                                    // Plugin rejected initialization or speaks another protocol
+                                    log::error!(
+                                        "Plugin '{}' initialization failed: {}",
+                                        plugin_name,
+                                        err
+                                    );
+                                    results.push((plugin_name.clone(), Err(err)));
+                                }
+                            },
                             Err(err) => {
                                 // Provide detailed error information from the RPC error
                                 let error_msg = format!("RPC error: {}", err);
@@ -622,101 +924,224 @@ impl PluginManager {
             }
         }
 
+        // Keep the reason with the plugin, so the plugin view can show it
+        for (plugin_name, result) in &results {
+            if let Some(plugin) = self.plugins.get_mut(plugin_name) {
+                plugin.info.error = result.as_ref().err().cloned();
+            }
+        }
+
         Ok(results)
     }
 
-    /// Get time entries from a specific plugin
-    pub async fn get_time_entries(
-        &mut self,
-        plugin_name: &str,
+    /// Ask every plugin that provides time entries for its entries in a date range.
+    /// The returned future doesn't borrow the manager, so it can run in a task while the
+    /// interface keeps showing the progress plugins report.
+    pub(crate) fn fetch_time_entries(
+        &self,
         start_date: &DateTime<Utc>,
         end_date: &DateTime<Utc>,
-    ) -> eyre::Result<Vec<PluginTimeEntry>> {
-        let plugin = self
+    ) -> impl Future<Output = FetchedTimeEntries> + Send + 'static {
+        // Disabled and uninitialized plugins, or plugins without entries, are skipped
+        let plugins: Vec<(String, mpsc::Sender<JsonRpcRequestWithCallback>)> = self
             .plugins
-            .get_mut(plugin_name)
-            .ok_or_else(|| eyre!("Plugin not found: {}", plugin_name))?;
+            .iter()
+            .filter(|(_, plugin)| {
+                plugin.info.enabled
+                    && plugin.info.initialized
+                    && plugin.supports(GET_TIME_ENTRIES_METHOD)
+            })
+            .map(|(name, plugin)| (name.clone(), plugin.channel.tx.clone()))
+            .collect();
+        let params = serde_json::json!({
+            "start_date": start_date.to_rfc3339(),
+            "end_date": end_date.to_rfc3339()
+        });
 
-        // Check if plugin is enabled and initialized before proceeding
-        if !plugin.info.enabled {
-            return Err(eyre!("Plugin '{}' is disabled", plugin_name));
+        async move {
+            let mut all_entries = Vec::new();
+            let mut errors = Vec::new();
+            for (plugin_name, tx) in plugins {
+                match send_request::<Value, Vec<PluginTimeEntry>>(
+                    &tx,
+                    GET_TIME_ENTRIES_METHOD,
+                    params.clone(),
+                )
+                .await
+                {
+                    Ok(entries) => {
+                        // Ensure each entry has the correct plugin_name set
+                        all_entries.extend(entries.into_iter().map(|mut entry| {
+                            entry.plugin_name = Some(plugin_name.clone());
+                            entry
+                        }));
+                    }
+                    Err(err) => errors.push((
+                        plugin_name.clone(),
+                        t!(
+                            "plugin_get_entries_error",
+                            name = plugin_name,
+                            error = err.to_string()
+                        )
+                        .to_string(),
+                    )),
+                }
+            }
+            (all_entries, errors)
         }
-        if !plugin.info.initialized {
-            return Err(eyre!("Plugin '{}' is not initialized", plugin_name));
+    }
+
+    /// Tell a plugin what happened to the MoneyBird entry one of its entries was imported as.
+    /// Plugins that don't accept writes or don't handle the event are skipped, which
+    /// returns false. The answer isn't waited for, a plugin that fails to handle the change
+    /// or doesn't answer in time is reported as a log notification.
+    pub fn notify_time_entry(
+        &self,
+        plugin_name: &str,
+        event: TimeEntryEvent,
+        moneybird_id: &str,
+        entry_id: &str,
+    ) -> eyre::Result<bool> {
+        let Some(plugin) = self.plugins.get(plugin_name) else {
+            return Ok(false);
+        };
+        let writes = plugin
+            .info
+            .capabilities
+            .as_ref()
+            .is_some_and(|capabilities| capabilities.writes);
+        if !plugin.info.enabled || !writes || !plugin.supports(event.method()) {
+            return Ok(false);
         }
 
         let params = serde_json::json!({
-            "start_date": start_date.to_rfc3339(),
-            "end_date": end_date.to_rfc3339()
+            "moneybird_id": moneybird_id,
+            "entry_id": entry_id
         });
+        let response = queue_request(&plugin.channel.tx, event.method(), params)
+            .map_err(|err| eyre!("{} failed: {}", event.method(), err))?;
 
-        match plugin
-            .channel
-            .request::<Value, Vec<PluginTimeEntry>>(GET_TIME_ENTRIES_METHOD, params)
-            .await
-        {
-            Ok(mut entries) => {
-                // Ensure each entry has the correct plugin_name set
-                for entry in &mut entries {
-                    entry.plugin_name = Some(plugin_name.to_string());
+        let notifications = self.notification_tx.clone();
+        let plugin_name = plugin_name.to_string();
+        tokio::spawn(async move {
+            let error = match tokio::time::timeout(NOTIFY_TIMEOUT, response).await {
+                Ok(Ok(Ok(_))) => return,
+                Ok(Ok(Err(err))) => format!("{} failed: {}", event.method(), err),
+                Ok(Err(_)) => "Plugin response channel closed prematurely".to_string(),
+                Err(_) => {
+                    t!("plugin_notify_timeout", seconds = NOTIFY_TIMEOUT.as_secs()).to_string()
                 }
-                Ok(entries)
-            }
-            Err(err) => Err(eyre!(
-                "Failed to get time entries from {}: {}",
-                plugin_name,
-                err
-            )),
+            };
+            let message = t!("plugin_notify_error", name = plugin_name, error = error);
+            let _ = notifications.send(PluginNotification::Log {
+                plugin: plugin_name,
+                severity: LogSeverity::Warning,
+                message: message.to_string(),
+            });
+        });
+        Ok(true)
+    }
+
+    /// Check if any enabled plugin provides time entries, which are fetched on refresh
+    pub fn provides_time_entries(&self) -> bool {
+        self.plugins
+            .values()
+            .any(|plugin| plugin.info.enabled && plugin.supports(GET_TIME_ENTRIES_METHOD))
+    }
+
+    /// Take the notifications plugins sent since the last call
+    pub(crate) fn take_notifications(&mut self) -> Vec<PluginNotification> {
+        let mut notifications = Vec::new();
+        while let Ok(notification) = self.notification_rx.try_recv() {
+            notifications.push(notification);
         }
+        notifications
     }
 
-    /// Get time entries from all plugins for a date range
-    pub async fn get_all_time_entries(
-        &mut self,
-        start_date: &DateTime<Utc>,
-        end_date: &DateTime<Utc>,
-    ) -> eyre::Result<(Vec<PluginTimeEntry>, Vec<(String, String)>)> {
-        let mut all_entries = Vec::new();
+    /// Check if any enabled plugin can suggest descriptions
+    pub fn has_suggestions(&self) -> bool {
+        self.plugins.values().any(Plugin::suggests_descriptions)
+    }
+
+    /// Ask the plugins that can for descriptions matching the typed text, e.g. issue
+    /// titles or branch names. Returns the suggestions without duplicates, and the errors
+    /// of plugins that failed.
+    pub async fn suggest_descriptions(
+        &self,
+        text: &str,
+        date: &str,
+        project_id: Option<&str>,
+        project_name: &str,
+    ) -> (Vec<String>, Vec<(String, String)>) {
+        let mut suggestions: Vec<String> = Vec::new();
         let mut errors = Vec::new();
-        let plugin_names: Vec<String> = self.plugins.keys().cloned().collect();
+        let params = serde_json::json!({
+            "text": text,
+            "date": date,
+            "project_id": project_id,
+            "project_name": project_name
+        });
 
-        for plugin_name in plugin_names {
-            match self
-                .get_time_entries(&plugin_name, start_date, end_date)
+        // Ask all plugins at once, so a slow plugin only costs the time of one timeout
+        let mut plugin_names: Vec<&String> = self
+            .plugins
+            .iter()
+            .filter(|(_, plugin)| plugin.suggests_descriptions())
+            .map(|(plugin_name, _)| plugin_name)
+            .collect();
+        plugin_names.sort();
+        let requests: Vec<_> = plugin_names
+            .into_iter()
+            .map(|plugin_name| {
+                let tx = self.plugins[plugin_name].channel.tx.clone();
+                let params = params.clone();
+                let request = tokio::spawn(async move {
+                    let request = send_request::<Value, Vec<String>>(
+                        &tx,
+                        SUGGEST_DESCRIPTIONS_METHOD,
+                        params,
+                    );
+                    tokio::time::timeout(SUGGESTION_TIMEOUT, request).await
+                });
+                (plugin_name, request)
+            })
+            .collect();
+
+        // Merged in plugin order, so the same answers always give the same list
+        for (plugin_name, request) in requests {
+            let response = request
                 .await
-            {
-                Ok(entries) => {
-                    all_entries.extend(entries);
-                }
-                Err(err) => {
-                    // Check if the error is specifically about being disabled or uninitialized
-                    let err_string = err.to_string();
-                    if err_string.contains("is disabled")
-                        || err_string.contains("is not initialized")
-                    {
-                        // Log as info, don't add to user-facing errors
-                        log::info!(
-                            "Skipping entries for plugin '{}': {}",
-                            plugin_name,
-                            err_string
-                        );
-                    } else {
-                        // Actual error getting entries, report it
-                        errors.push((
-                            plugin_name.clone(),
-                            t!(
-                                "plugin_get_entries_error",
-                                name = plugin_name,
-                                error = err_string
-                            )
-                            .to_string(),
-                        ));
+                .unwrap_or_else(|err| Ok(Err(eyre!("Suggestion request failed: {}", err))));
+            match response {
+                Ok(Ok(plugin_suggestions)) => {
+                    for suggestion in plugin_suggestions {
+                        if !suggestions.contains(&suggestion) {
+                            suggestions.push(suggestion);
+                        }
                     }
                 }
+                Ok(Err(err)) => errors.push((
+                    plugin_name.clone(),
+                    t!(
+                        "plugin_suggest_error",
+                        name = plugin_name,
+                        error = err.to_string()
+                    )
+                    .to_string(),
+                )),
+                Err(_) => errors.push((
+                    plugin_name.clone(),
+                    t!(
+                        "plugin_suggest_timeout",
+                        name = plugin_name,
+                        milliseconds = SUGGESTION_TIMEOUT.as_millis()
+                    )
+                    .to_string(),
+                )),
             }
         }
 
-        Ok((all_entries, errors))
+        (suggestions, errors)
     }
 
     /// Shutdown all plugins gracefully.
@@ -727,6 +1152,17 @@ impl PluginManager {
 
         for name in plugin_names {
             if let Some(plugin) = self.plugins.remove(&name) {
+                // Plugins that told they don't handle shutdown are just terminated
+                let unsupported = plugin
+                    .info
+                    .capabilities
+                    .as_ref()
+                    .is_some_and(|capabilities| !capabilities.supports(SHUTDOWN_METHOD));
+                if unsupported {
+                    log::debug!("Plugin {} doesn't handle shutdown, terminating.", name);
+                    continue;
+                }
+
                 // Attempt graceful shutdown via RPC
                 match plugin
                     .channel
@@ -1015,17 +1451,35 @@ impl PluginManager {
             if let Some(plugin) = self.plugins.get_mut(plugin_name) {
                 let config_path = plugin_dir.join("config.toml").to_string_lossy().to_string();
 
-                let params = serde_json::json!({ "config_path": config_path });
+                let params = serde_json::json!({
+                    "config_path": config_path,
+                    "protocol_version": PROTOCOL_VERSION
+                });
                 match plugin
                     .channel
-                    .request::<Value, bool>(INITIALIZE_METHOD, params)
+                    .request::<Value, InitializeResponse>(INITIALIZE_METHOD, params)
                     .await
                 {
-                    Ok(true) => {
-                        plugin.info.initialized = true;
-                        "✅ Plugin initialized successfully".to_string()
-                    }
-                    Ok(false) => "❌ Plugin returned false during initialization".to_string(),
+                    Ok(response) => match negotiate(plugin_name, response) {
+                        Ok(capabilities) => {
+                            let report = format!(
+                                "✅ Plugin initialized successfully:\n- Protocol version: {}\n- Methods: {}\n- Writes: {}\n- Search: {}\n- Suggestions: {}",
+                                capabilities.protocol_version,
+                                capabilities.methods.join(", "),
+                                capabilities.writes,
+                                capabilities.search,
+                                capabilities.suggestions
+                            );
+                            plugin.info.initialized = true;
+                            plugin.info.capabilities = Some(capabilities);
+                            plugin.info.error = None;
+                            report
+                        }
+                        Err(err) => {
+                            plugin.info.error = Some(err.clone());
+                            format!("❌ {}", err)
+                        }
+                    },
                     Err(err) => {
                         format!("❌ Plugin initialization error: {}", err)
                     }
@@ -1068,3 +1522,281 @@ impl PluginManager {
         Ok(report)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A plugin process that answers every request with a null result
+    #[cfg(unix)]
+    async fn answering_plugin(capabilities: PluginCapabilities, enabled: bool) -> Plugin {
+        let script = r#"while read -r line; do
+            id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+            echo "{\"jsonrpc\":\"2.0\",\"result\":null,\"id\":$id}"
+        done"#;
+        script_plugin(script, capabilities, enabled).await
+    }
+
+    async fn failing_plugin(capabilities: PluginCapabilities) -> Plugin {
+        let script = r#"while read -r line; do
+            id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+            echo "{\"jsonrpc\":\"2.0\",\"error\":{\"code\":1,\"message\":\"nope\"},\"id\":$id}"
+        done"#;
+        script_plugin(script, capabilities, true).await
+    }
+
+    async fn suggesting_plugin(suggestions: &str, capabilities: PluginCapabilities) -> Plugin {
+        let script = format!(
+            r#"while read -r line; do
+            id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+            echo "{{\"jsonrpc\":\"2.0\",\"result\":{},\"id\":$id}}"
+        done"#,
+            suggestions.replace('"', "\\\"")
+        );
+        script_plugin(&script, capabilities, true).await
+    }
+
+    async fn silent_plugin(capabilities: PluginCapabilities) -> Plugin {
+        script_plugin("cat > /dev/null", capabilities, true).await
+    }
+
+    async fn script_plugin(
+        script: &str,
+        capabilities: PluginCapabilities,
+        enabled: bool,
+    ) -> Plugin {
+        let process = Command::new("sh")
+            .arg("-c")
+            .arg(script)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let (notifications, _) = mpsc::unbounded_channel();
+        Plugin {
+            info: PluginInfo {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                description: None,
+                enabled,
+                initialized: true,
+                icon: None,
+                capabilities: Some(capabilities),
+                error: None,
+            },
+            channel: PluginRpcChannel::new(process, "test".to_string(), notifications)
+                .await
+                .unwrap(),
+            directory: PathBuf::new(),
+        }
+    }
+
+    fn manager_with(plugins: Vec<(&str, Plugin)>) -> PluginManager {
+        let (notification_tx, notification_rx) = mpsc::unbounded_channel();
+        PluginManager {
+            plugins: plugins
+                .into_iter()
+                .map(|(name, plugin)| (name.to_string(), plugin))
+                .collect(),
+            plugins_dir: PathBuf::new(),
+            notification_tx,
+            notification_rx,
+        }
+    }
+
+    fn negotiate_json(response: Value) -> Result<PluginCapabilities, String> {
+        negotiate("test", serde_json::from_value(response).unwrap())
+    }
+
+    #[test]
+    fn test_negotiate_legacy() {
+        let capabilities = negotiate_json(json!(true)).unwrap();
+        assert_eq!(capabilities, PluginCapabilities::legacy());
+        assert_eq!(capabilities.protocol_version, 1);
+        assert!(capabilities.supports(GET_TIME_ENTRIES_METHOD));
+        assert!(!capabilities.supports(ON_TIME_ENTRY_IMPORTED_METHOD));
+        assert!(!capabilities.writes);
+
+        assert!(negotiate_json(json!(false)).is_err());
+    }
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        assert!(negotiate_json(json!({ "protocol_version": 0 })).is_err());
+        assert!(negotiate_json(json!({ "protocol_version": PROTOCOL_VERSION + 1 })).is_err());
+        // Without a version the plugin doesn't speak a protocol the host knows
+        assert!(negotiate_json(json!({ "methods": ["initialize"] })).is_err());
+        assert!(negotiate_json(json!({ "protocol_version": MIN_PROTOCOL_VERSION })).is_ok());
+    }
+
+    #[test]
+    fn test_negotiate_capabilities() {
+        let capabilities = negotiate_json(json!({
+            "protocol_version": 2,
+            "methods": ["initialize", "get_time_entries", "suggest_descriptions"],
+            "writes": true,
+            "suggestions": true
+        }))
+        .unwrap();
+
+        assert_eq!(capabilities.protocol_version, 2);
+        assert!(capabilities.writes);
+        assert!(capabilities.suggestions);
+        assert!(!capabilities.search);
+        assert!(capabilities.supports(SUGGEST_DESCRIPTIONS_METHOD));
+        assert!(!capabilities.supports(SHUTDOWN_METHOD));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_notify_time_entry() {
+        let writes = PluginCapabilities {
+            protocol_version: 2,
+            methods: vec![
+                ON_TIME_ENTRY_IMPORTED_METHOD.to_string(),
+                ON_TIME_ENTRY_DELETED_METHOD.to_string(),
+            ],
+            writes: true,
+            ..PluginCapabilities::default()
+        };
+        let read_only = PluginCapabilities {
+            writes: false,
+            ..writes.clone()
+        };
+        let mut manager = manager_with(vec![
+            ("writes", answering_plugin(writes.clone(), true).await),
+            ("read_only", answering_plugin(read_only, true).await),
+            ("disabled", answering_plugin(writes.clone(), false).await),
+            ("failing", failing_plugin(writes).await),
+        ]);
+        let notify = |manager: &PluginManager, plugin: &str, event: TimeEntryEvent| {
+            manager
+                .notify_time_entry(plugin, event, "123", "abc")
+                .unwrap()
+        };
+
+        assert!(notify(&manager, "writes", TimeEntryEvent::Imported));
+        assert!(notify(&manager, "writes", TimeEntryEvent::Deleted));
+        // The plugin doesn't handle updates
+        assert!(!notify(&manager, "writes", TimeEntryEvent::Updated));
+        assert!(!notify(&manager, "read_only", TimeEntryEvent::Imported));
+        assert!(!notify(&manager, "disabled", TimeEntryEvent::Imported));
+        assert!(!notify(&manager, "missing", TimeEntryEvent::Imported));
+
+        // The answer isn't waited for, a failure shows up as a warning later on
+        assert!(notify(&manager, "failing", TimeEntryEvent::Imported));
+        let notification = manager.notification_rx.recv().await.unwrap();
+        let PluginNotification::Log {
+            plugin, severity, ..
+        } = notification
+        else {
+            panic!("expected a log notification");
+        };
+        assert_eq!(plugin, "failing");
+        assert_eq!(severity, LogSeverity::Warning);
+        assert!(manager.take_notifications().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_suggest_descriptions() {
+        let capabilities = PluginCapabilities {
+            protocol_version: 2,
+            methods: vec![SUGGEST_DESCRIPTIONS_METHOD.to_string()],
+            suggestions: true,
+            ..PluginCapabilities::default()
+        };
+        let manager = manager_with(vec![
+            ("a_slow", silent_plugin(capabilities.clone()).await),
+            (
+                "b_issues",
+                suggesting_plugin(r#"["Fix login","Deploy"]"#, capabilities.clone()).await,
+            ),
+            (
+                "c_branches",
+                suggesting_plugin(r#"["Deploy","Review"]"#, capabilities.clone()).await,
+            ),
+            ("d_slow", silent_plugin(capabilities).await),
+        ]);
+
+        let started = std::time::Instant::now();
+        let (suggestions, errors) = manager
+            .suggest_descriptions("de", "2025-04-04", None, "")
+            .await;
+        // Both slow plugins are waited for at the same time
+        assert!(started.elapsed() < SUGGESTION_TIMEOUT * 2);
+        assert_eq!(suggestions, vec!["Fix login", "Deploy", "Review"]);
+        let failed: Vec<&str> = errors.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(failed, vec!["a_slow", "d_slow"]);
+    }
+
+    #[test]
+    fn test_severity_from_level() {
+        assert_eq!(severity_from_level("debug"), LogSeverity::Debug);
+        assert_eq!(severity_from_level("trace"), LogSeverity::Debug);
+        assert_eq!(severity_from_level("info"), LogSeverity::Notice);
+        assert_eq!(severity_from_level("success"), LogSeverity::Success);
+        assert_eq!(severity_from_level("WARN"), LogSeverity::Warning);
+        assert_eq!(severity_from_level("warning"), LogSeverity::Warning);
+        assert_eq!(severity_from_level("error"), LogSeverity::Error);
+        assert_eq!(severity_from_level(""), LogSeverity::Notice);
+        assert_eq!(severity_from_level("fatal"), LogSeverity::Notice);
+    }
+
+    #[test]
+    fn test_parse_notification() {
+        let parse = |message: Value| parse_notification("test", message.as_object().unwrap());
+
+        assert_eq!(
+            parse(json!({ "method": "log", "params": { "level": "warn", "message": "Slow" } })),
+            Some(PluginNotification::Log {
+                plugin: "test".to_string(),
+                severity: LogSeverity::Warning,
+                message: "Slow".to_string(),
+            })
+        );
+        assert_eq!(
+            parse(
+                json!({ "method": "progress", "params": { "message": "Issues", "current": 2, "total": 5 } })
+            ),
+            Some(PluginNotification::Progress {
+                plugin: "test".to_string(),
+                message: "Issues".to_string(),
+                current: Some(2),
+                total: Some(5),
+                done: false,
+            })
+        );
+        assert_eq!(
+            parse(json!({ "method": "progress", "params": { "done": true } })),
+            Some(PluginNotification::Progress {
+                plugin: "test".to_string(),
+                message: String::new(),
+                current: None,
+                total: None,
+                done: true,
+            })
+        );
+        assert_eq!(
+            parse(json!({ "method": "notify", "params": { "message": "Token expires soon" } })),
+            Some(PluginNotification::Notify {
+                plugin: "test".to_string(),
+                severity: LogSeverity::Notice,
+                title: None,
+                message: "Token expires soon".to_string(),
+            })
+        );
+
+        // Unknown methods and params without the required message are ignored
+        assert_eq!(parse(json!({ "method": "unknown", "params": {} })), None);
+        assert_eq!(
+            parse(json!({ "method": "log", "params": { "level": "info" } })),
+            None
+        );
+        assert_eq!(parse(json!({ "method": "notify" })), None);
+        assert_eq!(parse(json!({ "params": { "message": "No method" } })), None);
+    }
+}
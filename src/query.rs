@@ -0,0 +1,850 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use rust_i18n::t;
+
+use crate::{datetime, fuzzy, TimeEntryForTable};
+
+/// Error returned when a search query can't be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct QueryError {
+    pub(crate) message: String,
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+/// Kind of a query token, used for highlighting the search input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Text,
+    Field,
+    Value,
+    Operator,
+    Error,
+}
+
+/// Highlighted part of the query input, `start` and `end` are char indices
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TokenSpan {
+    pub(crate) kind: TokenKind,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word {
+        text: String,
+        quoted: bool, // Quoted words are always plain text
+        start: usize,
+        end: usize,
+    },
+    Negate {
+        start: usize,
+    },
+    And {
+        start: usize,
+        end: usize,
+    },
+    Or {
+        start: usize,
+        end: usize,
+    },
+    LeftParen {
+        start: usize,
+    },
+    RightParen {
+        start: usize,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    fn parse(op: &str) -> Option<Self> {
+        match op {
+            ":" | "=" => Some(Comparison::Equal),
+            ">" => Some(Comparison::Greater),
+            ">=" => Some(Comparison::GreaterOrEqual),
+            "<" => Some(Comparison::Less),
+            "<=" => Some(Comparison::LessOrEqual),
+            _ => None,
+        }
+    }
+
+    fn compare(&self, left: u64, right: u64) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DateBound {
+    Weekday(u32), // Days from Monday
+    Date(NaiveDate),
+    DaysAgo(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TextField {
+    Client,
+    Project,
+    Description,
+    Source,
+    User,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    Text(String),
+    Field(TextField, String),
+    Billable(bool),
+    Duration(Comparison, u64),
+    Date(DateBound, DateBound),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Term(Term),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+/// Char positions in the text fields of a time entry that matched the query
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MatchPositions {
+    pub(crate) customer: Vec<usize>,
+    pub(crate) project: Vec<usize>,
+    pub(crate) description: Vec<usize>,
+}
+
+// Weights of the text fields when scoring a match
+const WEIGHT_CLIENT: i64 = 4;
+const WEIGHT_PROJECT: i64 = 2;
+const WEIGHT_DESCRIPTION: i64 = 1;
+
+/// Values a query needs besides the time entry itself
+pub(crate) struct QueryContext {
+    pub(crate) timezone: String,
+    pub(crate) today: NaiveDate,
+}
+
+impl QueryContext {
+    /// Context for the current day in the given timezone
+    pub(crate) fn new(timezone: &str) -> Self {
+        let tz = timezone.parse::<chrono_tz::Tz>().unwrap_or(chrono_tz::UTC);
+        Self {
+            timezone: timezone.to_string(),
+            today: chrono::Utc::now().with_timezone(&tz).date_naive(),
+        }
+    }
+}
+
+/// A parsed search query
+///
+/// Terms are combined with `AND` by default, `OR` binds weaker than `AND` and
+/// parentheses can be used for grouping. A `-` in front of a term or group negates it.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Query {
+    expr: Expr,
+}
+
+impl Query {
+    /// Parse a query string
+    pub(crate) fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(input);
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+
+        let expr = if tokens.is_empty() {
+            Expr::And(Vec::new())
+        } else {
+            parser.parse_or()?
+        };
+
+        if let Some(token) = parser.peek() {
+            return Err(match token {
+                Token::RightParen { .. } => QueryError::new(t!("query_unmatched_parenthesis")),
+                _ => QueryError::new(t!("query_expected_term")),
+            });
+        }
+
+        Ok(Self { expr })
+    }
+
+    /// Check if a query has no terms and thus matches everything
+    pub(crate) fn is_empty(&self) -> bool {
+        matches!(&self.expr, Expr::And(terms) if terms.is_empty())
+    }
+
+    /// Check if a time entry matches the query
+    pub(crate) fn matches(&self, entry: &TimeEntryForTable, context: &QueryContext) -> bool {
+        matches_expr(&self.expr, entry, context)
+    }
+
+    /// Score how well a time entry matches the text terms of the query
+    ///
+    /// Client matches weigh 4, project matches 2 and description matches 1.
+    pub(crate) fn score(&self, entry: &TimeEntryForTable) -> i64 {
+        let score_field = |pattern: &str, text: &str, weight: i64| {
+            fuzzy::fuzzy_match(pattern, text).map_or(0, |m| m.score * weight)
+        };
+
+        positive_terms(&self.expr)
+            .into_iter()
+            .map(|term| match term {
+                Term::Text(text) => {
+                    score_field(text, &entry.customer, WEIGHT_CLIENT)
+                        + score_field(text, &entry.project, WEIGHT_PROJECT)
+                        + score_field(text, &entry.description, WEIGHT_DESCRIPTION)
+                }
+                Term::Field(TextField::Client, value) => {
+                    score_field(value, &entry.customer, WEIGHT_CLIENT)
+                }
+                Term::Field(TextField::Project, value) => {
+                    score_field(value, &entry.project, WEIGHT_PROJECT)
+                }
+                Term::Field(TextField::Description, value) => {
+                    score_field(value, &entry.description, WEIGHT_DESCRIPTION)
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Get the positions of the characters matched by the text terms of the query
+    pub(crate) fn match_positions(&self, entry: &TimeEntryForTable) -> MatchPositions {
+        let mut positions = MatchPositions::default();
+        let add = |target: &mut Vec<usize>, pattern: &str, text: &str| {
+            if let Some(m) = fuzzy::fuzzy_match(pattern, text) {
+                target.extend(m.positions);
+            }
+        };
+
+        for term in positive_terms(&self.expr) {
+            match term {
+                Term::Text(text) => {
+                    add(&mut positions.customer, text, &entry.customer);
+                    add(&mut positions.project, text, &entry.project);
+                    add(&mut positions.description, text, &entry.description);
+                }
+                Term::Field(TextField::Client, value) => {
+                    add(&mut positions.customer, value, &entry.customer)
+                }
+                Term::Field(TextField::Project, value) => {
+                    add(&mut positions.project, value, &entry.project)
+                }
+                Term::Field(TextField::Description, value) => {
+                    add(&mut positions.description, value, &entry.description)
+                }
+                _ => {}
+            }
+        }
+
+        for field in [
+            &mut positions.customer,
+            &mut positions.project,
+            &mut positions.description,
+        ] {
+            field.sort_unstable();
+            field.dedup();
+        }
+        positions
+    }
+}
+
+/// Collect the terms that aren't negated, only those explain why an entry matched
+fn positive_terms(expr: &Expr) -> Vec<&Term> {
+    match expr {
+        Expr::Term(term) => vec![term],
+        Expr::Not(_) => Vec::new(),
+        Expr::And(terms) | Expr::Or(terms) => terms.iter().flat_map(positive_terms).collect(),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, QueryError> {
+        let mut terms = vec![self.parse_and()?];
+        while let Some(Token::Or { .. }) = self.peek() {
+            self.position += 1;
+            terms.push(self.parse_and()?);
+        }
+
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Expr::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, QueryError> {
+        let mut terms = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                None | Some(Token::Or { .. }) | Some(Token::RightParen { .. }) => break,
+                Some(Token::And { .. }) => {
+                    self.position += 1;
+                    terms.push(self.parse_unary()?);
+                }
+                Some(_) => terms.push(self.parse_unary()?),
+            }
+        }
+
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Expr::And(terms)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, QueryError> {
+        match self.advance().cloned() {
+            Some(Token::Negate { .. }) => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LeftParen { .. }) => {
+                let expr = self.parse_or()?;
+                match self.advance() {
+                    Some(Token::RightParen { .. }) => Ok(expr),
+                    _ => Err(QueryError::new(t!("query_unmatched_parenthesis"))),
+                }
+            }
+            Some(Token::Word { text, quoted, .. }) => {
+                if quoted {
+                    Ok(Expr::Term(Term::Text(text.to_lowercase())))
+                } else {
+                    Ok(Expr::Term(parse_term(&text)?))
+                }
+            }
+            Some(Token::RightParen { .. }) => {
+                Err(QueryError::new(t!("query_unmatched_parenthesis")))
+            }
+            _ => Err(QueryError::new(t!("query_expected_term"))),
+        }
+    }
+}
+
+/// Split the input into tokens, keeping quoted parts together
+fn tokenize(input: &str) -> Vec<Token> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token::LeftParen { start: i });
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RightParen { start: i });
+                i += 1;
+            }
+            '-' if chars
+                .get(i + 1)
+                .is_some_and(|next| !next.is_whitespace() && *next != ')') =>
+            {
+                tokens.push(Token::Negate { start: i });
+                i += 1;
+            }
+            _ => {
+                let start = i;
+                let mut text = String::new();
+                let mut in_quotes = false;
+                while i < chars.len() {
+                    let c = chars[i];
+                    if c == '"' {
+                        in_quotes = !in_quotes;
+                    } else if !in_quotes && (c.is_whitespace() || c == '(' || c == ')') {
+                        break;
+                    } else {
+                        text.push(c);
+                    }
+                    i += 1;
+                }
+
+                // Only bare OR/AND are operators, a quoted "OR" is text
+                let raw: String = chars[start..i].iter().collect();
+                tokens.push(match raw.as_str() {
+                    "OR" => Token::Or { start, end: i },
+                    "AND" => Token::And { start, end: i },
+                    _ => Token::Word {
+                        text,
+                        quoted: c == '"',
+                        start,
+                        end: i,
+                    },
+                });
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Field names the query language knows, anything else before an operator is plain text
+const FIELDS: &[&str] = &[
+    "client",
+    "customer",
+    "contact",
+    "project",
+    "description",
+    "desc",
+    "source",
+    "user",
+    "billable",
+    "duration",
+    "date",
+];
+
+/// Split a word in a field name, comparison operator and value
+///
+/// Words like `re:meeting` or `TODO:` don't start with a known field and stay text.
+fn split_field(word: &str) -> Option<(&str, &str, &str)> {
+    let op_start = word.find([':', '=', '>', '<'])?;
+    let (field, rest) = word.split_at(op_start);
+    if !FIELDS.contains(&field.to_lowercase().as_str()) {
+        return None;
+    }
+
+    let op_len = if rest.starts_with(">=") || rest.starts_with("<=") {
+        2
+    } else {
+        1
+    };
+    let (op, value) = rest.split_at(op_len);
+    Some((field, op, value))
+}
+
+fn parse_term(word: &str) -> Result<Term, QueryError> {
+    let Some((field, op, value)) = split_field(word) else {
+        return Ok(Term::Text(word.to_lowercase()));
+    };
+
+    let field_lower = field.to_lowercase();
+    let comparison = Comparison::parse(op).ok_or_else(|| {
+        QueryError::new(t!("query_invalid_operator", operator = op, field = field))
+    })?;
+
+    if value.is_empty() {
+        return Err(QueryError::new(t!("query_missing_value", field = field)));
+    }
+
+    let text_field = match field_lower.as_str() {
+        "client" | "customer" | "contact" => Some(TextField::Client),
+        "project" => Some(TextField::Project),
+        "description" | "desc" => Some(TextField::Description),
+        "source" => Some(TextField::Source),
+        "user" => Some(TextField::User),
+        _ => None,
+    };
+
+    if let Some(text_field) = text_field {
+        if comparison != Comparison::Equal {
+            return Err(QueryError::new(t!(
+                "query_invalid_operator",
+                operator = op,
+                field = field
+            )));
+        }
+        return Ok(Term::Field(text_field, value.to_lowercase()));
+    }
+
+    match field_lower.as_str() {
+        "billable" => {
+            if comparison != Comparison::Equal {
+                return Err(QueryError::new(t!(
+                    "query_invalid_operator",
+                    operator = op,
+                    field = field
+                )));
+            }
+            match value.to_lowercase().as_str() {
+                "yes" | "true" | "y" | "1" => Ok(Term::Billable(true)),
+                "no" | "false" | "n" | "0" => Ok(Term::Billable(false)),
+                _ => Err(QueryError::new(t!("query_invalid_billable", value = value))),
+            }
+        }
+        "duration" => datetime::parse_duration_minutes(value)
+            .map(|minutes| Term::Duration(comparison, minutes))
+            .ok_or_else(|| QueryError::new(t!("query_invalid_duration", value = value))),
+        "date" => {
+            if comparison != Comparison::Equal {
+                return Err(QueryError::new(t!(
+                    "query_invalid_operator",
+                    operator = op,
+                    field = field
+                )));
+            }
+            parse_date_range(value)
+                .map(|(from, to)| Term::Date(from, to))
+                .ok_or_else(|| QueryError::new(t!("query_invalid_date", value = value)))
+        }
+        _ => Err(QueryError::new(t!("query_unknown_field", field = field))),
+    }
+}
+
+fn parse_date_bound(value: &str) -> Option<DateBound> {
+    let value = value.to_lowercase();
+    match value.as_str() {
+        "today" => return Some(DateBound::DaysAgo(0)),
+        "yesterday" => return Some(DateBound::DaysAgo(1)),
+        _ => {}
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return Some(DateBound::Date(date));
+    }
+
+    let weekday = match value.as_str() {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(DateBound::Weekday(weekday.num_days_from_monday()))
+}
+
+/// Parse `mon`, `mon..wed`, `2025-04-01..2025-04-03` or `yesterday..today`
+fn parse_date_range(value: &str) -> Option<(DateBound, DateBound)> {
+    let (from, to) = match value.split_once("..") {
+        Some((from, to)) => (parse_date_bound(from)?, parse_date_bound(to)?),
+        None => {
+            let bound = parse_date_bound(value)?;
+            (bound, bound)
+        }
+    };
+
+    // Weekdays can't be mixed with calendar dates in a range
+    match (from, to) {
+        (DateBound::Weekday(_), DateBound::Weekday(_)) => Some((from, to)),
+        (DateBound::Weekday(_), _) | (_, DateBound::Weekday(_)) => None,
+        _ => Some((from, to)),
+    }
+}
+
+fn resolve_date(bound: DateBound, today: NaiveDate) -> Option<NaiveDate> {
+    match bound {
+        DateBound::Date(date) => Some(date),
+        DateBound::DaysAgo(days) => today.checked_sub_signed(chrono::Duration::days(days)),
+        DateBound::Weekday(_) => None,
+    }
+}
+
+fn matches_expr(expr: &Expr, entry: &TimeEntryForTable, context: &QueryContext) -> bool {
+    match expr {
+        Expr::Term(term) => matches_term(term, entry, context),
+        Expr::Not(inner) => !matches_expr(inner, entry, context),
+        Expr::And(terms) => terms.iter().all(|t| matches_expr(t, entry, context)),
+        Expr::Or(terms) => terms.iter().any(|t| matches_expr(t, entry, context)),
+    }
+}
+
+fn matches_term(term: &Term, entry: &TimeEntryForTable, context: &QueryContext) -> bool {
+    let contains = |field: &str, value: &str| field.to_lowercase().contains(value);
+    let fuzzy = |field: &str, value: &str| fuzzy::fuzzy_match(value, field).is_some();
+
+    match term {
+        Term::Text(text) => {
+            fuzzy(&entry.customer, text)
+                || fuzzy(&entry.project, text)
+                || fuzzy(&entry.description, text)
+        }
+        Term::Field(TextField::Client, value) => fuzzy(&entry.customer, value),
+        Term::Field(TextField::Project, value) => fuzzy(&entry.project, value),
+        Term::Field(TextField::Description, value) => fuzzy(&entry.description, value),
+        Term::Field(TextField::Source, value) => {
+            contains(&entry.source, value)
+                || entry
+                    .plugin_name
+                    .as_ref()
+                    .is_some_and(|name| contains(name, value))
+        }
+        Term::Field(TextField::User, value) => contains(&entry.user, value),
+        Term::Billable(billable) => entry.billable == *billable,
+        Term::Duration(comparison, minutes) => {
+            let (hours, mins) = datetime::calculate_duration(&entry.started_at, &entry.ended_at);
+            comparison.compare(hours * 60 + mins, *minutes)
+        }
+        Term::Date(from, to) => {
+            let Some(started_at) =
+                datetime::parse_iso_datetime(&entry.started_at, &context.timezone)
+            else {
+                return false;
+            };
+            let date = started_at.date_naive();
+
+            match (from, to) {
+                (DateBound::Weekday(from), DateBound::Weekday(to)) => {
+                    let day = date.weekday().num_days_from_monday();
+                    if from <= to {
+                        (*from..=*to).contains(&day)
+                    } else {
+                        // Ranges like `sun..tue` wrap around the end of the week
+                        day >= *from || day <= *to
+                    }
+                }
+                _ => match (
+                    resolve_date(*from, context.today),
+                    resolve_date(*to, context.today),
+                ) {
+                    (Some(from), Some(to)) => date >= from.min(to) && date <= from.max(to),
+                    _ => false,
+                },
+            }
+        }
+    }
+}
+
+/// Split the query input in highlighted parts
+///
+/// Unlike [`Query::parse`] this never fails, terms that can't be parsed are
+/// marked as [`TokenKind::Error`] so the search input can show them while typing.
+pub(crate) fn highlight(input: &str) -> Vec<TokenSpan> {
+    let mut spans = Vec::new();
+    for token in tokenize(input) {
+        match token {
+            Token::Negate { start } | Token::LeftParen { start } | Token::RightParen { start } => {
+                spans.push(TokenSpan {
+                    kind: TokenKind::Operator,
+                    start,
+                    end: start + 1,
+                })
+            }
+            Token::And { start, end } | Token::Or { start, end } => spans.push(TokenSpan {
+                kind: TokenKind::Operator,
+                start,
+                end,
+            }),
+            Token::Word {
+                text,
+                quoted,
+                start,
+                end,
+            } => {
+                if quoted {
+                    spans.push(TokenSpan {
+                        kind: TokenKind::Text,
+                        start,
+                        end,
+                    });
+                } else if parse_term(&text).is_err() {
+                    spans.push(TokenSpan {
+                        kind: TokenKind::Error,
+                        start,
+                        end,
+                    });
+                } else if let Some((field, op, _)) = split_field(&text) {
+                    let value_start = start + field.chars().count() + op.chars().count();
+                    spans.push(TokenSpan {
+                        kind: TokenKind::Field,
+                        start,
+                        end: value_start,
+                    });
+                    spans.push(TokenSpan {
+                        kind: TokenKind::Value,
+                        start: value_start,
+                        end,
+                    });
+                } else {
+                    spans.push(TokenSpan {
+                        kind: TokenKind::Text,
+                        start,
+                        end,
+                    });
+                }
+            }
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(customer: &str, project: &str, description: &str) -> TimeEntryForTable {
+        TimeEntryForTable {
+            id: "1".to_string(),
+            customer: customer.to_string(),
+            project: project.to_string(),
+            description: description.to_string(),
+            // Wednesday 9 April 2025, 1h30m
+            started_at: "2025-04-09T08:00:00Z".to_string(),
+            ended_at: "2025-04-09T09:30:00Z".to_string(),
+            billable: true,
+            source: "moneybird".to_string(),
+            icon: None,
+            plugin_name: None,
+            user: "Jane".to_string(),
+            user_id: Some("42".to_string()),
+            contact_id: Some("7".to_string()),
+            tags: Vec::new(),
+            linked_id: None,
+            merged_ids: Vec::new(),
+        }
+    }
+
+    fn context() -> QueryContext {
+        QueryContext {
+            timezone: "Europe/Amsterdam".to_string(),
+            today: NaiveDate::from_ymd_opt(2025, 4, 10).unwrap(),
+        }
+    }
+
+    fn matches(query: &str, entry: &TimeEntryForTable) -> bool {
+        Query::parse(query).unwrap().matches(entry, &context())
+    }
+
+    #[test]
+    fn test_text_and_fields() {
+        let e = entry("Acme", "Web shop", "Weekly meeting");
+
+        assert!(matches("", &e));
+        assert!(matches("acme", &e));
+        assert!(matches("client:acme project:\"web shop\"", &e));
+        assert!(!matches("client:acme -meeting", &e));
+        assert!(matches("source:moneybird user:jane", &e));
+        assert!(matches("\"weekly meeting\"", &e));
+        assert!(!matches("\"client:acme\"", &e));
+        assert!(!matches("project:intranet", &e));
+    }
+
+    #[test]
+    fn test_unknown_field_is_text() {
+        let e = entry("Acme", "Web shop", "re:meeting about TODO: a=b");
+
+        assert!(matches("re:meeting", &e));
+        assert!(matches("TODO:", &e));
+        assert!(matches("a=b", &e));
+        assert!(!matches("foo:bar", &e));
+        assert_eq!(
+            highlight("re:meeting")
+                .iter()
+                .map(|s| s.kind)
+                .collect::<Vec<_>>(),
+            vec![TokenKind::Text]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_score_and_positions() {
+        let e = entry("Acme", "Web shop", "Weekly meeting");
+        assert!(matches("acmee", &e));
+
+        let client = Query::parse("acme").unwrap().score(&e);
+        let description = Query::parse("weekly").unwrap().score(&e);
+        assert!(client > description);
+
+        let positions = Query::parse("shop -acme").unwrap().match_positions(&e);
+        assert_eq!(positions.project, vec![4, 5, 6, 7]);
+        assert!(positions.customer.is_empty());
+    }
+
+    #[test]
+    fn test_and_or_grouping() {
+        let e = entry("Acme", "Web shop", "Weekly meeting");
+
+        assert!(matches("intranet OR shop", &e));
+        assert!(matches("acme AND (intranet OR shop)", &e));
+        assert!(!matches("acme AND -(intranet OR shop)", &e));
+        assert!(matches("other OR acme meeting", &e));
+        assert!(!matches("(other OR acme) nothing", &e));
+    }
+
+    #[test]
+    fn test_billable_duration_date() {
+        let e = entry("Acme", "Web shop", "Weekly meeting");
+
+        assert!(matches("billable:yes", &e));
+        assert!(!matches("billable:no", &e));
+        assert!(matches("duration>1h", &e));
+        assert!(matches("duration>=1h30", &e));
+        assert!(!matches("duration<1h", &e));
+        assert!(matches("date:mon..wed", &e));
+        assert!(!matches("date:thu..fri", &e));
+        assert!(matches("date:sun..wed", &e));
+        assert!(matches("date:2025-04-09", &e));
+        assert!(matches("date:yesterday", &e));
+        assert!(!matches("date:today", &e));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Query::parse("client:").is_err());
+        assert!(Query::parse("billable:maybe").is_err());
+        assert!(Query::parse("duration>soon").is_err());
+        assert!(Query::parse("client>acme").is_err());
+        assert!(Query::parse("date:mon..2025-04-01").is_err());
+        assert!(Query::parse("(acme").is_err());
+        assert!(Query::parse("acme)").is_err());
+        assert!(Query::parse("acme OR").is_err());
+    }
+
+    #[test]
+    fn test_highlight() {
+        let spans = highlight("client:acme -x OR billable:maybe");
+        let kinds: Vec<TokenKind> = spans.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Field,
+                TokenKind::Value,
+                TokenKind::Operator,
+                TokenKind::Text,
+                TokenKind::Operator,
+                TokenKind::Error,
+            ]
+        );
+        assert_eq!((spans[0].start, spans[0].end), (0, 7));
+        assert_eq!((spans[1].start, spans[1].end), (7, 11));
+    }
+}
@@ -1,7 +1,7 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     widgets::{
         Block, Borders, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget,
     },
@@ -156,8 +156,10 @@ where
     pub input_style: Style,
     pub dropdown_style: Style,
     pub selected_style: Style,
+    pub match_style: Style,
     pub transform_fn: F,
     pub placeholder: Option<&'a str>,
+    pub dropdown_only: bool,
 }
 
 impl<'a, T, F> Autocomplete<'a, T, F>
@@ -172,8 +174,12 @@ where
             input_style: Style::default(),
             dropdown_style: Style::default(),
             selected_style: Style::default().add_modifier(Modifier::REVERSED),
+            match_style: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
             transform_fn,
             placeholder: None,
+            dropdown_only: false,
         }
     }
 
@@ -197,10 +203,21 @@ where
         self
     }
 
+    pub fn match_style(mut self, style: Style) -> Self {
+        self.match_style = style;
+        self
+    }
+
     pub fn placeholder(mut self, placeholder: &'a str) -> Self {
         self.placeholder = Some(placeholder);
         self
     }
+
+    /// Render only the dropdown, for an input that is rendered by another widget
+    pub fn dropdown_only(mut self) -> Self {
+        self.dropdown_only = true;
+        self
+    }
 }
 
 impl<T, F> Widget for Autocomplete<'_, T, F>
@@ -218,32 +235,38 @@ where
             None => area,
         };
 
-        // Split the area for input and potential dropdown
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(1), Constraint::Min(0)])
-            .split(area);
-
-        // Render input value
-        let display_text = if self.state.input.is_empty() && self.placeholder.is_some() {
-            Paragraph::new(self.placeholder.unwrap()).style(self.input_style)
+        // The dropdown takes the whole area when another widget renders the input
+        let dropdown_space = if self.dropdown_only {
+            area
         } else {
-            Paragraph::new(self.state.input.clone()).style(self.input_style)
-        };
+            // Split the area for input and potential dropdown
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+
+            // Render input value
+            let display_text = if self.state.input.is_empty() && self.placeholder.is_some() {
+                Paragraph::new(self.placeholder.unwrap()).style(self.input_style)
+            } else {
+                Paragraph::new(self.state.input.clone()).style(self.input_style)
+            };
 
-        display_text.render(chunks[0], buf);
+            display_text.render(chunks[0], buf);
+            chunks[1]
+        };
 
         // Render dropdown if visible
         if self.state.is_dropdown_visible {
             // Calculate max available space
-            let max_height = (chunks[1].height).min(10);
+            let max_height = (dropdown_space.height).min(10);
 
             if max_height == 0 {
                 return; // No space to show dropdown
             }
 
             // Calculate position for dropdown - always beneath input field
-            let dropdown_area = Rect::new(area.x, chunks[0].y + 1, area.width, max_height);
+            let dropdown_area = Rect::new(area.x, dropdown_space.y, area.width, max_height);
 
             // Create Clear widget to ensure dropdown overlays properly
             Clear.render(dropdown_area, buf);
@@ -278,8 +301,16 @@ where
                     .skip(self.state.scroll_offset)
                     .take(max_height as usize)
                     .map(|item| {
+                        // Highlight the characters that match the typed input
                         let display = (self.transform_fn)(item);
-                        ListItem::new(display)
+                        let positions = crate::fuzzy::fuzzy_match(&self.state.input, &display)
+                            .map(|m| m.positions)
+                            .unwrap_or_default();
+                        ListItem::new(crate::ui::highlight_matches(
+                            &display,
+                            &positions,
+                            self.match_style,
+                        ))
                     })
                     .collect();
 
@@ -7,10 +7,49 @@ pub(crate) mod shortcuts;
 pub use autocomplete::Autocomplete;
 pub use color::*;
 pub use modal::*;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
 pub use render::*;
 use rust_i18n::t;
 pub use shortcuts::*;
 
+/// Build a line where the characters at `positions` (char indices) get `match_style`
+pub(crate) fn highlight_matches(
+    text: &str,
+    positions: &[usize],
+    match_style: Style,
+) -> Line<'static> {
+    if positions.is_empty() {
+        return Line::from(text.to_string());
+    }
+
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_is_match = false;
+    for (idx, c) in text.chars().enumerate() {
+        let is_match = positions.binary_search(&idx).is_ok();
+        if is_match != current_is_match && !current.is_empty() {
+            let part = std::mem::take(&mut current);
+            spans.push(if current_is_match {
+                Span::styled(part, match_style)
+            } else {
+                Span::raw(part)
+            });
+        }
+        current_is_match = is_match;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        spans.push(if current_is_match {
+            Span::styled(current, match_style)
+        } else {
+            Span::raw(current)
+        });
+    }
+
+    Line::from(spans)
+}
+
 /// Format a contact name for display, prioritizing company name and falling back to person name if needed
 pub(crate) fn format_contact_name(contact: &crate::moneybird::types::Contact) -> String {
     // First try company name
@@ -66,6 +105,34 @@ pub(crate) fn get_contact_name(
     }
 }
 
+/// Get the display label of a sort column
+pub(crate) fn get_sort_column_label(column: crate::config::SortColumn) -> String {
+    use crate::config::SortColumn;
+    match column {
+        SortColumn::Date => t!("ui_sort_date"),
+        SortColumn::Client => t!("ui_sort_client"),
+        SortColumn::Project => t!("ui_sort_project"),
+        SortColumn::Duration => t!("ui_sort_duration"),
+        SortColumn::Source => t!("ui_sort_source"),
+    }
+    .to_string()
+}
+
+/// Get a user name from the list of users by user ID, falling back to the ID itself if not found
+pub(crate) fn get_user_name(
+    users: &[crate::moneybird::types::User],
+    user_id: Option<&String>,
+) -> String {
+    match user_id {
+        Some(id) => users
+            .iter()
+            .find(|u| u.id.as_ref() == Some(id))
+            .and_then(|u| u.name.clone())
+            .unwrap_or_else(|| id.clone()),
+        None => String::new(),
+    }
+}
+
 /// Generate a default icon based on a name, ensuring consistency across the application
 pub fn get_default_icon(name: &str) -> String {
     // Available default icons (colored circles)
@@ -6,6 +6,7 @@ use rust_i18n::t;
 use std::borrow::Cow;
 
 use super::{Shortcut, Shortcuts};
+use crate::keybindings::{Action, KeyMode};
 
 /// Modal type for different visual styles
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +39,7 @@ pub struct ModalData {
     pub id: Option<String>,         // Optional identifier for action handling
     pub on_confirm: Option<crate::event::Message>, // Message to send when confirmed
     pub on_cancel: Option<crate::event::Message>, // Message to send when canceled
+    pub on_alternative: Option<crate::event::Message>, // Message to send for the alternative choice
 }
 
 impl Default for ModalData {
@@ -50,6 +52,7 @@ impl Default for ModalData {
             id: None,
             on_confirm: None,
             on_cancel: None,
+            on_alternative: None,
         }
     }
 }
@@ -100,6 +103,8 @@ pub fn show_confirmation(
     on_confirm: Option<crate::event::Message>,
     on_cancel: Option<crate::event::Message>,
 ) {
+    let cancel = model.keybindings.label(KeyMode::Modal, Action::Cancel);
+    let confirm = model.keybindings.label(KeyMode::Modal, Action::Confirm);
     show_modal(
         model,
         ModalData {
@@ -108,14 +113,50 @@ pub fn show_confirmation(
             modal_type: ModalType::Confirm,
             buttons: Some(
                 Shortcuts::new(vec![
-                    Shortcut::Pair("Esc", t!("modal_cancel").as_ref()),
-                    Shortcut::Pair("Enter", t!("modal_ok").as_ref()),
+                    Shortcut::Pair(&cancel, t!("modal_cancel").as_ref()),
+                    Shortcut::Pair(&confirm, t!("modal_ok").as_ref()),
                 ])
                 .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD)),
             ),
             id: Some("confirmation".to_string()),
             on_confirm,
             on_cancel,
+            ..Default::default()
+        },
+    );
+}
+
+/// Helper function to show a confirmation modal with a second way to go ahead
+pub fn show_choice(
+    model: &mut crate::AppModel,
+    title: impl Into<Cow<'static, str>>,
+    message: impl Into<Cow<'static, str>>,
+    (confirm_label, on_confirm): (impl Into<Cow<'static, str>>, crate::event::Message),
+    (alternative_label, on_alternative): (impl Into<Cow<'static, str>>, crate::event::Message),
+) {
+    let cancel = model.keybindings.label(KeyMode::Modal, Action::Cancel);
+    let confirm = model.keybindings.label(KeyMode::Modal, Action::Confirm);
+    let alternative = model.keybindings.label(KeyMode::Modal, Action::Alternative);
+    let confirm_label = confirm_label.into();
+    let alternative_label = alternative_label.into();
+    show_modal(
+        model,
+        ModalData {
+            title: title.into().to_string(),
+            message: message.into().to_string(),
+            modal_type: ModalType::Confirm,
+            buttons: Some(
+                Shortcuts::new(vec![
+                    Shortcut::Pair(&cancel, t!("modal_cancel").as_ref()),
+                    Shortcut::Pair(&alternative, alternative_label.as_ref()),
+                    Shortcut::Pair(&confirm, confirm_label.as_ref()),
+                ])
+                .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD)),
+            ),
+            id: Some("choice".to_string()),
+            on_confirm: Some(on_confirm),
+            on_alternative: Some(on_alternative),
+            ..Default::default()
         },
     );
 }
@@ -127,14 +168,36 @@ pub fn show_info(
     title: impl Into<Cow<'static, str>>,
     message: impl Into<Cow<'static, str>>,
 ) {
+    show_notification(model, id, ModalType::Info, title, message);
+}
+
+/// Helper function to show a warning modal
+pub fn show_warning(
+    model: &mut crate::AppModel,
+    id: &str,
+    title: impl Into<Cow<'static, str>>,
+    message: impl Into<Cow<'static, str>>,
+) {
+    show_notification(model, id, ModalType::Warning, title, message);
+}
+
+/// Helper function to show a modal that only has to be dismissed
+pub fn show_notification(
+    model: &mut crate::AppModel,
+    id: &str,
+    modal_type: ModalType,
+    title: impl Into<Cow<'static, str>>,
+    message: impl Into<Cow<'static, str>>,
+) {
+    let cancel = model.keybindings.label(KeyMode::Modal, Action::Cancel);
     show_modal(
         model,
         ModalData {
             title: title.into().to_string(),
             message: message.into().to_string(),
-            modal_type: ModalType::Info,
+            modal_type,
             buttons: Some(
-                Shortcuts::new(vec![Shortcut::Pair("Esc", t!("modal_dismiss").as_ref())])
+                Shortcuts::new(vec![Shortcut::Pair(&cancel, t!("modal_dismiss").as_ref())])
                     .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD)),
             ),
             id: Some(id.to_string()),
@@ -173,11 +236,10 @@ pub fn render_modal(model: &crate::AppModel, frame: &mut Frame) {
         };
 
         // Prepare the bottom instructions text
-        let dismiss_shortcut = Shortcuts::new(vec![Shortcut::Pair(
-            "Enter",
-            t!("modal_press_enter_to_dismiss").as_ref(),
-        )])
-        .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
+        let confirm = model.keybindings.label(KeyMode::Modal, Action::Confirm);
+        let dismiss_shortcut =
+            Shortcuts::new(vec![Shortcut::Pair(&confirm, t!("modal_dismiss").as_ref())])
+                .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
         let instructions = match &modal_data.buttons {
             Some(buttons) => buttons.as_line(),
             _ => dismiss_shortcut.as_line(),
@@ -0,0 +1,255 @@
+use crate::{
+    datetime, fuzzy,
+    keybindings::{Action, KeyMode},
+    model::{AppModel, BatchImportItem, BatchImportTarget},
+    ui::{self, Shortcut, Shortcuts},
+};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, Row, Table},
+};
+use rust_i18n::t;
+
+use super::time_entry_table::get_time_entry_icon;
+
+const CANDIDATES_HEIGHT: u16 = 10;
+
+/// Render the batch import review, with the contact or project picker below it while
+/// one is being changed
+pub fn render_batch_import(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let (table_area, picker_area) = if model.batch_import_state.editing.is_some() {
+        let [table_area, picker_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(CANDIDATES_HEIGHT)])
+                .areas(area);
+        (table_area, Some(picker_area))
+    } else {
+        (area, None)
+    };
+
+    render_batch_import_table(model, table_area, frame);
+    if let Some(picker_area) = picker_area {
+        render_batch_import_picker(model, picker_area, frame);
+    }
+}
+
+fn render_batch_import_table(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let key = |action| model.keybindings.label(KeyMode::BatchImport, action);
+    let check = model
+        .keybindings
+        .label_pair(KeyMode::BatchImport, Action::Check, Action::CheckAll);
+    let (contact, project, merge, confirm, close) = (
+        key(Action::EditContact),
+        key(Action::EditProject),
+        key(Action::MergeFragments),
+        key(Action::Confirm),
+        key(Action::Close),
+    );
+    let shortcuts = Shortcuts::new(vec![
+        Shortcut::Pair(&check, t!("ui_shortcut_check").as_ref()),
+        Shortcut::Pair(&contact, t!("ui_shortcut_contact").as_ref()),
+        Shortcut::Pair(&project, t!("ui_shortcut_project").as_ref()),
+        Shortcut::Pair(&merge, t!("ui_shortcut_merge").as_ref()),
+        Shortcut::Pair(&confirm, t!("ui_shortcut_import").as_ref()),
+        Shortcut::Pair(&close, t!("ui_shortcut_back").as_ref()),
+    ])
+    .with_alignment(Alignment::Right)
+    .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
+
+    let admin_timezone_str = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    let state = &model.batch_import_state;
+    let checked = state.items.iter().filter(|item| item.checked).count();
+
+    let header = Row::new(vec![
+        String::new(),
+        String::new(),
+        t!("ui_table_header_date").to_string(),
+        t!("ui_table_header_time").to_string(),
+        t!("ui_table_header_duration").to_string(),
+        t!("ui_batch_import_header_plugin").to_string(),
+        t!("ui_batch_import_header_moneybird").to_string(),
+        t!("ui_table_header_description").to_string(),
+        t!("ui_batch_import_header_result").to_string(),
+    ])
+    .style(Style::default().bold());
+
+    let rows: Vec<Row> = state
+        .items
+        .iter()
+        .map(|item| batch_import_row(item, &admin_timezone_str))
+        .collect();
+    let widths = [
+        Constraint::Length(3),
+        Constraint::Length(2),
+        Constraint::Length(10),
+        Constraint::Length(13),
+        Constraint::Length(8),
+        Constraint::Fill(2),
+        Constraint::Fill(2),
+        Constraint::Fill(3),
+        Constraint::Fill(2),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::ITALIC | Modifier::BOLD),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(format!(
+                    " {} ",
+                    t!(
+                        "ui_batch_import_title",
+                        checked = checked,
+                        total = state.items.len()
+                    )
+                ))
+                .title_alignment(Alignment::Center)
+                .title_bottom(shortcuts.as_line())
+                .style(model.appearance.default_style),
+        );
+
+    frame.render_stateful_widget(table, area, &mut model.batch_import_state.table_state);
+}
+
+/// A row with the plugin names next to the contact and project they become
+fn batch_import_row<'a>(item: &BatchImportItem, admin_timezone_str: &str) -> Row<'a> {
+    let entry = &item.entry;
+    let check = if item.checked { "[x]" } else { "[ ]" };
+    let (hours, minutes) = datetime::calculate_duration(&entry.started_at, &entry.ended_at);
+
+    let missing = || Span::styled("?", Style::default().fg(Color::Red));
+    let contact = item
+        .contact
+        .as_ref()
+        .and_then(|contact| contact.company_name.clone())
+        .map_or_else(missing, Span::raw);
+    let project = item
+        .project
+        .as_ref()
+        .and_then(|project| project.name.clone())
+        .map_or_else(missing, Span::raw);
+
+    let result = match &item.result {
+        Some(Ok(_)) => Line::from(Span::styled(
+            format!("✓ {}", t!("ui_batch_import_imported")),
+            Style::default().fg(Color::Green),
+        )),
+        Some(Err(err)) => Line::from(Span::styled(
+            format!("✗ {}", err),
+            Style::default().fg(Color::Red),
+        )),
+        None => Line::default(),
+    };
+
+    let cells = vec![
+        Line::from(check),
+        Line::from(get_time_entry_icon(entry)),
+        Line::from(datetime::format_date_from_time_entry(
+            entry.clone(),
+            admin_timezone_str,
+        )),
+        Line::from(datetime::format_time_range_from_time_entry(
+            entry.clone(),
+            admin_timezone_str,
+        )),
+        Line::from(datetime::format_duration(hours, minutes, Style::default())),
+        Line::from(format!("{} / {}", entry.customer, entry.project)),
+        Line::from(vec![contact, Span::raw(" / "), project]),
+        Line::from(entry.description.replace('\n', " ")),
+        result,
+    ];
+
+    // Unchecked entries are left out of the import
+    if item.checked {
+        Row::new(cells)
+    } else {
+        Row::new(cells).style(Style::default().add_modifier(Modifier::DIM))
+    }
+}
+
+/// Render the input and the matching contacts or projects
+fn render_batch_import_picker(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let state = &mut model.batch_import_state;
+    let (title, input, names, list_state) = match state.editing {
+        Some(BatchImportTarget::Project) => (
+            t!("ui_batch_import_pick_project"),
+            state.project_autocomplete.input.clone(),
+            state
+                .project_autocomplete
+                .items
+                .iter()
+                .map(|project| project.name.clone().unwrap_or_default())
+                .collect::<Vec<_>>(),
+            &mut state.project_autocomplete.list_state,
+        ),
+        _ => (
+            t!("ui_batch_import_pick_contact"),
+            state.contact_autocomplete.input.clone(),
+            state
+                .contact_autocomplete
+                .items
+                .iter()
+                .map(ui::format_contact_name)
+                .collect::<Vec<_>>(),
+            &mut state.contact_autocomplete.list_state,
+        ),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" {} ", title))
+        .title_alignment(Alignment::Center)
+        .style(model.appearance.default_style);
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [input_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner_area);
+    let [prompt_area, text_area] =
+        Layout::horizontal([Constraint::Length(2), Constraint::Min(0)]).areas(input_area);
+    frame.render_widget(
+        Paragraph::new(">").style(Style::default().bold()),
+        prompt_area,
+    );
+    frame.render_widget(Paragraph::new(input.clone()), text_area);
+    let cursor_x = text_area.x + (input.chars().count() as u16).min(text_area.width);
+    frame.set_cursor_position((cursor_x, text_area.y));
+
+    if names.is_empty() {
+        frame.render_widget(
+            Paragraph::new(t!("ui_no_matching_items").to_string()).style(Style::default().italic()),
+            list_area,
+        );
+        return;
+    }
+
+    let match_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let items: Vec<ListItem> = names
+        .iter()
+        .map(|name| {
+            let positions = fuzzy::fuzzy_match(&input, name)
+                .map(|found| found.positions)
+                .unwrap_or_default();
+            ListItem::new(ui::highlight_matches(name, &positions, match_style))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::ITALIC | Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, list_area, list_state);
+}
@@ -0,0 +1,181 @@
+use crate::{
+    datetime,
+    event::Message,
+    keybindings::{Action, KeyMode},
+    model::AppModel,
+    palette::{self, Command},
+    ui::{self, Shortcut, Shortcuts},
+};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph},
+};
+use rust_i18n::t;
+
+const PALETTE_WIDTH: u16 = 80;
+const PALETTE_MAX_ITEMS: u16 = 12;
+
+/// Render the command palette on top of the time entry view
+pub fn render_command_palette(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let key = |action| model.keybindings.label(KeyMode::Palette, action);
+    let (confirm, complete, close) = (
+        key(Action::Confirm),
+        key(Action::Complete),
+        key(Action::Close),
+    );
+    let shortcuts = Shortcuts::new(vec![
+        Shortcut::Pair(&confirm, t!("ui_shortcut_run").as_ref()),
+        Shortcut::Pair(&complete, t!("ui_shortcut_complete").as_ref()),
+        Shortcut::Pair(&close, t!("ui_shortcut_back").as_ref()),
+    ])
+    .with_alignment(Alignment::Right)
+    .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
+
+    let matches = model.palette_matches();
+    let mode = model.command_palette_state.mode;
+
+    // Near the top of the screen, so the selected time entry stays visible
+    let width = PALETTE_WIDTH.min(area.width.saturating_sub(4));
+    let list_height = (matches.len() as u16).clamp(1, PALETTE_MAX_ITEMS);
+    let height = (list_height + 4).min(area.height.saturating_sub(2));
+    let palette_area = Rect::new(
+        area.x + (area.width.saturating_sub(width)) / 2,
+        area.y + 1,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, palette_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" {} ", t!("ui_command_palette")))
+        .title_alignment(Alignment::Center)
+        .title_bottom(shortcuts.as_line())
+        .style(model.appearance.default_style);
+    let inner_area = block.inner(palette_area);
+    frame.render_widget(block, palette_area);
+
+    let [input_area, message_area, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(inner_area);
+
+    let [prompt_area, text_area] =
+        Layout::horizontal([Constraint::Length(2), Constraint::Min(0)]).areas(input_area);
+    frame.render_widget(
+        Paragraph::new(":").style(Style::default().bold()),
+        prompt_area,
+    );
+    frame.render_widget(&model.command_palette_state.input, text_area);
+
+    // The error of the last command, the week a go to ends up in, or a hint when
+    // nothing matches
+    let message = match &model.command_palette_state.error {
+        Some(err) => Some(Span::styled(err.clone(), Style::default().fg(Color::Red))),
+        None if matches.is_empty() => Some(Span::styled(
+            t!("ui_no_matching_items").to_string(),
+            Style::default().italic(),
+        )),
+        None => go_to_preview(model).map(|preview| Span::styled(preview, Style::default().green())),
+    };
+    if let Some(message) = message {
+        frame.render_widget(Paragraph::new(Line::from(message)), message_area);
+    }
+
+    let name_width = matches
+        .iter()
+        .map(|found| command_label(found.command).chars().count())
+        .max()
+        .unwrap_or(0);
+    let match_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|found| {
+            let label = command_label(found.command);
+            let padding = " ".repeat(name_width - label.chars().count());
+            let keys = match found.command {
+                Command::Action(action) => model.keybindings.label(mode, action),
+                _ => String::new(),
+            };
+
+            let mut line =
+                ui::highlight_matches(found.command.name(), &found.positions, match_style);
+            if let Some(argument) = found.command.argument() {
+                line.spans.push(Span::styled(
+                    format!(" {}", argument),
+                    Style::default().dim(),
+                ));
+            }
+            line.spans.push(Span::raw(padding));
+            line.spans.push(Span::styled(
+                format!("  {:<8} ", keys),
+                Style::default().green().bold(),
+            ));
+            line.spans.push(Span::raw(found.command.description()));
+            ListItem::new(line).style(model.appearance.default_style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::ITALIC | Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, list_area, &mut model.command_palette_state.list_state);
+}
+
+/// Describe the week a typed `week` or `goto` command navigates to
+fn go_to_preview(model: &AppModel) -> Option<String> {
+    let input = model.palette_input();
+    let (name, argument) = palette::split_input(&input);
+    let command = [Command::Week, Command::GoTo]
+        .into_iter()
+        .find(|command| command.name() == name)?;
+    if argument.is_empty() {
+        return None;
+    }
+
+    let admin_timezone_str = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    let message = palette::command_message(
+        command,
+        argument,
+        model.command_palette_state.mode,
+        &admin_timezone_str,
+        &model.config.week_starts_on,
+    );
+    match message {
+        Ok(Message::TimeEntryGoToWeek(week_offset)) => Some(
+            t!(
+                "palette_go_to_preview",
+                week = datetime::get_week_description(
+                    week_offset,
+                    &admin_timezone_str,
+                    &model.config.week_starts_on
+                )
+            )
+            .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+/// Name of a command with its argument placeholder, used to align the descriptions
+fn command_label(command: Command) -> String {
+    match command.argument() {
+        Some(argument) => format!("{} {}", command.name(), argument),
+        None => command.name().to_string(),
+    }
+}
@@ -1,13 +1,19 @@
+pub(crate) mod batch_import;
+pub(crate) mod command_palette;
 pub(crate) mod log;
 pub(crate) mod plugins;
+pub(crate) mod saved_searches;
 pub(crate) mod time_entry_detail;
 pub(crate) mod time_entry_edit;
 pub(crate) mod time_entry_search;
 pub(crate) mod time_entry_table;
 pub(crate) mod user_selection;
 
+pub use batch_import::*;
+pub use command_palette::*;
 pub use log::*;
 pub use plugins::*;
+pub use saved_searches::*;
 pub use time_entry_detail::*;
 pub use time_entry_edit::*;
 pub use time_entry_search::*;
@@ -8,6 +8,7 @@ use ratatui::{
 use rust_i18n::t;
 
 use crate::{
+    keybindings::{Action, KeyMode},
     model::AppModel,
     plugin::PluginInfo,
     ui::{self, Shortcut, Shortcuts},
@@ -57,11 +58,14 @@ fn create_detail_line<'a>(
 
 /// Render the plugins view
 pub(crate) fn render_plugins(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let key = |action| model.keybindings.label(KeyMode::Plugins, action);
+    let (close, quit) = (key(Action::Close), key(Action::Quit));
+    let (toggle, debug) = (key(Action::ToggleActivation), key(Action::Debug));
     let shortcuts = Shortcuts::new(vec![
-        Shortcut::Pair("Esc", t!("ui_shortcut_back").as_ref()),
-        Shortcut::Pair("q", t!("ui_shortcut_quit").as_ref()),
-        Shortcut::Pair("Space", t!("ui_shortcut_plugin_toggle").as_ref()),
-        Shortcut::Pair("Ctrl+D", t!("ui_shortcut_plugin_debug").as_ref()),
+        Shortcut::Pair(&close, t!("ui_shortcut_back").as_ref()),
+        Shortcut::Pair(&quit, t!("ui_shortcut_quit").as_ref()),
+        Shortcut::Pair(&toggle, t!("ui_shortcut_plugin_toggle").as_ref()),
+        Shortcut::Pair(&debug, t!("ui_shortcut_plugin_debug").as_ref()),
     ])
     .with_alignment(Alignment::Right)
     .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
@@ -188,6 +192,20 @@ fn render_plugin_details(
         let version_label = t!("ui_plugins_version");
         let status_label = t!("ui_plugins_status");
         let description_label = t!("ui_plugins_description");
+        let error_label = t!("ui_plugins_error");
+        let protocol_label = t!("ui_plugins_protocol");
+        let methods_label = t!("ui_plugins_methods");
+        let features_label = t!("ui_plugins_features");
+        let (protocol_text, methods_text) = plugin
+            .capabilities
+            .as_ref()
+            .map(|capabilities| {
+                (
+                    capabilities.protocol_version.to_string(),
+                    capabilities.methods.join(", "),
+                )
+            })
+            .unwrap_or_default();
 
         // Status text
         let status_text = if plugin.initialized {
@@ -232,6 +250,51 @@ fn render_plugin_details(
             status_style,
         ));
 
+        // Why initialization failed, e.g. an incompatible protocol version
+        if let Some(error) = &plugin.error {
+            detail_lines.push(create_detail_line(
+                &error_label,
+                error,
+                bold_style,
+                Style::default().fg(Color::Red),
+            ));
+        }
+
+        // What the plugin told it supports while initializing
+        if let Some(capabilities) = &plugin.capabilities {
+            detail_lines.push(create_detail_line(
+                &protocol_label,
+                &protocol_text,
+                bold_style,
+                Style::default(),
+            ));
+            detail_lines.push(create_detail_line(
+                &methods_label,
+                &methods_text,
+                bold_style,
+                Style::default(),
+            ));
+
+            let features: Vec<String> = [
+                (capabilities.writes, t!("ui_plugins_writes")),
+                (capabilities.search, t!("ui_plugins_search")),
+                (capabilities.suggestions, t!("ui_plugins_suggestions")),
+            ]
+            .into_iter()
+            .filter(|(supported, _)| *supported)
+            .map(|(_, feature)| feature.to_string())
+            .collect();
+            let features_text = if features.is_empty() {
+                t!("ui_plugins_none").to_string()
+            } else {
+                features.join(", ")
+            };
+            detail_lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", features_label), bold_style),
+                Span::raw(features_text),
+            ]));
+        }
+
         // Description header and content
         detail_lines.push(create_detail_line(
             &description_label,
@@ -0,0 +1,84 @@
+use crate::{
+    keybindings::{Action, KeyMode},
+    model::AppModel,
+    ui::{Shortcut, Shortcuts},
+};
+use ratatui::{
+    layout::{Alignment, Rect},
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, BorderType, Borders, List, ListItem},
+};
+use rust_i18n::t;
+
+pub fn render_saved_searches(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let key = |action| model.keybindings.label(KeyMode::SavedSearches, action);
+    let apply = format!("{}/1-9", key(Action::Apply));
+    let (pin, delete, close) = (key(Action::Pin), key(Action::Delete), key(Action::Close));
+    let shortcuts = Shortcuts::new(vec![
+        Shortcut::Pair(&apply, t!("ui_shortcut_apply").as_ref()),
+        Shortcut::Pair(&pin, t!("ui_shortcut_pin").as_ref()),
+        Shortcut::Pair(&delete, t!("ui_shortcut_delete").as_ref()),
+        Shortcut::Pair(&close, t!("ui_shortcut_back").as_ref()),
+    ])
+    .with_alignment(Alignment::Right)
+    .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
+
+    let name_width = model
+        .config
+        .saved_searches
+        .iter()
+        .map(|search| search.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let items: Vec<ListItem> = model
+        .config
+        .saved_searches
+        .iter()
+        .enumerate()
+        .map(|(idx, search)| {
+            // Only the first nine searches have a number key
+            let number = if idx < 9 {
+                format!("{}", idx + 1)
+            } else {
+                " ".to_string()
+            };
+            let pin = if model.config.default_search.as_deref() == Some(search.name.as_str()) {
+                "📌"
+            } else {
+                "  "
+            };
+            let line = Line::from(vec![
+                Span::styled(number, Style::default().green().bold()),
+                Span::raw(" "),
+                Span::raw(pin),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:<width$}", search.name, width = name_width),
+                    Style::default().bold(),
+                ),
+                Span::raw(" | "),
+                Span::styled(search.query.clone(), Style::default().italic()),
+            ]);
+            ListItem::new(line).style(model.appearance.default_style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(format!(" {} ", t!("ui_saved_searches")))
+                .title_alignment(Alignment::Center)
+                .title_bottom(shortcuts.as_line())
+                .style(model.appearance.default_style),
+        )
+        .highlight_style(
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::ITALIC | Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, area, &mut model.saved_search_state.list_state);
+}
@@ -1,3 +1,5 @@
+use crate::analysis::Issue;
+use crate::keybindings::{Action, KeyMode};
 use crate::ui::Shortcut;
 use crate::{datetime, ui, ui::Shortcuts, AppModel, TimeEntryForTable};
 use ratatui::layout::{Alignment, Rect};
@@ -10,7 +12,10 @@ use rust_i18n::t;
 
 /// Get the display icon for a time entry
 fn get_time_entry_icon(time_entry: &TimeEntryForTable) -> String {
-    if let Some(custom_icon) = &time_entry.icon {
+    if time_entry.linked_id.is_some() {
+        // Already imported into MoneyBird
+        "🔗".to_string()
+    } else if let Some(custom_icon) = &time_entry.icon {
         // Use custom icon from plugin manifest if available
         custom_icon.clone()
     } else if time_entry.source.to_lowercase() == "moneybird" {
@@ -25,18 +30,122 @@ fn get_time_entry_icon(time_entry: &TimeEntryForTable) -> String {
     }
 }
 
+/// Calculate the total minutes per user for the MoneyBird entries, in order of appearance
+fn get_team_totals(model: &AppModel) -> Vec<(String, u64)> {
+    let mut totals: Vec<(String, u64)> = Vec::new();
+
+    for time_entry in model
+        .time_entries_for_table
+        .iter()
+        .filter(|time_entry| time_entry.source.to_lowercase() == "moneybird")
+    {
+        let entry_minutes = model.rounded_minutes(time_entry);
+
+        match totals.iter_mut().find(|(user, _)| user == &time_entry.user) {
+            Some((_, total)) => *total += entry_minutes,
+            None => totals.push((time_entry.user.clone(), entry_minutes)),
+        }
+    }
+
+    totals
+}
+
+/// Explain the badges of an entry, one line per overlap, gap or duplicate
+fn get_issue_lines(
+    model: &AppModel,
+    time_entry: &TimeEntryForTable,
+    admin_timezone_str: &str,
+) -> Vec<Line<'static>> {
+    let describe = |id: &str| match model.find_loaded_entry(id) {
+        Some(other) => (
+            datetime::format_time_range_from_time_entry(other.clone(), admin_timezone_str),
+            other
+                .description
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        None => (String::new(), id.to_string()),
+    };
+    let fix = model
+        .keybindings
+        .label(KeyMode::Normal, Action::FixConflict);
+
+    model
+        .analysis
+        .issues(&time_entry.id)
+        .iter()
+        .map(|issue| {
+            let text = match issue {
+                Issue::Overlap(id) => {
+                    let (time, description) = describe(id);
+                    t!(
+                        "analysis_overlap",
+                        time = time,
+                        description = description,
+                        key = fix
+                    )
+                }
+                Issue::Gap(minutes) => t!("analysis_gap", minutes = minutes),
+                Issue::Duplicate(id) => {
+                    let (time, description) = describe(id);
+                    t!("analysis_duplicate", time = time, description = description)
+                }
+            };
+            Line::from(vec![
+                Span::from(format!("{} ", issue.badge())).yellow(),
+                Span::from(text.to_string()).italic(),
+            ])
+        })
+        .collect()
+}
+
 pub fn render_time_entry_detail(model: &AppModel, area: Rect, frame: &mut Frame) {
+    // The footer shows the configured keys, see the keybindings module
+    let bindings = &model.keybindings;
+    let key = |action| bindings.label(KeyMode::Normal, action);
+    let key_pair = |first, second| bindings.label_pair(KeyMode::Normal, first, second);
+    let (previous_week, next_week) = (key(Action::PreviousWeek), key(Action::NextWeek));
+    let current_week = key(Action::CurrentWeek);
+    let search = key(Action::Search);
+    let saved_searches = key(Action::SavedSearches);
+    let sort = key_pair(Action::SortNextColumn, Action::SortDirection);
+    let group = key(Action::Group);
+    let collapse = key_pair(Action::ToggleGroup, Action::ToggleAllGroups);
+    let state = key(Action::CycleState);
+    let server_filter = key_pair(Action::FilterContact, Action::FilterProject);
+    let switch_user = key(Action::SwitchUser);
+    let create = key(Action::Create);
+    let edit = key(Action::Edit);
+    let import = key(Action::Import);
+    let delete = key(Action::Delete);
+    let plugins = key(Action::Plugins);
+    let export = key(Action::Export);
+    let submit = key(Action::SubmitWeek);
+    let quit = key(Action::Quit);
+    let command_palette = key(Action::CommandPalette);
+
     let shortcuts = Shortcuts::new(vec![
-        Shortcut::Trio("◀", t!("ui_shortcut_week").as_ref(), "▶"),
-        Shortcut::Pair("t", t!("ui_shortcut_this_week").as_ref()),
-        Shortcut::Pair("f", t!("ui_shortcut_filter").as_ref()),
-        Shortcut::Pair("c", t!("ui_shortcut_create").as_ref()),
-        Shortcut::Pair("e", t!("ui_shortcut_edit").as_ref()),
-        Shortcut::Pair("i", t!("ui_shortcut_import").as_ref()),
-        Shortcut::Pair("d", t!("ui_shortcut_delete").as_ref()),
-        Shortcut::Pair("p", t!("ui_shortcut_plugins").as_ref()),
-        Shortcut::Pair("x", t!("ui_shortcut_export").as_ref()),
-        Shortcut::Pair("q", t!("ui_shortcut_quit").as_ref()),
+        Shortcut::Trio(&previous_week, t!("ui_shortcut_week").as_ref(), &next_week),
+        Shortcut::Pair(&current_week, t!("ui_shortcut_this_week").as_ref()),
+        Shortcut::Pair(&command_palette, t!("ui_shortcut_commands").as_ref()),
+        Shortcut::Pair(&search, t!("ui_shortcut_filter").as_ref()),
+        Shortcut::Pair(&saved_searches, t!("ui_shortcut_saved_searches").as_ref()),
+        Shortcut::Pair(&sort, t!("ui_shortcut_sort").as_ref()),
+        Shortcut::Pair(&group, t!("ui_shortcut_group").as_ref()),
+        Shortcut::Pair(&collapse, t!("ui_shortcut_collapse").as_ref()),
+        Shortcut::Pair(&state, t!("ui_shortcut_state").as_ref()),
+        Shortcut::Pair(&server_filter, t!("ui_shortcut_server_filter").as_ref()),
+        Shortcut::Pair(&switch_user, t!("ui_shortcut_switch_user").as_ref()),
+        Shortcut::Pair(&create, t!("ui_shortcut_create").as_ref()),
+        Shortcut::Pair(&edit, t!("ui_shortcut_edit").as_ref()),
+        Shortcut::Pair(&import, t!("ui_shortcut_import").as_ref()),
+        Shortcut::Pair(&delete, t!("ui_shortcut_delete").as_ref()),
+        Shortcut::Pair(&plugins, t!("ui_shortcut_plugins").as_ref()),
+        Shortcut::Pair(&export, t!("ui_shortcut_export").as_ref()),
+        Shortcut::Pair(&submit, t!("ui_shortcut_submit").as_ref()),
+        Shortcut::Pair(&quit, t!("ui_shortcut_quit").as_ref()),
     ])
     .with_alignment(Alignment::Right)
     .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
@@ -58,7 +167,10 @@ pub fn render_time_entry_detail(model: &AppModel, area: Rect, frame: &mut Frame)
         .title_bottom(shortcuts.as_line());
 
     // Check if we have any items to display and a valid selection
-    if model.time_entries_for_table.is_empty() || model.time_entry_table_state.selected().is_none()
+    // A group header hides its entries, so it has no details
+    if model.time_entries_for_table.is_empty()
+        || model.time_entry_table_state.selected().is_none()
+        || model.is_group_header_selected()
     {
         // Render an empty detail view with a message
         let message = if model.time_entries_for_table.is_empty() {
@@ -120,7 +232,20 @@ pub fn render_time_entry_detail(model: &AppModel, area: Rect, frame: &mut Frame)
         datetime::calculate_duration(&selected_item.started_at, &selected_item.ended_at);
 
     let total_time_style = Style::default().bold().yellow();
-    let total_time = datetime::format_duration(hours, minutes, total_time_style);
+    let mut total_time = datetime::format_duration(hours, minutes, total_time_style);
+
+    // Show what is billed next to the recorded time when rounding changes it
+    let rounded_minutes = model.rounded_minutes(selected_item);
+    if selected_item.source.to_lowercase() == "moneybird" && rounded_minutes != hours * 60 + minutes
+    {
+        total_time.push(Span::from(" → "));
+        total_time.extend(datetime::format_duration(
+            rounded_minutes / 60,
+            rounded_minutes % 60,
+            total_time_style,
+        ));
+        total_time.push(Span::from(format!(" {}", t!("ui_rounded"))).italic());
+    }
 
     let mut times = vec![
         Span::from(crate::datetime::format_date_from_time_entry(
@@ -153,7 +278,58 @@ pub fn render_time_entry_detail(model: &AppModel, area: Rect, frame: &mut Frame)
 
     title_spans.push(Span::from(" "));
 
-    let mut detail_lines: Vec<Line> = vec![Line::from(times), Line::from("")];
+    // Show who owns the entry when looking at the whole team
+    if model.time_entry_filter.user_id.is_none() && !selected_item.user.is_empty() {
+        times.push(Span::from("  👤 "));
+        times.push(Span::from(selected_item.user.clone()).italic());
+    }
+
+    let mut detail_lines: Vec<Line> = vec![Line::from(times)];
+
+    // Show the weekly total per user when entries of more than one user are listed
+    let team_totals = get_team_totals(model);
+    if model.time_entry_filter.user_id.is_none() && team_totals.len() > 1 {
+        let mut team_spans = vec![Span::from("👥 ")];
+        for (idx, (user, total_minutes)) in team_totals.into_iter().enumerate() {
+            if idx > 0 {
+                team_spans.push(Span::from("  "));
+            }
+            let user = if user.is_empty() {
+                t!("ui_undefined").to_string()
+            } else {
+                user
+            };
+            team_spans.push(Span::from(format!("{} ", user)).bold());
+            team_spans.extend(datetime::format_duration(
+                total_minutes / 60,
+                total_minutes % 60,
+                total_time_style,
+            ));
+        }
+        detail_lines.push(Line::from(team_spans));
+    }
+
+    if let Some(moneybird_entry) = selected_item
+        .linked_id
+        .as_deref()
+        .and_then(|id| model.find_loaded_entry(id))
+    {
+        let import = model.keybindings.label(KeyMode::Normal, Action::Import);
+        detail_lines.push(Line::from(
+            Span::from(t!(
+                "ui_linked",
+                time = datetime::format_time_range_from_time_entry(
+                    moneybird_entry.clone(),
+                    &admin_timezone_str
+                ),
+                key = import
+            ))
+            .italic(),
+        ));
+    }
+    detail_lines.extend(get_issue_lines(model, selected_item, &admin_timezone_str));
+
+    detail_lines.push(Line::from(""));
     detail_lines.extend(description_lines);
 
     let detail = Paragraph::new(Text::from(detail_lines))
@@ -1,10 +1,12 @@
 use crate::{
+    keybindings::{Action, KeyMode},
     model::{AppModel, EditField, EditType},
     ui::{Autocomplete, Shortcut, Shortcuts},
 };
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::prelude::Stylize;
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
 use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap};
 use ratatui::Frame;
 use rust_i18n::t;
@@ -23,10 +25,13 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
     model.edit_form_area = Some(area);
 
     // Set up shortcuts for the edit view
+    let next_field = model.keybindings.label(KeyMode::Edit, Action::NextField);
+    let save = model.keybindings.label(KeyMode::Edit, Action::Save);
+    let cancel = model.keybindings.label(KeyMode::Edit, Action::Cancel);
     let shortcuts = Shortcuts::new(vec![
-        Shortcut::Pair("Tab", t!("ui_shortcut_change_focus").as_ref()),
-        Shortcut::Pair("Ctrl+S", t!("ui_shortcut_save").as_ref()),
-        Shortcut::Pair("Esc", t!("ui_shortcut_cancel").as_ref()),
+        Shortcut::Pair(&next_field, t!("ui_shortcut_change_focus").as_ref()),
+        Shortcut::Pair(&save, t!("ui_shortcut_save").as_ref()),
+        Shortcut::Pair(&cancel, t!("ui_shortcut_cancel").as_ref()),
     ])
     .with_alignment(Alignment::Right)
     .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
@@ -34,6 +39,7 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
     // Derive title from the edit type
     let title = match edit_state.edit_type {
         EditType::Create => t!("ui_edit_title_create"),
+        EditType::Import if edit_state.time_entry_id.is_some() => t!("ui_edit_title_reimport"),
         EditType::Import => t!("ui_edit_title_import"),
         EditType::Edit => t!("ui_edit_title_edit"),
     };
@@ -111,12 +117,36 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
     // Render the description field
     if edit_state.selected_field == EditField::Description {
         // Render editor in description field
-        edit_state.editor.set_block(active_block.clone());
+        edit_state.editor.set_block(with_field_error(
+            active_block.clone(),
+            edit_state.field_error(EditField::Description),
+        ));
         frame.render_widget(&edit_state.editor, chunks[1]);
+
+        // Suggestions from plugins drop down below the line with the cursor
+        if edit_state.description_autocomplete.is_dropdown_visible {
+            let (cursor_row, _) = edit_state.editor.cursor();
+            let inner = active_block.inner(chunks[1]);
+            let top = inner.y + (cursor_row as u16).min(inner.height.saturating_sub(1)) + 1;
+            let height = inner.bottom().saturating_sub(top);
+            if height > 2 {
+                let dropdown_area = Rect::new(inner.x, top, inner.width, height);
+                let suggestions_widget = Autocomplete::new(
+                    &mut edit_state.description_autocomplete,
+                    |suggestion: &String| suggestion.replace('\n', " "),
+                )
+                .selected_style(Style::default().bg(Color::Green).fg(Color::White))
+                .dropdown_only();
+                frame.render_widget(suggestions_widget, dropdown_area);
+            }
+        }
     } else {
         // Render static text
         let description_para = Paragraph::new(edit_state.description.clone())
-            .block(inactive_block.clone())
+            .block(with_field_error(
+                inactive_block.clone(),
+                edit_state.field_error(EditField::Description),
+            ))
             .wrap(Wrap { trim: true });
         frame.render_widget(description_para, chunks[1]);
     }
@@ -144,6 +174,7 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
     let transform_contact_fn = |contact: &crate::moneybird::types::Contact| -> String {
         contact.company_name.clone().unwrap_or_default()
     };
+    let contact_error = edit_state.field_error(EditField::Contact).cloned();
     let contact_title = format!(" {} ", t!("ui_edit_field_contact"));
     let contact_placeholder = t!("ui_edit_placeholder_contact");
     let contact_autocomplete_widget =
@@ -162,7 +193,10 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
         // Use active block when selected
         let active_title = format!("{}{}", t!("ui_edit_icon_active"), contact_title);
         frame.render_widget(
-            contact_autocomplete_widget.block(active_block.clone().title(active_title)),
+            contact_autocomplete_widget.block(with_field_error(
+                active_block.clone().title(active_title),
+                contact_error.as_ref(),
+            )),
             contact_area,
         );
         // Set cursor position for the active contact field
@@ -182,12 +216,13 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
         } else {
             t!("ui_edit_contact_empty").to_string()
         };
-        let contact_label = Paragraph::new(contact_display).block(
+        let contact_label = Paragraph::new(contact_display).block(with_field_error(
             inactive_block
                 .clone()
                 .title(contact_title)
                 .padding(Padding::new(1, 0, 0, 0)),
-        );
+            contact_error.as_ref(),
+        ));
         frame.render_widget(contact_label, contact_area);
     }
 
@@ -197,6 +232,7 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
     let transform_project_fn = |project: &crate::moneybird::types::Project| -> String {
         project.name.clone().unwrap_or_default()
     };
+    let project_error = edit_state.field_error(EditField::Project).cloned();
     let project_title = format!(" {} ", t!("ui_edit_field_project"));
     let project_placeholder = t!("ui_edit_placeholder_project");
     let project_autocomplete_widget =
@@ -215,7 +251,10 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
         // Use active block when selected
         let active_title = format!("{}{}", t!("ui_edit_icon_active"), project_title);
         frame.render_widget(
-            project_autocomplete_widget.block(active_block.clone().title(active_title)),
+            project_autocomplete_widget.block(with_field_error(
+                active_block.clone().title(active_title),
+                project_error.as_ref(),
+            )),
             project_area,
         );
         // Set cursor position for the active project field
@@ -235,12 +274,13 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
         } else {
             t!("ui_edit_project_empty").to_string()
         };
-        let project_label = Paragraph::new(project_display).block(
+        let project_label = Paragraph::new(project_display).block(with_field_error(
             inactive_block
                 .clone()
                 .title(project_title)
                 .padding(Padding::new(1, 0, 0, 0)),
-        );
+            project_error.as_ref(),
+        ));
         frame.render_widget(project_label, project_area);
     }
 
@@ -272,71 +312,101 @@ pub fn render_time_entry_edit(model: &mut AppModel, area: Rect, frame: &mut Fram
     // Start time field
     let start_time_label = format!(" {} ", t!("ui_edit_field_start_time"));
     if selected_field == EditField::StartTime {
-        edit_state
-            .editor
-            .set_block(active_block.clone().title(format!(
+        edit_state.editor.set_block(with_field_error(
+            active_block.clone().title(format!(
                 "{}{}",
                 t!("ui_edit_icon_active"),
                 start_time_label
-            )));
+            )),
+            edit_state.field_error(EditField::StartTime),
+        ));
         frame.render_widget(&edit_state.editor, date_time_row[0]);
     } else {
-        let widget = Paragraph::new(edit_state.start_time.clone())
-            .block(inactive_block.clone().title(start_time_label));
+        let widget = Paragraph::new(edit_state.start_time.clone()).block(with_field_error(
+            inactive_block.clone().title(start_time_label),
+            edit_state.field_error(EditField::StartTime),
+        ));
         frame.render_widget(widget, date_time_row[0]);
     }
 
     // End time field
     let end_time_label = format!(" {} ", t!("ui_edit_field_end_time"));
     if selected_field == EditField::EndTime {
-        edit_state
-            .editor
-            .set_block(active_block.clone().title(format!(
-                "{}{}",
-                t!("ui_edit_icon_active"),
-                end_time_label
-            )));
+        edit_state.editor.set_block(with_field_error(
+            active_block
+                .clone()
+                .title(format!("{}{}", t!("ui_edit_icon_active"), end_time_label)),
+            edit_state.field_error(EditField::EndTime),
+        ));
         frame.render_widget(&edit_state.editor, date_time_row[1]);
     } else {
-        let widget = Paragraph::new(edit_state.end_time.clone())
-            .block(inactive_block.clone().title(end_time_label));
+        let widget = Paragraph::new(edit_state.end_time.clone()).block(with_field_error(
+            inactive_block.clone().title(end_time_label),
+            edit_state.field_error(EditField::EndTime),
+        ));
         frame.render_widget(widget, date_time_row[1]);
     }
 
     // Start date field
     let start_date_label = format!(" {} ", t!("ui_edit_field_start_date"));
     if selected_field == EditField::StartDate {
-        edit_state
-            .editor
-            .set_block(active_block.clone().title(format!(
+        edit_state.editor.set_block(with_field_error(
+            active_block.clone().title(format!(
                 "{}{}",
                 t!("ui_edit_icon_active"),
                 start_date_label
-            )));
+            )),
+            edit_state.field_error(EditField::StartDate),
+        ));
         frame.render_widget(&edit_state.editor, date_time_row[2]);
     } else {
-        let widget = Paragraph::new(edit_state.start_date.clone())
-            .block(inactive_block.clone().title(start_date_label));
+        let widget = Paragraph::new(edit_state.start_date.clone()).block(with_field_error(
+            inactive_block.clone().title(start_date_label),
+            edit_state.field_error(EditField::StartDate),
+        ));
         frame.render_widget(widget, date_time_row[2]);
     }
 
     // End date field
     let end_date_label = format!(" {} ", t!("ui_edit_field_end_date"));
     if selected_field == EditField::EndDate {
-        edit_state
-            .editor
-            .set_block(active_block.clone().title(format!(
-                "{}{}",
-                t!("ui_edit_icon_active"),
-                end_date_label
-            )));
+        edit_state.editor.set_block(with_field_error(
+            active_block
+                .clone()
+                .title(format!("{}{}", t!("ui_edit_icon_active"), end_date_label)),
+            edit_state.field_error(EditField::EndDate),
+        ));
         frame.render_widget(&edit_state.editor, date_time_row[3]);
     } else {
-        let widget = Paragraph::new(edit_state.end_date.clone())
-            .block(inactive_block.clone().title(end_date_label));
+        let widget = Paragraph::new(edit_state.end_date.clone()).block(with_field_error(
+            inactive_block.clone().title(end_date_label),
+            edit_state.field_error(EditField::EndDate),
+        ));
         frame.render_widget(widget, date_time_row[3]);
     }
 
+    // Saving is refused until the errors under the fields are fixed
+    let error_count = edit_state.error_count();
+    if edit_state.save_attempted && error_count > 0 {
+        let summary = Paragraph::new(t!("edit_fix_errors", count = error_count).to_string())
+            .style(Style::default().fg(Color::Red))
+            .block(Block::default().padding(Padding::new(1, 0, 0, 0)));
+        frame.render_widget(summary, chunks[7]);
+    }
+
     // Render the form's outer block
     frame.render_widget(form_block.clone(), area);
 }
+
+/// Mark a field that needs fixing red, with the reason below its value
+fn with_field_error<'a>(block: Block<'a>, error: Option<&String>) -> Block<'a> {
+    match error {
+        Some(error) => block
+            .border_style(Style::default().fg(Color::Red))
+            .title_bottom(Line::styled(
+                format!(" {} ", error),
+                Style::default().fg(Color::Red),
+            )),
+        None => block,
+    }
+}
@@ -1,11 +1,70 @@
+use crate::keybindings::{Action, KeyMode};
+use crate::query::{self, TokenKind};
 use crate::ui::{Shortcut, Shortcuts};
 use crate::AppModel;
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Modifier, Stylize};
-use ratatui::widgets::{Block, Borders, Padding};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
 use ratatui::{symbols, Frame};
 use rust_i18n::t;
 
+/// Style the query input per character based on the query tokens
+fn get_highlighted_line(model: &AppModel, width: usize) -> Line<'static> {
+    let input = model
+        .search_state
+        .text_input
+        .lines()
+        .first()
+        .cloned()
+        .unwrap_or_default();
+    let chars: Vec<char> = input.chars().collect();
+    let default_style = model.appearance.default_style;
+
+    let mut styles = vec![default_style; chars.len() + 1];
+    for span in query::highlight(&input) {
+        let style = match span.kind {
+            TokenKind::Text => default_style,
+            TokenKind::Field => default_style.fg(Color::Cyan),
+            TokenKind::Value => default_style.fg(Color::Yellow),
+            TokenKind::Operator => default_style
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            TokenKind::Error => default_style
+                .fg(Color::Red)
+                .add_modifier(Modifier::UNDERLINED),
+        };
+        for char_style in styles.iter_mut().take(span.end).skip(span.start) {
+            *char_style = style;
+        }
+    }
+
+    // Draw the cursor the same way the text area does
+    let (_, cursor_col) = model.search_state.text_input.cursor();
+    let cursor_col = cursor_col.min(chars.len());
+    styles[cursor_col] = styles[cursor_col].add_modifier(Modifier::REVERSED);
+
+    // Scroll horizontally so the cursor stays visible
+    let offset = cursor_col.saturating_sub(width.saturating_sub(1));
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut current = String::new();
+    let mut current_style: Option<Style> = None;
+    for (idx, style) in styles.iter().enumerate().skip(offset) {
+        let c = chars.get(idx).copied().unwrap_or(' ');
+        if let Some(previous_style) = current_style.filter(|s| s != style) {
+            spans.push(Span::styled(std::mem::take(&mut current), previous_style));
+        }
+        current.push(c);
+        current_style = Some(*style);
+    }
+    if let Some(style) = current_style {
+        spans.push(Span::styled(current, style));
+    }
+
+    Line::from(spans)
+}
+
 pub fn render_search(model: &mut AppModel, area: Rect, frame: &mut Frame) {
     let collapsed_top_border_set = symbols::border::Set {
         top_left: symbols::line::NORMAL.vertical_right,
@@ -13,11 +72,26 @@ pub fn render_search(model: &mut AppModel, area: Rect, frame: &mut Frame) {
         ..symbols::border::PLAIN
     };
 
-    let shortcuts = Shortcuts::new(vec![
-        Shortcut::Pair("🔍", t!("ui_shortcut_filter").as_ref()),
-        Shortcut::Pair("Esc", t!("ui_shortcut_exit").as_ref()),
-        Shortcut::Pair("Ctrl+U", t!("ui_shortcut_clear").as_ref()),
-    ])
+    let naming = model.saved_search_state.name_input.is_some();
+    let close = model.keybindings.label(KeyMode::Search, Action::Close);
+    let clear = model
+        .keybindings
+        .label(KeyMode::Search, Action::ClearSearch);
+    let save = model.keybindings.label(KeyMode::Search, Action::SaveSearch);
+    let shortcuts = if naming {
+        Shortcuts::new(vec![
+            Shortcut::Pair("💾", t!("ui_save_search_as").as_ref()),
+            Shortcut::Pair("Enter", t!("ui_shortcut_save").as_ref()),
+            Shortcut::Pair("Esc", t!("ui_shortcut_cancel").as_ref()),
+        ])
+    } else {
+        Shortcuts::new(vec![
+            Shortcut::Pair("🔍", t!("ui_shortcut_filter").as_ref()),
+            Shortcut::Pair(&close, t!("ui_shortcut_exit").as_ref()),
+            Shortcut::Pair(&clear, t!("ui_shortcut_clear").as_ref()),
+            Shortcut::Pair(&save, t!("ui_shortcut_save").as_ref()),
+        ])
+    }
     .with_alignment(Alignment::Left)
     .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
 
@@ -33,9 +107,26 @@ pub fn render_search(model: &mut AppModel, area: Rect, frame: &mut Frame) {
         .title(shortcuts.as_line())
         .style(model.appearance.default_style);
 
+    // Show why the query isn't applied
+    let block = match &model.search_state.error {
+        Some(error) => block.title(
+            Line::from(format!(" {} ", error))
+                .red()
+                .alignment(Alignment::Right),
+        ),
+        None => block,
+    };
+
     let inner_area = block.inner(area);
 
     frame.render_widget(block, area);
 
-    frame.render_widget(&model.search_state.text_input, inner_area);
+    // While naming the search, the input takes the place of the query
+    if let Some(name_input) = &model.saved_search_state.name_input {
+        frame.render_widget(name_input, inner_area);
+        return;
+    }
+
+    let line = get_highlighted_line(model, inner_area.width as usize);
+    frame.render_widget(Paragraph::new(line), inner_area);
 }
@@ -1,16 +1,22 @@
+use crate::config::{SortDirection, TableColumn, TableColumnKind};
+use crate::model::{GroupBy, TableRow, TimeEntryStateFilter};
+use crate::query::{MatchPositions, Query};
 use crate::ui;
 use crate::{datetime, AppModel, TimeEntryForTable};
-use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::prelude::Stylize;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Row, Table};
 use ratatui::Frame;
 use rust_i18n::t;
 
 /// Get the display icon for a time entry
-fn get_time_entry_icon(time_entry: &TimeEntryForTable) -> String {
-    if let Some(custom_icon) = &time_entry.icon {
+pub(crate) fn get_time_entry_icon(time_entry: &TimeEntryForTable) -> String {
+    if time_entry.linked_id.is_some() {
+        // Already imported into MoneyBird
+        "🔗".to_string()
+    } else if let Some(custom_icon) = &time_entry.icon {
         // Use custom icon from plugin manifest if available
         custom_icon.clone()
     } else if time_entry.source.to_lowercase() == "moneybird" {
@@ -25,22 +31,171 @@ fn get_time_entry_icon(time_entry: &TimeEntryForTable) -> String {
     }
 }
 
+// Narrowest a flexible column gets before columns are hidden
+const FLEXIBLE_MIN_WIDTH: u16 = 10;
+
+/// Get the header label of a table column
+fn get_column_header(kind: TableColumnKind) -> String {
+    match kind {
+        TableColumnKind::Icon => String::new(), // Empty header for the icon column
+        TableColumnKind::Date => t!("ui_table_header_date").to_string(),
+        TableColumnKind::Time => t!("ui_table_header_time").to_string(),
+        TableColumnKind::Duration => t!("ui_table_header_duration").to_string(),
+        TableColumnKind::Billable => t!("ui_table_header_billable").to_string(),
+        TableColumnKind::User => t!("ui_table_header_user").to_string(),
+        TableColumnKind::Client => t!("ui_table_header_client").to_string(),
+        TableColumnKind::Project => t!("ui_table_header_project").to_string(),
+        TableColumnKind::Source => t!("ui_table_header_source").to_string(),
+        TableColumnKind::Tags => t!("ui_table_header_tags").to_string(),
+        TableColumnKind::Description => t!("ui_table_header_description").to_string(),
+    }
+}
+
+/// Build the cell of a table column for a time entry
+fn get_column_cell(
+    kind: TableColumnKind,
+    time_entry: &TimeEntryForTable,
+    admin_timezone_str: &str,
+    positions: &MatchPositions,
+    match_style: Style,
+    badges: &str,
+) -> Line<'static> {
+    match kind {
+        TableColumnKind::Icon => Line::from(get_time_entry_icon(time_entry)),
+        TableColumnKind::Date => Line::from(datetime::format_date_from_time_entry(
+            time_entry.clone(),
+            admin_timezone_str,
+        )),
+        TableColumnKind::Time => {
+            let mut line = Line::from(datetime::format_time_range_from_time_entry(
+                time_entry.clone(),
+                admin_timezone_str,
+            ));
+            // Overlaps, gaps and duplicates, explained in the detail view
+            if !badges.is_empty() {
+                line.spans.push(Span::styled(
+                    format!(" {}", badges),
+                    Style::default().yellow(),
+                ));
+            }
+            line
+        }
+        TableColumnKind::Duration => {
+            let (hours, minutes) =
+                datetime::calculate_duration(&time_entry.started_at, &time_entry.ended_at);
+            Line::from(datetime::format_duration(hours, minutes, Style::default()))
+        }
+        TableColumnKind::Billable => Line::from(if time_entry.billable { "✓" } else { "" }),
+        TableColumnKind::User => Line::from(time_entry.user.clone()),
+        TableColumnKind::Client => {
+            ui::highlight_matches(&time_entry.customer, &positions.customer, match_style)
+        }
+        TableColumnKind::Project => {
+            ui::highlight_matches(&time_entry.project, &positions.project, match_style)
+        }
+        TableColumnKind::Source => Line::from(time_entry.source.clone()),
+        TableColumnKind::Tags => Line::from(time_entry.tags.join(", ")),
+        // Replacing newlines keeps the char positions intact
+        TableColumnKind::Description => ui::highlight_matches(
+            &time_entry.description.replace("\n", " "),
+            &positions.description,
+            match_style,
+        ),
+    }
+}
+
+/// Pick the columns that fit in the available width together with their width constraint.
+/// Columns with the lowest priority are hidden first, on a tie the rightmost column goes.
+fn fit_columns(
+    columns: &[TableColumn],
+    content_widths: &[u16],
+    available: u16,
+) -> Vec<(usize, Constraint)> {
+    let is_flexible = |column: &TableColumn| {
+        column.percentage.is_some()
+            || (column.column == TableColumnKind::Description && column.max_width.is_none())
+    };
+    let fitted_width = |idx: usize| {
+        let column = &columns[idx];
+        content_widths[idx]
+            .max(column.min_width.unwrap_or(0))
+            .min(column.max_width.unwrap_or(u16::MAX))
+    };
+    let min_width = |idx: usize| {
+        if is_flexible(&columns[idx]) {
+            columns[idx].min_width.unwrap_or(FLEXIBLE_MIN_WIDTH)
+        } else {
+            fitted_width(idx)
+        }
+    };
+
+    let mut visible: Vec<usize> = (0..columns.len()).collect();
+    loop {
+        // Each column after the first is separated by one space
+        let required: u32 = visible
+            .iter()
+            .map(|&idx| u32::from(min_width(idx)))
+            .sum::<u32>()
+            + visible.len().saturating_sub(1) as u32;
+        if visible.len() <= 1 || required <= u32::from(available) {
+            break;
+        }
+
+        let hidden = visible
+            .iter()
+            .enumerate()
+            .min_by_key(|&(pos, &idx)| (columns[idx].get_priority(), std::cmp::Reverse(pos)))
+            .map(|(pos, _)| pos);
+        if let Some(pos) = hidden {
+            visible.remove(pos);
+        }
+    }
+
+    visible
+        .into_iter()
+        .map(|idx| {
+            let column = &columns[idx];
+            let constraint = match column.percentage {
+                Some(percentage) => Constraint::Percentage(percentage),
+                None if is_flexible(column) => Constraint::Fill(1),
+                None => Constraint::Length(fitted_width(idx)),
+            };
+            (idx, constraint)
+        })
+        .collect()
+}
+
+/// Describe the active server-side filters for the table title
+fn get_filter_description(model: &AppModel) -> Vec<String> {
+    let filter = &model.time_entry_filter;
+    let mut parts = Vec::new();
+
+    if filter.state != TimeEntryStateFilter::All {
+        parts.push(filter.state.label());
+    }
+    if filter.contact_id.is_some() {
+        parts.push(ui::get_contact_name(
+            &model.contacts,
+            filter.contact_id.as_ref(),
+        ));
+    }
+    if filter.project_id.is_some() {
+        parts.push(ui::get_project_name(
+            &model.projects,
+            filter.project_id.as_ref(),
+        ));
+    }
+    if filter.user_id.is_some() {
+        parts.push(ui::get_user_name(&model.users, filter.user_id.as_ref()));
+    }
+
+    parts
+}
+
 pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut Frame) {
     // Store the table area for mouse click handling
     model.table_area = Some(area);
 
-    let header_cols = vec![
-        "".to_string(), // Empty header for the icon column
-        t!("ui_table_header_date").to_string(),
-        t!("ui_table_header_time").to_string(),
-        t!("ui_table_header_client").to_string(),
-        t!("ui_table_header_project").to_string(),
-        t!("ui_table_header_description").to_string(),
-    ];
-    let header = Row::new(header_cols)
-        .style(model.appearance.default_style.add_modifier(Modifier::BOLD))
-        .height(1);
-
     // Get administration timezone, default to UTC if not set
     let admin_timezone_str = model
         .administration
@@ -54,20 +209,6 @@ pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut F
         &model.config.week_starts_on,
     );
 
-    // Calculate the colmun widths
-    let client_width = model
-        .time_entries_for_table
-        .iter()
-        .map(|time_entry| time_entry.customer.len())
-        .max()
-        .unwrap_or(0);
-    let project_width = model
-        .time_entries_for_table
-        .iter()
-        .map(|time_entry| time_entry.project.len())
-        .max()
-        .unwrap_or(0);
-
     // Get relative week description
     let week_relative = datetime::get_title_week_description(model.week_offset);
 
@@ -77,9 +218,7 @@ pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut F
         .iter()
         .filter(|time_entry| time_entry.source.to_lowercase() == "moneybird") // Filter for Moneybird entries
         .fold(0_u64, |acc, time_entry| {
-            let (hours, minutes) =
-                datetime::calculate_duration(&time_entry.started_at, &time_entry.ended_at);
-            acc + (hours * 60 + minutes)
+            acc + model.rounded_minutes(time_entry)
         });
 
     // Convert to hours and minutes for display
@@ -104,15 +243,188 @@ pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut F
     title_spans.extend(total_time_str);
     title_spans.push(Span::from(" "));
 
-    // Get the currently selected index for calculating distance
-    let selected_idx = model.time_entry_table_state.selected().unwrap_or(0);
+    // Show the active server-side filters
+    let filter_description = get_filter_description(model);
+    if !filter_description.is_empty() {
+        title_spans.push(Span::from(title_separator.to_string()));
+        title_spans.push(filter_description.join(" · ").magenta());
+        title_spans.push(Span::from(" "));
+    }
+
+    // Mark a submitted week
+    if model.week_lock.is_some() {
+        title_spans.push(Span::from(title_separator.to_string()));
+        title_spans.push(t!("ui_table_title_submitted").to_string().red().bold());
+        title_spans.push(Span::from(" "));
+    }
+
+    // Mark a grouped table
+    let grouped = model.group_state.group_by != GroupBy::None;
+    if grouped {
+        title_spans.push(Span::from(title_separator.to_string()));
+        title_spans.push(
+            t!(
+                "ui_table_title_grouped",
+                group = model.group_state.group_by.label()
+            )
+            .to_string()
+            .cyan(),
+        );
+        title_spans.push(Span::from(" "));
+    }
+
+    // Show what the plugins are busy with
+    let mut busy: Vec<String> = model
+        .plugin_progress
+        .iter()
+        .map(|(plugin, progress)| format!("{}: {}", plugin, progress.label()))
+        .collect();
+    if model.loading_plugin_entries() && busy.is_empty() {
+        busy.push(t!("ui_table_title_loading_plugins").to_string());
+    }
+    if !busy.is_empty() {
+        title_spans.push(Span::from(title_separator.to_string()));
+        title_spans.push(format!("⟳ {}", busy.join(" · ")).blue().italic());
+        title_spans.push(Span::from(" "));
+    }
+
+    // The visible rows, group headers included when grouping is active
+    let table_rows = model.table_rows();
+    let selected_row = if grouped {
+        model.selected_table_row(&table_rows)
+    } else {
+        model.time_entry_table_state.selected()
+    };
+
+    // Get the currently selected row for calculating distance
+    let selected_idx = selected_row.unwrap_or(0);
+
+    // Highlight the characters that matched the search query
+    let search_query = if model.search_state.active {
+        model
+            .search_state
+            .text_input
+            .lines()
+            .first()
+            .and_then(|input| Query::parse(input).ok())
+    } else {
+        None
+    };
+    let match_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    // The configured columns, the user column only shows who owns each entry when
+    // looking at the whole team
+    let show_user_column = model.time_entry_filter.user_id.is_none();
+    let columns: Vec<TableColumn> = model
+        .config
+        .table
+        .get_columns()
+        .into_iter()
+        .filter(|column| show_user_column || column.column != TableColumnKind::User)
+        .collect();
+
+    // Mark the sorted column
+    let sort_indicator = match model.config.sort.direction {
+        SortDirection::Ascending => "▲",
+        SortDirection::Descending => "▼",
+    };
+    let headers: Vec<String> = columns
+        .iter()
+        .map(|column| {
+            let label = get_column_header(column.column);
+            if column.column.sort_column() == Some(model.config.sort.column) {
+                format!("{}{}", label, sort_indicator)
+            } else {
+                label
+            }
+        })
+        .collect();
 
-    // Create table rows from time entries
-    let rows: Vec<Row> = model
+    // Build the cells of every entry, also the ones in collapsed groups so the widths
+    // don't change when a group is collapsed
+    let entry_cells: Vec<Vec<Line>> = model
         .time_entries_for_table
+        .iter()
+        .map(|time_entry| {
+            let positions = search_query
+                .as_ref()
+                .map(|query| query.match_positions(time_entry))
+                .unwrap_or_default();
+            columns
+                .iter()
+                .map(|column| {
+                    get_column_cell(
+                        column.column,
+                        time_entry,
+                        &admin_timezone_str,
+                        &positions,
+                        match_style,
+                        &model.analysis.badges(&time_entry.id),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    // Calculate the column widths from the header and the content
+    let content_widths: Vec<u16> = headers
         .iter()
         .enumerate()
-        .map(|(idx, time_entry)| {
+        .map(|(col_idx, header)| {
+            entry_cells
+                .iter()
+                .map(|cells| cells[col_idx].width())
+                .max()
+                .unwrap_or(0)
+                .max(Line::from(header.as_str()).width()) as u16
+        })
+        .collect();
+
+    // Hide columns that don't fit on narrow terminals
+    let table_block = model
+        .appearance
+        .default_block
+        .clone()
+        .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+        .padding(Padding::new(1, 1, 0, 0));
+    let inner_area = table_block.inner(area);
+    let visible_columns = fit_columns(&columns, &content_widths, inner_area.width);
+    let widths: Vec<Constraint> = visible_columns
+        .iter()
+        .map(|(_, constraint)| *constraint)
+        .collect();
+
+    let header = Row::new(
+        visible_columns
+            .iter()
+            .map(|(col_idx, _)| headers[*col_idx].clone())
+            .collect::<Vec<String>>(),
+    )
+    .style(model.appearance.default_style.add_modifier(Modifier::BOLD))
+    .height(1);
+
+    // Group headers show their summary in the description column and the subtotal in the
+    // duration or time column
+    let summary_position = visible_columns
+        .iter()
+        .position(|(col_idx, _)| columns[*col_idx].column == TableColumnKind::Description)
+        .unwrap_or(visible_columns.len().saturating_sub(1));
+    let subtotal_position = visible_columns
+        .iter()
+        .position(|(col_idx, _)| columns[*col_idx].column == TableColumnKind::Duration)
+        .or_else(|| {
+            visible_columns
+                .iter()
+                .position(|(col_idx, _)| columns[*col_idx].column == TableColumnKind::Time)
+        });
+
+    // Create table rows from time entries and group headers
+    let rows: Vec<Row> = table_rows
+        .iter()
+        .enumerate()
+        .map(|(idx, table_row)| {
             // Calculate distance from selected row to apply gradient
             let distance = if idx > selected_idx {
                 idx - selected_idx
@@ -130,40 +442,79 @@ pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut F
                 model.appearance.color_mode,
             );
 
-            // Get a properly formatted set of time entries
-            // Use crate::datetime::format_date_from_time_entry and crate::datetime::format_time_range_from_time_entry
-            let date = crate::datetime::format_date_from_time_entry(
-                time_entry.clone(),
-                &admin_timezone_str,
-            );
-            let time = crate::datetime::format_time_range_from_time_entry(
-                time_entry.clone(),
-                &admin_timezone_str,
-            );
+            match table_row {
+                TableRow::Entry(entry_idx) => {
+                    let cells: Vec<Line> = visible_columns
+                        .iter()
+                        .map(|(col_idx, _)| entry_cells[*entry_idx][*col_idx].clone())
+                        .collect();
+                    // Imported plugin entries step back behind their MoneyBird entry
+                    if model.time_entries_for_table[*entry_idx].linked_id.is_some() {
+                        Row::new(cells).style(row_style.add_modifier(Modifier::DIM))
+                    } else {
+                        Row::new(cells).style(row_style)
+                    }
+                }
+                TableRow::Group {
+                    label,
+                    count,
+                    minutes,
+                    collapsed,
+                    ..
+                } => {
+                    let icon = if *collapsed { "▸" } else { "▾" };
+                    let cells: Vec<Line> = (0..visible_columns.len())
+                        .map(|position| {
+                            let mut spans = Vec::new();
+                            // The expand marker goes in front of the first column
+                            if position == 0 {
+                                spans.push(Span::raw(icon));
+                                if position == summary_position
+                                    || Some(position) == subtotal_position
+                                {
+                                    spans.push(Span::raw(" "));
+                                }
+                            }
+                            if position == summary_position {
+                                spans.push(Span::styled(
+                                    label.clone(),
+                                    Style::default().fg(Color::Cyan).bold(),
+                                ));
+                                spans.push(Span::raw(" · "));
+                                spans.push(Span::raw(
+                                    t!("ui_group_entries", count = *count).to_string(),
+                                ));
+                            } else if Some(position) == subtotal_position {
+                                spans.extend(datetime::format_duration(
+                                    minutes / 60,
+                                    minutes % 60,
+                                    Style::default().bold(),
+                                ));
+                            }
+                            Line::from(spans)
+                        })
+                        .collect();
 
-            // Get the icon for this time entry
-            let icon = get_time_entry_icon(time_entry);
-
-            Row::new(vec![
-                icon,
-                date,
-                time,
-                time_entry.customer.clone(),
-                time_entry.project.clone(),
-                time_entry.description.clone().replace("\n", " "),
-            ])
-            .style(row_style)
+                    Row::new(cells).style(row_style.add_modifier(Modifier::BOLD))
+                }
+            }
         })
         .collect();
 
-    let widths = [
-        Constraint::Length(2),                    // Icon column (small fixed width)
-        Constraint::Length(10),                   // Date (YYYY-MM-DD)
-        Constraint::Length(11),                   // Time range (HH:MM-HH:MM)
-        Constraint::Length(client_width as u16),  // Client name
-        Constraint::Length(project_width as u16), // Project name
-        Constraint::Fill(1),                      // Description (fills remaining space)
-    ];
+    // Remember where each header column is, so clicking it changes the sort
+    let column_areas = Layout::horizontal(widths.clone())
+        .spacing(1)
+        .split(inner_area);
+    model.table_header_columns = visible_columns
+        .iter()
+        .zip(column_areas.iter())
+        .filter_map(|((col_idx, _), column_area)| {
+            columns[*col_idx]
+                .column
+                .sort_column()
+                .map(|column| (column_area.x, column_area.right(), column))
+        })
+        .collect();
 
     // If table is empty, render empty state
     if model.time_entries_for_table.is_empty() {
@@ -171,14 +522,9 @@ pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut F
         let empty_state = Paragraph::new(empty_message)
             .alignment(Alignment::Center)
             .block(
-                model
-                    .appearance
-                    .default_block
-                    .clone()
-                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                table_block
                     .title(Line::from(title_spans))
-                    .title_alignment(Alignment::Center)
-                    .padding(Padding::new(1, 1, 0, 0)),
+                    .title_alignment(Alignment::Center),
             );
 
         frame.render_widget(empty_state, area);
@@ -191,16 +537,17 @@ pub fn render_time_entries_table(model: &mut AppModel, area: Rect, frame: &mut F
                     .add_modifier(Modifier::REVERSED | Modifier::ITALIC | Modifier::BOLD),
             )
             .block(
-                model
-                    .appearance
-                    .default_block
-                    .clone()
-                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-                    .padding(Padding::new(1, 1, 0, 0))
-                    .title(Line::from(title_spans.clone())) // Clone needed here
+                table_block
+                    .title(Line::from(title_spans))
                     .title_alignment(Alignment::Center),
             );
 
-        frame.render_stateful_widget(table, area, &mut model.time_entry_table_state);
+        if grouped {
+            // Headers shift the rows, so the grouped table keeps its own selection and offset
+            model.group_state.table_state.select(selected_row);
+            frame.render_stateful_widget(table, area, &mut model.group_state.table_state);
+        } else {
+            frame.render_stateful_widget(table, area, &mut model.time_entry_table_state);
+        }
     }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    keybindings::{Action, KeyMode},
     model::AppModel,
     ui::{Shortcut, Shortcuts},
 };
@@ -11,32 +12,54 @@ use ratatui::{
 use rust_i18n::t;
 
 pub fn render_user_selection(model: &mut AppModel, area: Rect, frame: &mut Frame) {
+    let (title, exit_key, exit_label) = if model.user_switcher_active {
+        (
+            t!("ui_switch_user"),
+            model.keybindings.label(KeyMode::Users, Action::Close),
+            t!("ui_shortcut_back"),
+        )
+    } else {
+        (
+            t!("ui_select_default_user"),
+            model.keybindings.label(KeyMode::Users, Action::Quit),
+            t!("ui_shortcut_exit_user_selection"),
+        )
+    };
+    let confirm = model.keybindings.label(KeyMode::Users, Action::Confirm);
+
     let shortcuts = Shortcuts::new(vec![
-        Shortcut::Pair("Enter", t!("ui_shortcut_select_user").as_ref()),
-        Shortcut::Pair("Esc", t!("ui_shortcut_exit_user_selection").as_ref()),
+        Shortcut::Pair(&confirm, t!("ui_shortcut_select_user").as_ref()),
+        Shortcut::Pair(&exit_key, exit_label.as_ref()),
     ])
     .with_alignment(Alignment::Right)
     .with_label_style(model.appearance.default_style.add_modifier(Modifier::BOLD));
 
+    // When switching users, the first row shows the entries of the whole team
+    let mut items: Vec<ListItem> = Vec::new();
+    if model.user_switcher_active {
+        let line = Line::from(vec![
+            Span::styled(format!("{:<38}", ""), Style::default()),
+            Span::raw(" | "),
+            Span::styled(t!("ui_whole_team").to_string(), Style::default().bold()),
+        ]);
+        items.push(ListItem::new(line).style(model.appearance.default_style));
+    }
+
     // Create ListItems instead of Rows
-    let items: Vec<ListItem> = model
-        .users
-        .iter()
-        .map(|user| {
-            let name = user.name.clone().unwrap_or_default();
-            let email = user.email.clone().unwrap_or_default();
-            let id = user.id.clone().unwrap_or_default();
-            // Format the user info into a single line for the list
-            let line = Line::from(vec![
-                Span::styled(format!("{:<38}", id), Style::default()), // Pad ID
-                Span::raw(" | "),
-                Span::styled(format!("{:<30}", name), Style::default().bold()), // Pad Name
-                Span::raw(" | "),
-                Span::styled(email, Style::default().italic()),
-            ]);
-            ListItem::new(line).style(model.appearance.default_style)
-        })
-        .collect();
+    items.extend(model.users.iter().map(|user| {
+        let name = user.name.clone().unwrap_or_default();
+        let email = user.email.clone().unwrap_or_default();
+        let id = user.id.clone().unwrap_or_default();
+        // Format the user info into a single line for the list
+        let line = Line::from(vec![
+            Span::styled(format!("{:<38}", id), Style::default()), // Pad ID
+            Span::raw(" | "),
+            Span::styled(format!("{:<30}", name), Style::default().bold()), // Pad Name
+            Span::raw(" | "),
+            Span::styled(email, Style::default().italic()),
+        ]);
+        ListItem::new(line).style(model.appearance.default_style)
+    }));
 
     // Create a List widget
     let list = List::new(items)
@@ -44,7 +67,7 @@ pub fn render_user_selection(model: &mut AppModel, area: Rect, frame: &mut Frame
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title(format!(" {} ", t!("ui_select_default_user")))
+                .title(format!(" {} ", title))
                 .title_alignment(Alignment::Center)
                 .title_bottom(shortcuts.as_line())
                 .style(model.appearance.default_style),
@@ -0,0 +1,193 @@
+use crate::moneybird::types::TimeEntry;
+use rust_i18n::t;
+
+// Older changes are dropped, the entries they refer to may be long gone
+const MAX_CHANGES: usize = 50;
+
+/// A change made to MoneyBird, with the entries needed to reverse or repeat it
+#[derive(Clone, Debug)]
+pub(crate) enum Change {
+    Created(TimeEntry),
+    Imported(TimeEntry),
+    Updated {
+        before: TimeEntry,
+        after: TimeEntry,
+    },
+    Deleted(TimeEntry),
+    // Two overlapping entries merged into the one that started first, the other was deleted
+    Merged {
+        kept_before: TimeEntry,
+        kept_after: TimeEntry,
+        removed: Box<TimeEntry>,
+    },
+}
+
+impl Change {
+    /// The entry the change is about, after it was made
+    pub(crate) fn entry(&self) -> &TimeEntry {
+        match self {
+            Change::Created(entry) | Change::Imported(entry) | Change::Deleted(entry) => entry,
+            Change::Updated { after, .. }
+            | Change::Merged {
+                kept_after: after, ..
+            } => after,
+        }
+    }
+
+    /// Translated description for the log and the messages
+    pub(crate) fn description(&self) -> String {
+        let description = self.entry().description.clone().unwrap_or_default();
+        match self {
+            Change::Created(_) => t!("undo_change_created", description = description),
+            Change::Imported(_) => t!("undo_change_imported", description = description),
+            Change::Updated { .. } => t!("undo_change_updated", description = description),
+            Change::Deleted(_) => t!("undo_change_deleted", description = description),
+            Change::Merged { .. } => t!("undo_change_merged", description = description),
+        }
+        .to_string()
+    }
+
+    /// Point the change to the id an entry got when it was created again
+    fn replace_id(&mut self, old_id: &str, new_id: &str) {
+        let entries = match self {
+            Change::Created(entry) | Change::Imported(entry) | Change::Deleted(entry) => {
+                vec![entry]
+            }
+            Change::Updated { before, after } => vec![before, after],
+            Change::Merged {
+                kept_before,
+                kept_after,
+                removed,
+            } => vec![kept_before, kept_after, removed.as_mut()],
+        };
+        for entry in entries {
+            if entry.id.as_deref() == Some(old_id) {
+                entry.id = Some(new_id.to_string());
+            }
+        }
+    }
+}
+
+/// The changes that can be undone and the undone changes that can be redone
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UndoStack {
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+}
+
+impl UndoStack {
+    /// Record a new change, which makes the undone changes impossible to redo
+    pub(crate) fn record(&mut self, change: Change) {
+        self.undo.push(change);
+        if self.undo.len() > MAX_CHANGES {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Take the last change to undo it
+    pub(crate) fn pop_undo(&mut self) -> Option<Change> {
+        self.undo.pop()
+    }
+
+    /// Take the last undone change to redo it
+    pub(crate) fn pop_redo(&mut self) -> Option<Change> {
+        self.redo.pop()
+    }
+
+    /// Store a change that was undone
+    pub(crate) fn push_redo(&mut self, change: Change) {
+        self.redo.push(change);
+    }
+
+    /// Store a change that was redone, unlike `record` this keeps the other undone changes
+    pub(crate) fn push_undo(&mut self, change: Change) {
+        self.undo.push(change);
+    }
+
+    /// Put a change back where it came from when undoing or redoing it failed
+    pub(crate) fn restore(&mut self, change: Change, was_undo: bool) {
+        if was_undo {
+            self.undo.push(change);
+        } else {
+            self.redo.push(change);
+        }
+    }
+
+    /// Creating a deleted entry again gives it a new id, which the other changes need
+    pub(crate) fn replace_id(&mut self, old_id: &str, new_id: &str) {
+        for change in self.undo.iter_mut().chain(self.redo.iter_mut()) {
+            change.replace_id(old_id, new_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> TimeEntry {
+        TimeEntry {
+            id: Some(id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_record_clears_redo() {
+        let mut stack = UndoStack::default();
+        stack.record(Change::Created(entry("1")));
+        stack.record(Change::Deleted(entry("2")));
+
+        let change = stack.pop_undo().unwrap();
+        stack.push_redo(change);
+        assert!(matches!(stack.pop_redo(), Some(Change::Deleted(_))));
+
+        stack.push_redo(Change::Deleted(entry("2")));
+        stack.record(Change::Created(entry("3")));
+        assert!(stack.pop_redo().is_none());
+        assert_eq!(stack.pop_undo().unwrap().entry().id.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_replace_id() {
+        let mut stack = UndoStack::default();
+        stack.record(Change::Updated {
+            before: entry("1"),
+            after: entry("1"),
+        });
+        stack.push_redo(Change::Deleted(entry("1")));
+        stack.replace_id("1", "9");
+
+        let Some(Change::Updated { before, after }) = stack.pop_undo() else {
+            panic!("expected an update");
+        };
+        assert_eq!(before.id.as_deref(), Some("9"));
+        assert_eq!(after.id.as_deref(), Some("9"));
+        assert_eq!(stack.pop_redo().unwrap().entry().id.as_deref(), Some("9"));
+
+        stack.record(Change::Merged {
+            kept_before: entry("1"),
+            kept_after: entry("1"),
+            removed: Box::new(entry("2")),
+        });
+        stack.replace_id("2", "8");
+        let Some(Change::Merged { removed, .. }) = stack.pop_undo() else {
+            panic!("expected a merge");
+        };
+        assert_eq!(removed.id.as_deref(), Some("8"));
+    }
+
+    #[test]
+    fn test_max_changes() {
+        let mut stack = UndoStack::default();
+        for idx in 0..MAX_CHANGES + 5 {
+            stack.record(Change::Created(entry(&idx.to_string())));
+        }
+        let mut count = 0;
+        while stack.pop_undo().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, MAX_CHANGES);
+    }
+}
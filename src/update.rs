@@ -1,20 +1,32 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use crossterm::event::KeyCode;
 use ratatui::style::Style;
 use rust_i18n::t;
 use std::fs;
+use std::time::Instant;
 use toml::Value;
 use tui_textarea::TextArea;
 
 use crate::{
+    analysis::{self, Issue},
     api,
-    api::{get_contacts_by_query, get_time_entries},
-    config, datetime,
+    api::get_contacts_by_query,
+    config::{self, RoundingApply, SortDirection},
+    datetime,
     event::Message,
-    file,
-    model::{AppModel, AutocompleteState, EditField, EditState, EditType, TimeEntryForTable},
+    file, fuzzy,
+    keybindings::KeyMode,
+    links,
+    lock::{self, WeekKey},
+    model::{
+        AppModel, AutocompleteState, BatchImportItem, BatchImportTarget, EditField, EditState,
+        EditType, GroupBy, LogSeverity, PluginProgress, TimeEntryForTable,
+    },
     moneybird::types::{Contact, Project, TimeEntry, User},
-    plugin::{PluginManager, PluginTimeEntry},
+    palette,
+    plugin::{PluginManager, PluginNotification, PluginTimeEntry, TimeEntryEvent},
     ui::{self},
+    undo::Change,
     RunningState,
 };
 
@@ -91,6 +103,11 @@ fn initialize_editor_or_autocomplete(edit_state: &mut EditState) {
     match edit_state.selected_field {
         crate::model::EditField::Description => {
             edit_state.editor.insert_str(&edit_state.description);
+            // Only suggest descriptions once the text changes
+            let autocomplete = &mut edit_state.description_autocomplete;
+            autocomplete.clear_input();
+            autocomplete.input = edit_state.description.clone();
+            autocomplete.last_searched_input = edit_state.description.clone();
         }
         crate::model::EditField::StartDate => {
             edit_state.editor.insert_str(&edit_state.start_date);
@@ -212,6 +229,93 @@ fn handle_autocomplete_select_contact(model: &mut AppModel) {
     }
 }
 
+// Helper function to keep the description suggestions' input in step with the editor
+fn sync_description_autocomplete(edit_state: &mut EditState) {
+    let autocomplete = &mut edit_state.description_autocomplete;
+    if autocomplete.input == edit_state.description {
+        return; // Cursor movement, nothing to search
+    }
+    autocomplete.input = edit_state.description.clone();
+    autocomplete.last_searched_input.clear(); // Mark as needing search
+    autocomplete.record_keypress();
+    if autocomplete.input.len() < autocomplete.min_chars_to_search {
+        autocomplete.update_items(vec![]);
+    }
+}
+
+// Helper function to handle replacing the description with a suggestion
+fn handle_autocomplete_select_description(model: &mut AppModel) {
+    let edit_state = &mut model.edit_state;
+
+    if let Some(suggestion) = edit_state.description_autocomplete.selected_item().cloned() {
+        edit_state.description = suggestion.clone();
+        edit_state.editor = TextArea::default();
+        edit_state.editor.insert_str(&suggestion);
+        let autocomplete_state = &mut edit_state.description_autocomplete;
+        autocomplete_state.input = suggestion;
+        autocomplete_state.mark_searched(); // Prevent re-search
+        autocomplete_state.is_dropdown_visible = false;
+        autocomplete_state.items.clear();
+    }
+}
+
+// Helper function to refresh description suggestions (plugin call)
+async fn handle_autocomplete_refresh_description(model: &mut AppModel) -> Option<Message> {
+    let query = model.edit_state.description_autocomplete.input.clone();
+    let min_chars = model
+        .edit_state
+        .description_autocomplete
+        .min_chars_to_search;
+
+    // If the query is not long enough, don't search
+    if query.len() < min_chars {
+        model
+            .edit_state
+            .description_autocomplete
+            .update_items(vec![]);
+        return None;
+    }
+
+    // Mark state as loading, set searched flag
+    model.edit_state.description_autocomplete.mark_searched();
+
+    let Some(plugin_manager) = &model.plugin_manager else {
+        model
+            .edit_state
+            .description_autocomplete
+            .update_items(vec![]);
+        return None;
+    };
+    let edit_state = &model.edit_state;
+    let (suggestions, errors) = plugin_manager
+        .suggest_descriptions(
+            &query,
+            &edit_state.start_date,
+            edit_state.project_id.as_deref(),
+            &edit_state.project_name,
+        )
+        .await;
+
+    for (_, error) in errors {
+        model.log_warning(error);
+    }
+    model.log_debug(format!(
+        "Description suggestions: {} results for '{}'",
+        suggestions.len(),
+        query
+    ));
+    let suggestions = suggestions
+        .into_iter()
+        .filter(|suggestion| *suggestion != query)
+        .collect();
+    model
+        .edit_state
+        .description_autocomplete
+        .update_items(suggestions);
+
+    None // Return None as we've already updated the state
+}
+
 // Helper function to refresh project autocomplete suggestions (local filter)
 async fn handle_autocomplete_refresh_project(model: &mut AppModel) -> Option<Message> {
     // Get the query and min chars once to avoid multiple borrows
@@ -228,19 +332,7 @@ async fn handle_autocomplete_refresh_project(model: &mut AppModel) -> Option<Mes
     // Mark state as loading, set searched flag
     model.edit_state.project_autocomplete.mark_searched();
 
-    // Create a filtered list of projects
-    let filtered_projects = model
-        .projects
-        .iter()
-        .filter(|project| {
-            if let Some(name) = &project.name {
-                name.to_lowercase().contains(&query.to_lowercase())
-            } else {
-                false
-            }
-        })
-        .cloned()
-        .collect::<Vec<_>>();
+    let filtered_projects = filter_projects(&model.projects, &query);
 
     // Update project autocomplete with filtered projects
     model
@@ -259,6 +351,46 @@ async fn handle_autocomplete_refresh_project(model: &mut AppModel) -> Option<Mes
     None // Return None as we've already updated the state
 }
 
+/// The projects whose name fuzzy matches the query, best matches first
+fn filter_projects(projects: &[Project], query: &str) -> Vec<Project> {
+    let mut scored_projects = projects
+        .iter()
+        .filter_map(|project| {
+            let name = project.name.as_ref()?;
+            fuzzy::fuzzy_match(query, name).map(|m| (project.clone(), m.score))
+        })
+        .collect::<Vec<_>>();
+    scored_projects.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    scored_projects
+        .into_iter()
+        .map(|(project, _)| project)
+        .collect()
+}
+
+/// Sort contacts on how well their name fuzzy matches the query, best matches first.
+/// Contacts that don't match at all are dropped unless `keep_unmatched` is set.
+fn sort_contacts_by_score(
+    contacts: Vec<Contact>,
+    query: &str,
+    keep_unmatched: bool,
+) -> Vec<Contact> {
+    let mut scored_contacts = contacts
+        .into_iter()
+        .filter_map(
+            |contact| match fuzzy::fuzzy_match(query, &ui::format_contact_name(&contact)) {
+                Some(m) => Some((contact, m.score)),
+                None if keep_unmatched => Some((contact, i64::MIN)),
+                None => None,
+            },
+        )
+        .collect::<Vec<_>>();
+    scored_contacts.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    scored_contacts
+        .into_iter()
+        .map(|(contact, _)| contact)
+        .collect()
+}
+
 // Helper function to refresh contact autocomplete suggestions (API call)
 async fn handle_autocomplete_refresh_contact(model: &mut AppModel) -> Option<Message> {
     // Get the query and min chars once to avoid multiple borrows
@@ -272,27 +404,40 @@ async fn handle_autocomplete_refresh_contact(model: &mut AppModel) -> Option<Mes
         return None;
     }
 
+    let contacts = search_contacts(model, &query).await;
+    model.edit_state.contact_autocomplete.update_items(contacts);
+
+    None // Return None as we've already updated the state
+}
+
+/// Search the contacts for the query, best matches first
+async fn search_contacts(model: &mut AppModel, query: &str) -> Vec<Contact> {
     // Log before making API call
     model.log_debug(format!("Searching contacts with query: '{}'", query));
 
     // Get admin ID properly
     let admin_id = model.administration.id.clone().unwrap_or_default();
     // Make the API call
-    let contacts_result = get_contacts_by_query(&model.client, &admin_id, &query).await;
+    let contacts_result = get_contacts_by_query(&model.client, &admin_id, query).await;
 
     match contacts_result {
         Ok(contacts) => {
             let count = contacts.len();
             model.log_debug(format!("Contact search returned {} results", count));
-            model.edit_state.contact_autocomplete.update_items(contacts);
+
+            // The API only finds exact substrings, so fall back to the known contacts for typos.
+            // API results may match on other fields than the name, so those are all kept.
+            if contacts.is_empty() {
+                sort_contacts_by_score(model.contacts.clone(), query, false)
+            } else {
+                sort_contacts_by_score(contacts, query, true)
+            }
         }
         Err(err) => {
-            model.log_error(format!("Contact search failed: {}", err));
-            model.edit_state.contact_autocomplete.update_items(vec![]);
+            model.log_error(t!("contact_search_failed", error = err.to_string()));
+            Vec::new()
         }
     }
-
-    None // Return None as we've already updated the state
 }
 
 // Helper function to handle exporting time entries to CSV
@@ -325,11 +470,11 @@ fn handle_export(model: &mut AppModel) {
     }
 }
 
-// Helper function to handle common logic for closing modals (confirm/dismiss)
+// Helper function to handle common logic for closing modals (confirm/dismiss/alternative)
 fn handle_modal_close(
     model: &mut AppModel,
     modal_id_from_message: String,
-    is_confirm: bool,
+    choice: impl Fn(&ui::ModalData) -> Option<Message>,
 ) -> Option<Message> {
     if model.modal_stack.is_empty() {
         return None;
@@ -344,11 +489,7 @@ fn handle_modal_close(
         if modal_id_from_message.is_empty() || modal_id_from_message == current_modal_id {
             // ID matches, determine potential next message and mark for popping
             should_pop = true;
-            next_message_candidate = if is_confirm {
-                modal.on_confirm.clone()
-            } else {
-                modal.on_cancel.clone()
-            };
+            next_message_candidate = choice(modal);
         }
     } // Immutable borrow of model.modal_stack ends here
 
@@ -382,64 +523,59 @@ fn handle_modal_close(
     }
 }
 
-/// Initialize an import from a plugin time entry to Moneybird
-async fn initialize_time_entry_import(model: &mut AppModel) -> Option<Message> {
-    // Get the currently selected entry from the time entries table
-    let selected_entry = if let Some(selected_index) = model.time_entry_table_state.selected() {
-        if selected_index < model.time_entries_for_table.len() {
-            model.time_entries_for_table[selected_index].clone()
-        } else {
-            return None;
-        }
-    } else {
-        return None;
-    };
-
-    // Check if this is a plugin entry (not from Moneybird)
-    if selected_entry.plugin_name.is_none() && selected_entry.source == "moneybird" {
-        // This is a Moneybird entry, can't import it
-        ui::show_error(model, t!("update_cant_import_moneybird_entry"));
-        return None;
-    }
-
-    // Store the original entry for reference
-    model.edit_state.original_entry = Some(selected_entry.clone());
-
-    // Initialize edit state with data from the selected entry
-    let mut edit_state = EditState {
-        description: selected_entry.description.clone(),
-        ..Default::default()
-    };
-
-    // Parse start_date and start_time from started_at
-    let admin_timezone = model
-        .administration
-        .time_zone
-        .clone()
-        .unwrap_or_else(|| "UTC".to_string());
-    if let (Some(start_date), Some(start_time)) =
-        crate::datetime::parse_datetime_for_edit(&selected_entry.started_at, &admin_timezone)
-    {
-        edit_state.start_date = start_date;
-        edit_state.start_time = start_time;
+/// Warn about mapping rules with patterns that never match
+fn warn_invalid_mappings(model: &mut AppModel) {
+    let invalid: Vec<String> = model
+        .config
+        .mappings
+        .iter()
+        .flat_map(|rule| rule.invalid_patterns())
+        .map(str::to_string)
+        .collect();
+    for pattern in invalid {
+        model.log_warning(t!("mapping_invalid_pattern", pattern = pattern));
     }
+}
 
-    // Parse end_date and end_time from ended_at
-    if let (Some(end_date), Some(end_time)) =
-        crate::datetime::parse_datetime_for_edit(&selected_entry.ended_at, &admin_timezone)
-    {
-        edit_state.end_date = end_date;
-        edit_state.end_time = end_time;
+/// Find the MoneyBird contact and project for a plugin entry. Mapping rules come first,
+/// otherwise a contact or project with the same name is looked up.
+async fn resolve_import_mapping(
+    model: &mut AppModel,
+    entry: &TimeEntryForTable,
+) -> (Option<Contact>, Option<Project>) {
+    // --- Mapping Rules ---
+    let plugin_name = entry.plugin_key().unwrap_or_default();
+    let mapping = model
+        .config
+        .find_mapping(plugin_name, &entry.customer, &entry.project);
+    if mapping.is_some() {
+        model.log_notice(t!(
+            "mapping_applied",
+            customer = entry.customer.clone(),
+            project = entry.project.clone()
+        ));
     }
 
     // --- Match Contact ---
-    let contact_name_from_plugin = selected_entry.customer.clone();
+    let contact_name_from_plugin = entry.customer.clone();
     model.log_debug(format!(
         "Contact name from plugin: {}",
         contact_name_from_plugin
     ));
-    let mut matched_contact: Option<Contact> = None;
-    if !contact_name_from_plugin.is_empty() {
+    // A rule without a name shows the name of the cached contact
+    let mut matched_contact: Option<Contact> = mapping.as_ref().and_then(|rule| {
+        let id = rule.contact_id.clone()?;
+        let cached = model.contacts.iter().find(|c| c.id.as_ref() == Some(&id));
+        Some(Contact {
+            company_name: rule
+                .contact_name
+                .clone()
+                .or_else(|| cached.and_then(|c| c.company_name.clone())),
+            id: Some(id),
+            ..Default::default()
+        })
+    });
+    if matched_contact.is_none() && !contact_name_from_plugin.is_empty() {
         // 1. Check local cache first
         for contact in &model.contacts {
             if let Some(company_name) = &contact.company_name {
@@ -512,9 +648,20 @@ async fn initialize_time_entry_import(model: &mut AppModel) -> Option<Message> {
     }
 
     // --- Match Project ---
-    let project_name_from_plugin = selected_entry.project.clone();
-    let mut matched_project: Option<Project> = None;
-    if !project_name_from_plugin.is_empty() {
+    let project_name_from_plugin = entry.project.clone();
+    let mut matched_project: Option<Project> = mapping.as_ref().and_then(|rule| {
+        let id = rule.project_id.clone()?;
+        let cached = model.projects.iter().find(|p| p.id.as_ref() == Some(&id));
+        Some(Project {
+            name: rule
+                .project_name
+                .clone()
+                .or_else(|| cached.and_then(|p| p.name.clone())),
+            id: Some(id),
+            ..Default::default()
+        })
+    });
+    if matched_project.is_none() && !project_name_from_plugin.is_empty() {
         for project in &model.projects {
             if let Some(name) = &project.name {
                 if name.to_lowercase() == project_name_from_plugin.to_lowercase() {
@@ -533,6 +680,88 @@ async fn initialize_time_entry_import(model: &mut AppModel) -> Option<Message> {
         ));
     }
 
+    (matched_contact, matched_project)
+}
+
+/// Fill an edit state with the description and times of a plugin entry
+fn plugin_entry_edit_state(entry: &TimeEntryForTable, timezone: &str) -> EditState {
+    let mut edit_state = EditState {
+        description: entry.description.clone(),
+        ..Default::default()
+    };
+
+    // Parse start_date and start_time from started_at
+    if let (Some(start_date), Some(start_time)) =
+        crate::datetime::parse_datetime_for_edit(&entry.started_at, timezone)
+    {
+        edit_state.start_date = start_date;
+        edit_state.start_time = start_time;
+    }
+
+    // Parse end_date and end_time from ended_at
+    if let (Some(end_date), Some(end_time)) =
+        crate::datetime::parse_datetime_for_edit(&entry.ended_at, timezone)
+    {
+        edit_state.end_date = end_date;
+        edit_state.end_time = end_time;
+    }
+    edit_state
+}
+
+/// Initialize an import from a plugin time entry to Moneybird. With the id of the MoneyBird
+/// entry it was imported as before, saving updates that entry instead.
+async fn initialize_time_entry_import(
+    model: &mut AppModel,
+    existing_id: Option<String>,
+) -> Option<Message> {
+    // Get the currently selected entry from the time entries table
+    if model.is_group_header_selected() {
+        return None;
+    }
+    let selected_entry = if let Some(selected_index) = model.time_entry_table_state.selected() {
+        if selected_index < model.time_entries_for_table.len() {
+            model.time_entries_for_table[selected_index].clone()
+        } else {
+            return None;
+        }
+    } else {
+        return None;
+    };
+
+    // Check if this is a plugin entry (not from Moneybird)
+    if selected_entry.plugin_name.is_none() && selected_entry.source == "moneybird" {
+        // This is a Moneybird entry, can't import it
+        ui::show_error(model, t!("update_cant_import_moneybird_entry"));
+        return None;
+    }
+
+    // The entry is imported for the configured user, into the week it started in
+    let key = model.week_key_for_entry(&TimeEntry {
+        started_at: Some(selected_entry.started_at.clone()),
+        user_id: model.config.user_id.clone(),
+        ..Default::default()
+    });
+    if refuse_if_week_locked(model, &key) {
+        return None;
+    }
+
+    // Store the original entry for reference
+    model.edit_state.original_entry = Some(selected_entry.clone());
+
+    // Initialize edit state with data from the selected entry
+    let admin_timezone = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    let mut edit_state = plugin_entry_edit_state(&selected_entry, &admin_timezone);
+
+    // --- Match Contact and Project ---
+    warn_invalid_mappings(model);
+    let (matched_contact, matched_project) = resolve_import_mapping(model, &selected_entry).await;
+    let contact_name_from_plugin = selected_entry.customer.clone();
+    let project_name_from_plugin = selected_entry.project.clone();
+
     // Set EditState fields based on matching results
     if let Some(contact) = matched_contact {
         edit_state.contact_id = contact.id;
@@ -550,6 +779,10 @@ async fn initialize_time_entry_import(model: &mut AppModel) -> Option<Message> {
         edit_state.project_name = project_name_from_plugin; // Show the original name if no match
     }
 
+    // Remember the prefilled choices, so corrections can be learned after the import
+    edit_state.prefilled_contact_id = edit_state.contact_id.clone();
+    edit_state.prefilled_project_id = edit_state.project_id.clone();
+
     // Log the import action
     model.log_notice(t!(
         "update_importing_time_entry",
@@ -560,6 +793,7 @@ async fn initialize_time_entry_import(model: &mut AppModel) -> Option<Message> {
     edit_state.edit_type = EditType::Import;
     edit_state.active = true;
     edit_state.original_entry = Some(selected_entry);
+    edit_state.time_entry_id = existing_id;
 
     // Set the default field to Description
     edit_state.selected_field = crate::model::EditField::Description;
@@ -579,87 +813,1445 @@ async fn initialize_time_entry_import(model: &mut AppModel) -> Option<Message> {
     Some(Message::None)
 }
 
-/// Check if the model's import state is active
-fn is_import_active(model: &AppModel) -> bool {
-    model.edit_state.active && model.edit_state.is_import_mode()
-}
+/// Open the batch import with all plugin entries of the week and the contact and project
+/// each of them would get. Entries that were imported before start unchecked.
+async fn show_batch_import(model: &mut AppModel) -> Option<Message> {
+    if model.edit_state.active || is_import_active(model) {
+        return None;
+    }
+    let key = model.week_key(model.config.user_id.clone());
+    if refuse_if_week_locked(model, &key) {
+        return None;
+    }
 
-// --- Helper function to get the active mutable EditState ---
-fn get_active_edit_state_mut(model: &mut AppModel) -> Option<&mut EditState> {
-    if model.edit_state.active {
-        Some(&mut model.edit_state)
-    } else {
-        None
+    let mut entries: Vec<TimeEntryForTable> = model
+        .time_entries_for_table_backup
+        .iter()
+        .filter(|entry| entry.plugin_name.is_some() || entry.source != "moneybird")
+        .cloned()
+        .collect();
+    if entries.is_empty() {
+        ui::show_info(
+            model,
+            "batch_import_empty",
+            t!("batch_import_title"),
+            t!("batch_import_empty"),
+        );
+        return None;
+    }
+    entries.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+
+    warn_invalid_mappings(model);
+    let mut items = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let (contact, project) = resolve_import_mapping(model, &entry).await;
+        items.push(BatchImportItem {
+            checked: entry.linked_id.is_none(),
+            entry,
+            contact,
+            project,
+            result: None,
+        });
     }
+
+    let state = &mut model.batch_import_state;
+    state.items = items;
+    state.table_state.select(Some(0));
+    state.editing = None;
+    state.active = true;
+    None
 }
 
-/// Formats a time input string. If it's a valid hour (0-23), formats as HH:00.
-/// Otherwise, returns the original string.
-fn format_time_input(time_str: &str) -> String {
-    // Trim whitespace for cleaner parsing
-    let trimmed_str = time_str.trim();
+/// Start picking the contact or project of the selected batch import entry
+fn edit_batch_import_item(model: &mut AppModel, target: BatchImportTarget) {
+    let state = &mut model.batch_import_state;
+    let Some(item) = state
+        .table_state
+        .selected()
+        .and_then(|index| state.items.get(index))
+    else {
+        return;
+    };
+    let name = match target {
+        BatchImportTarget::Contact => item.contact.as_ref().and_then(|c| c.company_name.clone()),
+        BatchImportTarget::Project => item.project.as_ref().and_then(|p| p.name.clone()),
+    }
+    .unwrap_or_else(|| match target {
+        BatchImportTarget::Contact => item.entry.customer.clone(),
+        BatchImportTarget::Project => item.entry.project.clone(),
+    });
 
-    // Try parsing as just an hour
-    match trimmed_str.parse::<u8>() {
-        Ok(hour) if hour < 24 => {
-            // Valid hour, format as HH:00
-            format!("{:02}:00", hour)
+    // The candidates are searched for the name, the contacts once the input settles
+    match target {
+        BatchImportTarget::Contact => {
+            state.contact_autocomplete.clear_input();
+            state.contact_autocomplete.input = name;
+            state.contact_autocomplete.record_keypress();
         }
-        _ => {
-            // Not a simple hour or parsing failed, return original string
-            // We assume it might already be HH:MM or invalid
-            time_str.to_string()
+        BatchImportTarget::Project => {
+            state.project_autocomplete.clear_input();
+            state.project_autocomplete.input = name;
+            state.project_autocomplete.record_keypress();
         }
     }
+    state.editing = Some(target);
 }
 
-/// Process a message and update the model state
-pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message> {
-    match msg {
-        Message::Quit => {
-            model.running_state = RunningState::Done;
-            None
-        }
-
-        // --- Time Entry Navigation/Refresh ---
-        Message::TimeEntryPreviousWeek => {
-            model.week_offset -= 1;
-            model.log_notice(format!(
-                "Navigating to previous week (offset: {})",
-                model.week_offset
-            ));
-            Some(Message::TimeEntryRefresh)
+/// Search the contacts or filter the projects for the batch import input
+async fn refresh_batch_import_candidates(model: &mut AppModel) {
+    match model.batch_import_state.editing {
+        Some(BatchImportTarget::Contact) => {
+            let autocomplete = &mut model.batch_import_state.contact_autocomplete;
+            let query = autocomplete.input.clone();
+            autocomplete.mark_searched();
+            let contacts = if query.len() < autocomplete.min_chars_to_search {
+                Vec::new()
+            } else {
+                search_contacts(model, &query).await
+            };
+            model
+                .batch_import_state
+                .contact_autocomplete
+                .update_items(contacts);
         }
-        Message::TimeEntryNextWeek => {
-            model.week_offset += 1;
-            model.log_notice(format!(
-                "Navigating to next week (offset: {})",
-                model.week_offset
-            ));
-            Some(Message::TimeEntryRefresh)
+        Some(BatchImportTarget::Project) => {
+            let autocomplete = &mut model.batch_import_state.project_autocomplete;
+            autocomplete.mark_searched();
+            let projects = if autocomplete.input.len() < autocomplete.min_chars_to_search {
+                Vec::new()
+            } else {
+                filter_projects(&model.projects, &autocomplete.input)
+            };
+            autocomplete.update_items(projects);
         }
-        Message::TimeEntryCurrentWeek => {
-            model.week_offset = 0;
-            model.log_notice(t!("update_log_navigating_current_week").to_string());
-            Some(Message::TimeEntryRefresh)
+        None => {}
+    }
+}
+
+/// Select the next or previous contact or project for the batch import entry
+fn navigate_batch_import_candidates(model: &mut AppModel, next: bool) {
+    let state = &mut model.batch_import_state;
+    match state.editing {
+        Some(BatchImportTarget::Contact) => {
+            handle_autocomplete_navigation(&mut state.contact_autocomplete, next)
         }
-        Message::TimeEntryRefresh => {
-            model.log_notice(t!("update_log_manual_refresh").to_string());
-            get_time_entries(model).await;
-            model.log_success(t!("update_time_entries_refreshed").to_string());
-            None
+        Some(BatchImportTarget::Project) => {
+            handle_autocomplete_navigation(&mut state.project_autocomplete, next)
         }
-        Message::TimeEntrySelectNext => {
-            let count = model.time_entries_for_table.len();
-            if let Some(next_index) =
-                calculate_next_index(model.time_entry_table_state.selected(), count)
-            {
-                model.time_entry_table_state.select(Some(next_index));
+        None => {}
+    }
+}
+
+/// Use the selected candidate for the batch import entry. It applies to the other entries
+/// with the same plugin customer or project, and is offered as a mapping rule once the
+/// import is done.
+fn apply_batch_import_candidate(model: &mut AppModel) {
+    let state = &mut model.batch_import_state;
+    let (Some(target), Some(index)) = (state.editing, state.table_state.selected()) else {
+        return;
+    };
+    let Some(entry) = state.items.get(index).map(|item| item.entry.clone()) else {
+        return;
+    };
+
+    let plugin = entry.plugin_key().unwrap_or_default().to_string();
+    let same_source = |other: &TimeEntryForTable| other.plugin_key() == Some(plugin.as_str());
+    let (plugin_name, name, rule) = match target {
+        BatchImportTarget::Contact => {
+            let Some(selected) = state.contact_autocomplete.selected_item() else {
+                return;
+            };
+            // The table and the import show the company name, people only have a name
+            let name = ui::format_contact_name(selected);
+            let contact = Contact {
+                company_name: Some(name.clone()),
+                ..selected.clone()
+            };
+            for (other_index, item) in state.items.iter_mut().enumerate() {
+                let same_customer = !entry.customer.is_empty()
+                    && same_source(&item.entry)
+                    && item.entry.customer == entry.customer;
+                if other_index == index || (same_customer && item.result.is_none()) {
+                    item.contact = Some(contact.clone());
+                }
             }
-            None
+            let rule = config::MappingRule {
+                contact_id: contact.id.clone(),
+                contact_name: Some(name.clone()),
+                ..config::MappingRule::exact(&plugin, &entry.customer, "")
+            };
+            (entry.customer.clone(), name, rule)
         }
-        Message::TimeEntrySelectPrevious => {
-            let count = model.time_entries_for_table.len();
+        BatchImportTarget::Project => {
+            let Some(project) = state.project_autocomplete.selected_item().cloned() else {
+                return;
+            };
+            let name = project.name.clone().unwrap_or_default();
+            for (other_index, item) in state.items.iter_mut().enumerate() {
+                let same_project = !entry.project.is_empty()
+                    && same_source(&item.entry)
+                    && item.entry.project == entry.project;
+                if other_index == index || (same_project && item.result.is_none()) {
+                    item.project = Some(project.clone());
+                }
+            }
+            let rule = config::MappingRule {
+                project_id: project.id.clone(),
+                project_name: Some(name.clone()),
+                ..config::MappingRule::exact(&plugin, "", &entry.project)
+            };
+            (entry.project.clone(), name, rule)
+        }
+    };
+    state.editing = None;
+
+    // Without a plugin name there is nothing to recognize the next time
+    if !plugin_name.is_empty() {
+        state
+            .learned
+            .retain(|(_, learned)| !learned.has_same_patterns(&rule));
+        state
+            .learned
+            .push((format!("{} → {}", plugin_name, name), rule));
+    }
+}
+
+/// Offer the rules learned during the batch import, all at once
+fn offer_batch_import_mappings(model: &mut AppModel) {
+    let learned = std::mem::take(&mut model.batch_import_state.learned);
+    if learned.is_empty() {
+        return;
+    }
+    let (lines, rules): (Vec<String>, Vec<config::MappingRule>) = learned.into_iter().unzip();
+    ui::show_confirmation(
+        model,
+        t!("mapping_learn_title"),
+        t!("batch_import_learn_prompt", rules = lines.join("\n")),
+        Some(Message::ExecuteLearnMappings(rules)),
+        None,
+    );
+}
+
+/// Merge the selected batch import entry with the adjacent checked fragments of the same
+/// plugin, client and project. The merged entry keeps the contact and project of the
+/// selected one.
+fn merge_batch_import_fragments(model: &mut AppModel) {
+    let max_gap_minutes = model.config.merge.max_gap_minutes;
+    let state = &mut model.batch_import_state;
+    let Some(selected) = state.table_state.selected() else {
+        return;
+    };
+
+    // Imported entries stay as they are, unchecked ones are left out of the merge
+    let eligible: Vec<usize> = (0..state.items.len())
+        .filter(|idx| {
+            let item = &state.items[*idx];
+            !matches!(item.result, Some(Ok(_))) && (item.checked || *idx == selected)
+        })
+        .collect();
+    let entries: Vec<TimeEntryForTable> = eligible
+        .iter()
+        .map(|idx| state.items[*idx].entry.clone())
+        .collect();
+    let indices: Vec<usize> = eligible
+        .iter()
+        .position(|idx| *idx == selected)
+        .map(|pos| analysis::adjacent_fragments(&entries, pos, max_gap_minutes))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|pos| eligible[pos])
+        .collect();
+    let fragments: Vec<&TimeEntryForTable> =
+        indices.iter().map(|idx| &state.items[*idx].entry).collect();
+    let Some(entry) = analysis::merge_fragments(&fragments, &model.config.merge.separator) else {
+        show_nothing_to_merge(model);
+        return;
+    };
+
+    let merged = BatchImportItem {
+        entry,
+        checked: true,
+        result: None,
+        ..state.items[selected].clone()
+    };
+    let position = analysis::replace_fragments(&mut state.items, &indices, merged);
+    state.table_state.select(Some(position));
+    model.log_success(t!("merge_done", count = indices.len()));
+}
+
+fn show_nothing_to_merge(model: &mut AppModel) {
+    let message = t!(
+        "merge_nothing_adjacent",
+        minutes = model.config.merge.max_gap_minutes
+    );
+    ui::show_info(model, "merge_nothing", t!("merge_title"), message);
+}
+
+/// Ask before creating the checked batch import entries
+fn confirm_batch_import(model: &mut AppModel) {
+    let count = model
+        .batch_import_state
+        .items
+        .iter()
+        .filter(|item| item.checked)
+        .count();
+    if count == 0 {
+        model.log_warning(t!("batch_import_nothing_checked"));
+        return;
+    }
+    ui::show_confirmation(
+        model,
+        t!("batch_import_title"),
+        t!("batch_import_confirm", count = count),
+        Some(Message::ExecuteBatchImport),
+        None,
+    );
+}
+
+/// Build the MoneyBird entry for a batch import entry, or tell why it can't be imported.
+/// It is validated like the edit form, against the given MoneyBird entries.
+fn batch_import_time_entry(
+    model: &AppModel,
+    item: &BatchImportItem,
+    entries: &[TimeEntryForTable],
+    timezone: &str,
+) -> Result<TimeEntry, String> {
+    let mut edit_state = plugin_entry_edit_state(&item.entry, timezone);
+    if let Some(contact) = &item.contact {
+        edit_state.contact_id = contact.id.clone();
+        edit_state.contact_name = contact.company_name.clone().unwrap_or_default();
+    }
+    if let Some(project) = &item.project {
+        edit_state.project_id = project.id.clone();
+        edit_state.project_name = project.name.clone().unwrap_or_default();
+    }
+
+    let mut errors = edit_state.validate(
+        &model.config.validation,
+        entries,
+        model.config.user_id.as_deref(),
+        timezone,
+    );
+    if let Some(error) = EDIT_FIELD_ORDER
+        .iter()
+        .find_map(|field| errors.remove(field))
+    {
+        return Err(error);
+    }
+
+    let rounding = &model.config.rounding;
+    let rule = (rounding.apply == RoundingApply::Save)
+        .then(|| rounding.rule_for(edit_state.contact_id.as_deref(), &edit_state.contact_name));
+    edit_state.try_into_time_entry(timezone, rule)
+}
+
+/// Create the checked batch import entries one by one and report how each of them went
+async fn execute_batch_import(model: &mut AppModel) -> Option<Message> {
+    let admin_timezone = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    let user_id = model.config.user_id.clone().unwrap_or_default();
+    let client = model.client.clone();
+
+    let (mut imported, mut attempted) = (0, 0);
+    let mut imported_ids = Vec::new();
+    // Entries imported earlier in the batch are checked for overlaps too
+    let mut entries = model.time_entries_for_table_backup.clone();
+    for index in 0..model.batch_import_state.items.len() {
+        let item = model.batch_import_state.items[index].clone();
+        if !item.checked {
+            continue;
+        }
+        attempted += 1;
+
+        let result = match batch_import_time_entry(model, &item, &entries, &admin_timezone) {
+            Ok(time_entry) => api::create_time_entry(&client, &admin_id, &user_id, time_entry)
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err),
+        };
+        let item = &mut model.batch_import_state.items[index];
+        match result {
+            Ok(created_entry) => {
+                let moneybird_id = created_entry.id.clone().unwrap_or_default();
+                let plugin = item.entry.plugin_key().unwrap_or_default();
+                for entry_id in item.entry.plugin_entry_ids() {
+                    model.import_links.insert(plugin, entry_id, &moneybird_id);
+                }
+                item.checked = false;
+                item.entry.linked_id = Some(moneybird_id.clone());
+                item.result = Some(Ok(moneybird_id.clone()));
+                entries.push(TimeEntryForTable {
+                    id: moneybird_id.clone(),
+                    source: "moneybird".to_string(),
+                    user_id: created_entry.user_id.clone(),
+                    started_at: created_entry.started_at.clone().unwrap_or_default(),
+                    ended_at: created_entry.ended_at.clone().unwrap_or_default(),
+                    ..Default::default()
+                });
+                imported_ids.push(moneybird_id);
+                model.undo_stack.record(Change::Imported(created_entry));
+                imported += 1;
+            }
+            Err(err) => {
+                let description = item.entry.description.clone();
+                item.result = Some(Err(err.clone()));
+                model.log_error(t!(
+                    "batch_import_entry_failed",
+                    description = description,
+                    error = err
+                ));
+            }
+        }
+    }
+    if imported > 0 {
+        save_import_links(model);
+    }
+    for moneybird_id in imported_ids {
+        notify_plugins(model, TimeEntryEvent::Imported, &moneybird_id);
+    }
+
+    // The summary is shown first, the learned rules are offered after it
+    offer_batch_import_mappings(model);
+    let summary = t!(
+        "batch_import_summary",
+        imported = imported,
+        total = attempted
+    );
+    if imported == attempted {
+        model.log_success(summary.clone());
+        ui::show_info(
+            model,
+            "batch_import_done",
+            t!("batch_import_title"),
+            summary,
+        );
+    } else {
+        model.log_warning(summary.clone());
+        ui::show_warning(
+            model,
+            "batch_import_done",
+            t!("batch_import_title"),
+            summary,
+        );
+    }
+    None
+}
+
+/// Fetch a time entry before changing it, so the change can be undone
+async fn fetch_original_entry(model: &mut AppModel, entry_id: &str) -> Option<TimeEntry> {
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    match api::get_time_entry_by_id(&model.client, &admin_id, entry_id).await {
+        Ok(entry) => Some(entry),
+        Err(err) => {
+            model.log_warning(t!("undo_original_unavailable", error = err.to_string()));
+            None
+        }
+    }
+}
+
+/// Create an entry again, for an undone delete or a redone create. The entry gets a
+/// new id, which the other changes on the undo stack need to know about.
+async fn recreate_entry(model: &mut AppModel, entry: TimeEntry) -> color_eyre::Result<TimeEntry> {
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    let user_id = entry
+        .user_id
+        .clone()
+        .or_else(|| model.config.user_id.clone())
+        .unwrap_or_default();
+    let old_id = entry.id.clone().unwrap_or_default();
+    let created = api::create_time_entry(&model.client, &admin_id, &user_id, entry).await?;
+    if let Some(new_id) = &created.id {
+        model.undo_stack.replace_id(&old_id, new_id);
+        for (plugin, entry_id) in model.deleted_links.remove_moneybird_id(&old_id) {
+            model.import_links.insert(&plugin, &entry_id, new_id);
+        }
+        save_import_links(model);
+        notify_plugins(model, TimeEntryEvent::Imported, new_id);
+    }
+    Ok(created)
+}
+
+/// Offer to remember the contact and project that were picked by hand during an import,
+/// so the next import of the same customer and project gets them right away
+fn offer_learned_mapping(model: &mut AppModel) {
+    let edit_state = &model.edit_state;
+    let Some(original) = &edit_state.original_entry else {
+        return;
+    };
+    let corrected = edit_state.contact_id != edit_state.prefilled_contact_id
+        || edit_state.project_id != edit_state.prefilled_project_id;
+    let has_names = !original.customer.is_empty() || !original.project.is_empty();
+    if !corrected || !has_names {
+        return;
+    }
+
+    let Some(plugin) = original.plugin_key() else {
+        return;
+    };
+    let rule = config::MappingRule {
+        contact_id: edit_state.contact_id.clone(),
+        contact_name: edit_state
+            .contact_id
+            .as_ref()
+            .map(|_| edit_state.contact_name.clone()),
+        project_id: edit_state.project_id.clone(),
+        project_name: edit_state
+            .project_id
+            .as_ref()
+            .map(|_| edit_state.project_name.clone()),
+        ..config::MappingRule::exact(plugin, &original.customer, &original.project)
+    };
+    let message = t!(
+        "mapping_learn_prompt",
+        customer = original.customer.clone(),
+        project = original.project.clone(),
+        contact = edit_state.contact_name.clone(),
+        moneybird_project = edit_state.project_name.clone()
+    );
+    ui::show_confirmation(
+        model,
+        t!("mapping_learn_title"),
+        message,
+        Some(Message::ExecuteLearnMappings(vec![rule])),
+        None,
+    );
+}
+
+/// Remember which MoneyBird entry the plugin entry being imported became
+fn link_imported_entry(model: &mut AppModel, moneybird_id: Option<&str>) {
+    let (Some(moneybird_id), Some(original)) = (moneybird_id, &model.edit_state.original_entry)
+    else {
+        return;
+    };
+    let Some(plugin) = original.plugin_key() else {
+        return;
+    };
+    for entry_id in original.plugin_entry_ids() {
+        model.import_links.insert(plugin, entry_id, moneybird_id);
+    }
+    save_import_links(model);
+}
+
+/// Put the links of a deleted entry aside, so undoing the delete links the new entry again
+fn unlink_deleted_entry(model: &mut AppModel, moneybird_id: &str) {
+    let removed = model.import_links.remove_moneybird_id(moneybird_id);
+    if removed.is_empty() {
+        return;
+    }
+    for (plugin, entry_id) in removed {
+        model.deleted_links.insert(&plugin, &entry_id, moneybird_id);
+    }
+    save_import_links(model);
+}
+
+fn save_import_links(model: &mut AppModel) {
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    if let Err(err) = links::write_links(&admin_id, &model.import_links) {
+        model.log_error(err.to_string());
+    }
+}
+
+/// Tell the plugins whose entries were imported as a MoneyBird entry what happened to it.
+/// The plugins answer in the background, a plugin failing to handle it doesn't undo the
+/// change, so it's only logged.
+fn notify_plugins(model: &mut AppModel, event: TimeEntryEvent, moneybird_id: &str) {
+    let linked = model.import_links.entries_for(moneybird_id);
+    let Some(plugin_manager) = &model.plugin_manager else {
+        return;
+    };
+
+    let mut errors = Vec::new();
+    for (plugin, entry_id) in linked {
+        if let Err(err) = plugin_manager.notify_time_entry(&plugin, event, moneybird_id, &entry_id)
+        {
+            errors.push(t!(
+                "plugin_notify_error",
+                name = plugin,
+                error = err.to_string()
+            ));
+        }
+    }
+    for error in errors {
+        model.log_warning(error);
+    }
+}
+
+/// Show what plugins reported on their own: logs in the log panel, progress in the table
+/// title and notifications in a modal
+fn handle_plugin_notifications(model: &mut AppModel, notifications: Vec<PluginNotification>) {
+    for notification in notifications {
+        match notification {
+            PluginNotification::Log {
+                plugin,
+                severity,
+                message,
+            } => model.log(format!("[{}] {}", plugin, message), Some(severity)),
+            PluginNotification::Progress {
+                plugin,
+                message,
+                current,
+                total,
+                done,
+            } => {
+                let finished = done || current.zip(total).is_some_and(|(c, t)| c >= t);
+                if finished {
+                    model.plugin_progress.remove(&plugin);
+                } else {
+                    model.plugin_progress.insert(
+                        plugin,
+                        PluginProgress {
+                            message,
+                            current,
+                            total,
+                        },
+                    );
+                }
+            }
+            PluginNotification::Notify {
+                plugin,
+                severity,
+                title,
+                message,
+            } => {
+                model.log(format!("[{}] {}", plugin, message), Some(severity));
+                show_plugin_notification(model, severity, title.unwrap_or(plugin), message);
+            }
+        }
+    }
+}
+
+/// Show a notification of a plugin. While an earlier one is still open the message is
+/// added to that modal instead, so a plugin sending many doesn't stack up modals. Only
+/// the latest messages are kept there, all of them are in the log.
+fn show_plugin_notification(
+    model: &mut AppModel,
+    severity: LogSeverity,
+    title: String,
+    message: String,
+) {
+    const MODAL_ID: &str = "plugin_notify";
+    const MAX_LINES: usize = 8;
+    let modal_type = match severity {
+        LogSeverity::Error => ui::ModalType::Error,
+        LogSeverity::Warning => ui::ModalType::Warning,
+        _ => ui::ModalType::Info,
+    };
+    let rank = |modal_type: ui::ModalType| match modal_type {
+        ui::ModalType::Error => 2,
+        ui::ModalType::Warning => 1,
+        _ => 0,
+    };
+
+    let Some(open) = model
+        .modal_stack
+        .top_mut()
+        .filter(|modal| modal.id.as_deref() == Some(MODAL_ID))
+    else {
+        ui::show_notification(model, MODAL_ID, modal_type, title, message);
+        return;
+    };
+
+    let merged_title = t!("plugin_notify_merged_title").to_string();
+    if open.title != merged_title {
+        open.message = format!("{}: {}", open.title, open.message);
+        open.title = merged_title;
+    }
+    open.message = format!("{}\n{}: {}", open.message, title, message);
+    let lines: Vec<&str> = open.message.lines().collect();
+    if lines.len() > MAX_LINES {
+        open.message = lines[lines.len() - MAX_LINES..].join("\n");
+    }
+    if rank(modal_type) > rank(open.modal_type) {
+        open.modal_type = modal_type;
+    }
+}
+
+/// Undo or redo a change through the API. Returns the change with the entries MoneyBird
+/// returned, to store on the other stack.
+async fn apply_change(
+    model: &mut AppModel,
+    change: Change,
+    undo: bool,
+) -> color_eyre::Result<Change> {
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    let client = model.client.clone();
+    match (change, undo) {
+        // Undoing a create and redoing a delete both delete the entry
+        (change @ (Change::Created(_) | Change::Imported(_)), true)
+        | (change @ Change::Deleted(_), false) => {
+            let entry_id = change.entry().id.clone().unwrap_or_default();
+            api::delete_time_entry_by_id(&client, &admin_id, &entry_id).await?;
+            notify_plugins(model, TimeEntryEvent::Deleted, &entry_id);
+            unlink_deleted_entry(model, &entry_id);
+            Ok(change)
+        }
+        (Change::Created(entry), false) => Ok(Change::Created(recreate_entry(model, entry).await?)),
+        (Change::Imported(entry), false) => {
+            Ok(Change::Imported(recreate_entry(model, entry).await?))
+        }
+        (Change::Deleted(entry), true) => Ok(Change::Deleted(recreate_entry(model, entry).await?)),
+        (Change::Updated { before, after }, true) => {
+            let entry_id = before.id.clone().unwrap_or_default();
+            let before =
+                api::update_time_entry_by_id(&client, &admin_id, &entry_id, before).await?;
+            notify_plugins(model, TimeEntryEvent::Updated, &entry_id);
+            Ok(Change::Updated { before, after })
+        }
+        (Change::Updated { before, after }, false) => {
+            let entry_id = after.id.clone().unwrap_or_default();
+            let after = api::update_time_entry_by_id(&client, &admin_id, &entry_id, after).await?;
+            notify_plugins(model, TimeEntryEvent::Updated, &entry_id);
+            Ok(Change::Updated { before, after })
+        }
+        (
+            Change::Merged {
+                kept_before,
+                kept_after,
+                removed,
+            },
+            true,
+        ) => {
+            let entry_id = kept_before.id.clone().unwrap_or_default();
+            let kept_before =
+                api::update_time_entry_by_id(&client, &admin_id, &entry_id, kept_before).await?;
+            notify_plugins(model, TimeEntryEvent::Updated, &entry_id);
+            let removed = recreate_entry(model, *removed).await?;
+            Ok(Change::Merged {
+                kept_before,
+                kept_after,
+                removed: Box::new(removed),
+            })
+        }
+        (
+            Change::Merged {
+                kept_before,
+                kept_after,
+                removed,
+            },
+            false,
+        ) => {
+            let kept_after = save_merged_entry(model, kept_after, &removed).await?;
+            let kept_id = kept_after.id.clone().unwrap_or_default();
+            let removed_id = removed.id.clone().unwrap_or_default();
+            notify_plugins(model, TimeEntryEvent::Updated, &kept_id);
+            notify_plugins(model, TimeEntryEvent::Deleted, &removed_id);
+            unlink_deleted_entry(model, &removed_id);
+            Ok(Change::Merged {
+                kept_before,
+                kept_after,
+                removed,
+            })
+        }
+    }
+}
+
+/// Undo the last change or redo the last undone change
+async fn undo_or_redo(model: &mut AppModel, undo: bool) -> Option<Message> {
+    let change = if undo {
+        model.undo_stack.pop_undo()
+    } else {
+        model.undo_stack.pop_redo()
+    };
+    let Some(change) = change else {
+        let message = if undo {
+            t!("undo_nothing_to_undo")
+        } else {
+            t!("undo_nothing_to_redo")
+        };
+        model.log_notice(message.clone());
+        ui::show_info(model, "undo_empty", t!("undo_title"), message);
+        return None;
+    };
+
+    // Submitted weeks stay as they are, also for the entry an update moved away from
+    let mut keys = vec![model.week_key_for_entry(change.entry())];
+    match &change {
+        Change::Updated { before, .. } => keys.push(model.week_key_for_entry(before)),
+        Change::Merged {
+            kept_before,
+            removed,
+            ..
+        } => {
+            keys.push(model.week_key_for_entry(kept_before));
+            keys.push(model.week_key_for_entry(removed));
+        }
+        _ => {}
+    }
+    for key in &keys {
+        if refuse_if_week_locked(model, key) {
+            model.undo_stack.restore(change, undo);
+            return None;
+        }
+    }
+
+    let description = change.description();
+    match apply_change(model, change.clone(), undo).await {
+        Ok(applied) => {
+            let message = if undo {
+                model.undo_stack.push_redo(applied);
+                t!("undo_undone", change = description)
+            } else {
+                model.undo_stack.push_undo(applied);
+                t!("undo_redone", change = description)
+            };
+            model.log_success(message.clone());
+            ui::show_info(model, "undo_success", t!("undo_title"), message);
+            Some(Message::TimeEntryRefresh)
+        }
+        Err(err) => {
+            model.undo_stack.restore(change, undo);
+            let error_msg =
+                t!("undo_failed", change = description, error = err.to_string()).to_string();
+            model.log_error(error_msg.clone());
+            ui::show_error(model, error_msg);
+            None
+        }
+    }
+}
+
+/// Offer to trim or merge the first entry the selected entry overlaps
+fn show_fix_conflict(model: &mut AppModel) {
+    if model.is_group_header_selected() {
+        return;
+    }
+    let Some(selected) = model
+        .time_entry_table_state
+        .selected()
+        .and_then(|idx| model.time_entries_for_table.get(idx))
+        .cloned()
+    else {
+        return;
+    };
+
+    let issues = model.analysis.issues(&selected.id).to_vec();
+    let overlap = issues.iter().find_map(|issue| match issue {
+        Issue::Overlap(other_id) => model.find_loaded_entry(other_id).cloned(),
+        _ => None,
+    });
+    let Some(other) = overlap else {
+        let message = if issues
+            .iter()
+            .any(|issue| matches!(issue, Issue::Duplicate(_)))
+        {
+            t!("analysis_fix_duplicate")
+        } else {
+            t!("analysis_fix_nothing")
+        };
+        ui::show_info(model, "fix_conflict", t!("analysis_fix_title"), message);
+        return;
+    };
+    let (Some(first), Some(second)) = (
+        analysis::entry_interval(&selected),
+        analysis::entry_interval(&other),
+    ) else {
+        return;
+    };
+
+    let admin_timezone_str = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    let describe = |entry: &TimeEntryForTable| {
+        format!(
+            "{} {}",
+            datetime::format_time_range_from_time_entry(entry.clone(), &admin_timezone_str),
+            entry.description.lines().next().unwrap_or_default()
+        )
+    };
+    let mut message = t!(
+        "analysis_fix_prompt",
+        first = describe(&selected),
+        second = describe(&other)
+    )
+    .to_string();
+    let merge = Message::ExecuteMergeOverlap(selected.id.clone(), other.id.clone());
+
+    match analysis::trim_overlap(first, second) {
+        Some((first_changes, (start, end))) => {
+            let changed = if first_changes { &selected } else { &other };
+            message.push_str(&format!(
+                "\n\n{}",
+                t!(
+                    "analysis_fix_trim_hint",
+                    description = changed.description.lines().next().unwrap_or_default(),
+                    start = datetime::format_time(&start.to_rfc3339(), &admin_timezone_str),
+                    end = datetime::format_time(&end.to_rfc3339(), &admin_timezone_str)
+                )
+            ));
+            ui::show_choice(
+                model,
+                t!("analysis_fix_title"),
+                message,
+                (
+                    t!("analysis_fix_trim"),
+                    Message::ExecuteTrimOverlap(selected.id, other.id),
+                ),
+                (t!("analysis_fix_merge"), merge),
+            );
+        }
+        // Starting at the same time, only merging keeps both
+        None => ui::show_confirmation(
+            model,
+            t!("analysis_fix_title"),
+            format!("{}\n\n{}", message, t!("analysis_fix_merge_only")),
+            Some(merge),
+            None,
+        ),
+    }
+}
+
+/// Fetch both overlapping entries, refusing when one of them is in a submitted week
+async fn fetch_overlapping_entries(
+    model: &mut AppModel,
+    first_id: &str,
+    second_id: &str,
+) -> Option<(TimeEntry, TimeEntry)> {
+    let first = fetch_original_entry(model, first_id).await?;
+    let second = fetch_original_entry(model, second_id).await?;
+    for entry in [&first, &second] {
+        let key = model.week_key_for_entry(entry);
+        if refuse_if_week_locked(model, &key) {
+            return None;
+        }
+    }
+    Some((first, second))
+}
+
+fn time_entry_interval(entry: &TimeEntry) -> Option<analysis::Interval> {
+    let start = chrono::DateTime::parse_from_rfc3339(entry.started_at.as_deref()?).ok()?;
+    let end = chrono::DateTime::parse_from_rfc3339(entry.ended_at.as_deref()?).ok()?;
+    Some((start, end))
+}
+
+/// Shorten one of two overlapping entries so they follow each other
+async fn trim_overlap(model: &mut AppModel, first_id: &str, second_id: &str) -> Option<Message> {
+    let (first, second) = fetch_overlapping_entries(model, first_id, second_id).await?;
+    let trimmed = time_entry_interval(&first)
+        .zip(time_entry_interval(&second))
+        .and_then(|(first_span, second_span)| analysis::trim_overlap(first_span, second_span));
+    let Some((first_changes, (start, end))) = trimmed else {
+        model.log_notice(t!("analysis_fix_nothing"));
+        return Some(Message::TimeEntryRefresh);
+    };
+
+    let before = if first_changes { first } else { second };
+    let mut after = before.clone();
+    after.started_at = Some(start.to_rfc3339());
+    after.ended_at = Some(end.to_rfc3339());
+
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    let entry_id = before.id.clone().unwrap_or_default();
+    match api::update_time_entry_by_id(&model.client, &admin_id, &entry_id, after).await {
+        Ok(updated) => {
+            notify_plugins(model, TimeEntryEvent::Updated, &entry_id);
+            model.undo_stack.record(Change::Updated {
+                before,
+                after: updated,
+            });
+            model.log_success(t!("analysis_trimmed"));
+            Some(Message::TimeEntryRefresh)
+        }
+        Err(err) => {
+            let error_msg = t!("analysis_fix_failed", error = err.to_string()).to_string();
+            model.log_error(error_msg.clone());
+            ui::show_error(model, error_msg);
+            None
+        }
+    }
+}
+
+/// Update the merged entry, then delete the entry it absorbed
+async fn save_merged_entry(
+    model: &AppModel,
+    merged: TimeEntry,
+    removed: &TimeEntry,
+) -> color_eyre::Result<TimeEntry> {
+    let admin_id = model.administration.id.clone().unwrap_or_default();
+    let merged_id = merged.id.clone().unwrap_or_default();
+    let removed_id = removed.id.clone().unwrap_or_default();
+    let updated =
+        api::update_time_entry_by_id(&model.client, &admin_id, &merged_id, merged).await?;
+    api::delete_time_entry_by_id(&model.client, &admin_id, &removed_id).await?;
+    Ok(updated)
+}
+
+/// Merge two overlapping entries into the one that starts first and delete the other
+async fn merge_overlap(model: &mut AppModel, first_id: &str, second_id: &str) -> Option<Message> {
+    let (first, second) = fetch_overlapping_entries(model, first_id, second_id).await?;
+    let (Some(first_span), Some(second_span)) =
+        (time_entry_interval(&first), time_entry_interval(&second))
+    else {
+        return None;
+    };
+    let (kept, removed) = if first_span.0 <= second_span.0 {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    let (start, end) = analysis::merge_intervals(first_span, second_span);
+    let mut merged = kept.clone();
+    merged.started_at = Some(start.to_rfc3339());
+    merged.ended_at = Some(end.to_rfc3339());
+    let kept_description = kept.description.clone().unwrap_or_default();
+    let removed_description = removed.description.clone().unwrap_or_default();
+    if !removed_description.is_empty() && removed_description != kept_description {
+        merged.description = Some(
+            [kept_description, removed_description]
+                .iter()
+                .filter(|description| !description.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+
+    match save_merged_entry(model, merged, &removed).await {
+        Ok(updated) => {
+            let kept_id = kept.id.clone().unwrap_or_default();
+            let removed_id = removed.id.clone().unwrap_or_default();
+            notify_plugins(model, TimeEntryEvent::Updated, &kept_id);
+            notify_plugins(model, TimeEntryEvent::Deleted, &removed_id);
+            unlink_deleted_entry(model, &removed_id);
+            model.undo_stack.record(Change::Merged {
+                kept_before: kept,
+                kept_after: updated,
+                removed: Box::new(removed),
+            });
+            model.log_success(t!("analysis_merged"));
+            Some(Message::TimeEntryRefresh)
+        }
+        Err(err) => {
+            let error_msg = t!("analysis_fix_failed", error = err.to_string()).to_string();
+            model.log_error(error_msg.clone());
+            ui::show_error(model, error_msg);
+            // The update may have gone through before the delete failed
+            Some(Message::TimeEntryRefresh)
+        }
+    }
+}
+
+/// Open the command palette for the current mode with some input already typed
+fn show_command_palette(model: &mut AppModel, input: &str) {
+    let state = &mut model.command_palette_state;
+    state.active = true;
+    state.mode = if model.search_state.active {
+        KeyMode::Search
+    } else {
+        KeyMode::Normal
+    };
+    state.input = TextArea::default();
+    state.input.insert_str(input);
+    state.error = None;
+    state.list_state.select(Some(0));
+}
+
+/// Find the full MoneyBird time entry behind the selected table row
+fn get_selected_moneybird_entry(model: &AppModel) -> Option<TimeEntry> {
+    if model.is_group_header_selected() {
+        return None;
+    }
+    let selected_index = model.time_entry_table_state.selected()?;
+    let selected_entry = model.time_entries_for_table.get(selected_index)?;
+    model
+        .time_entries
+        .iter()
+        .find(|e| e.id.clone().unwrap_or_default() == selected_entry.id)
+        .cloned()
+}
+
+/// Write the export snapshot of a week and lock it
+fn submit_week(model: &AppModel, key: &WeekKey) -> Result<lock::WeekLock, String> {
+    let locks = model
+        .locks
+        .as_ref()
+        .ok_or_else(|| t!("config_determine_dir_error").to_string())?;
+    locks.prepare_lock_dir(key).map_err(|e| e.to_string())?;
+
+    let entries: Vec<TimeEntryForTable> = model
+        .time_entries_for_table_backup
+        .iter()
+        .filter(|e| e.source == "moneybird")
+        .filter(|e| e.user_id.as_deref() == Some(key.user_id.as_str()))
+        .cloned()
+        .collect();
+    let snapshot = locks.snapshot_path(key);
+    file::export_entries_to_csv(model, &entries, &snapshot.to_string_lossy())?;
+
+    locks
+        .write_lock(key, chrono::Utc::now().to_rfc3339())
+        .map_err(|e| e.to_string())
+}
+
+/// Show a warning and return true when the week of `key` has been submitted
+fn refuse_if_week_locked(model: &mut AppModel, key: &WeekKey) -> bool {
+    let Some(week_lock) = model.read_week_lock(key) else {
+        return false;
+    };
+
+    model.log_warning(t!(
+        "week_locked_message",
+        week = week_lock.week,
+        year = week_lock.year,
+        submitted_at = week_lock.submitted_at.clone()
+    ));
+    ui::show_warning(
+        model,
+        "week_locked",
+        t!("week_locked_title"),
+        t!(
+            "week_locked_message",
+            week = week_lock.week,
+            year = week_lock.year,
+            submitted_at = week_lock.submitted_at
+        ),
+    );
+    true
+}
+
+/// Save the configuration, showing an error modal when that fails
+fn save_config_or_show_error(model: &mut AppModel) -> bool {
+    match config::save_configuration(&model.config) {
+        Ok(_) => {
+            model.log_success(t!("update_config_saved_success").to_string());
+            true
+        }
+        Err(e) => {
+            let error_msg = t!("update_failed_save_config", error = e.to_string()).to_string();
+            model.log_error(error_msg.clone());
+            ui::show_error(model, error_msg);
+            false
+        }
+    }
+}
+
+/// Re-sort the table after the sort changed and remember it in the configuration
+fn apply_table_sort(model: &mut AppModel) {
+    model.sort_time_entries();
+    model.log_notice(t!(
+        "update_sorted_by",
+        column = ui::get_sort_column_label(model.config.sort.column)
+    ));
+    save_config_or_show_error(model);
+}
+
+/// Check if the model's import state is active
+fn is_import_active(model: &AppModel) -> bool {
+    model.edit_state.active && model.edit_state.is_import_mode()
+}
+
+// --- Helper function to get the active mutable EditState ---
+fn get_active_edit_state_mut(model: &mut AppModel) -> Option<&mut EditState> {
+    if model.edit_state.active {
+        Some(&mut model.edit_state)
+    } else {
+        None
+    }
+}
+
+/// Order of the edit form fields for tabbing
+const EDIT_FIELD_ORDER: &[EditField] = &[
+    EditField::Description,
+    EditField::Contact,
+    EditField::Project,
+    EditField::StartTime,
+    EditField::EndTime,
+    EditField::StartDate,
+    EditField::EndDate,
+];
+
+/// Sync the editor into the field focus is leaving and expand what was typed there
+fn leave_edit_field(edit_state: &mut EditState, now: NaiveDateTime) {
+    update_edit_field_from_editor(edit_state);
+    let field = edit_state.selected_field;
+    normalize_edit_field(edit_state, field, now);
+    edit_state.touched_fields.insert(field);
+}
+
+/// Expand what was typed in a date or time field, like `930`, `now`, `yesterday` or a
+/// duration in the end time field. Invalid input is kept, with an error shown at the field.
+fn normalize_edit_field(edit_state: &mut EditState, field: EditField, now: NaiveDateTime) {
+    let result = match field {
+        EditField::StartTime => datetime::parse_time_input(&edit_state.start_time, now.time())
+            .map(|time| edit_state.start_time = time.format("%H:%M").to_string())
+            .ok_or_else(|| t!("edit_invalid_time").to_string()),
+        EditField::EndTime => normalize_end_time(edit_state, now),
+        EditField::StartDate => datetime::parse_date_input(&edit_state.start_date, now.date())
+            .map(|date| edit_state.start_date = date.format("%Y-%m-%d").to_string())
+            .ok_or_else(|| t!("edit_invalid_date").to_string()),
+        EditField::EndDate => datetime::parse_date_input(&edit_state.end_date, now.date())
+            .map(|date| edit_state.end_date = date.format("%Y-%m-%d").to_string())
+            .ok_or_else(|| t!("edit_invalid_date").to_string()),
+        _ => return,
+    };
+    match result {
+        Ok(()) => {
+            edit_state.input_errors.remove(&field);
+        }
+        Err(err) => {
+            edit_state.input_errors.insert(field, err);
+        }
+    }
+}
+
+/// The end time is a time, or a duration that sets the end date and time from the start
+fn normalize_end_time(edit_state: &mut EditState, now: NaiveDateTime) -> Result<(), String> {
+    if let Some(time) = datetime::parse_time_input(&edit_state.end_time, now.time()) {
+        edit_state.end_time = time.format("%H:%M").to_string();
+        return Ok(());
+    }
+    let minutes = datetime::parse_duration_minutes(&edit_state.end_time)
+        .filter(|minutes| *minutes > 0)
+        .ok_or_else(|| t!("edit_invalid_end_time").to_string())?;
+    let end = edit_state
+        .start_datetime()
+        .ok_or_else(|| t!("edit_duration_needs_start").to_string())?
+        .checked_add_signed(chrono::Duration::minutes(minutes as i64))
+        .ok_or_else(|| t!("edit_invalid_end_time").to_string())?;
+    edit_state.end_time = end.format("%H:%M").to_string();
+    edit_state.end_date = end.format("%Y-%m-%d").to_string();
+    edit_state.input_errors.remove(&EditField::EndDate);
+    Ok(())
+}
+
+/// Move the selected time field by a number of 15 minute steps, moving its date along
+/// when it passes midnight. An empty field starts from the current time.
+fn step_edit_time(edit_state: &mut EditState, steps: i32, now: NaiveDateTime) {
+    let (date, time) = match edit_state.selected_field {
+        EditField::StartTime => (&mut edit_state.start_date, &mut edit_state.start_time),
+        EditField::EndTime => (&mut edit_state.end_date, &mut edit_state.end_time),
+        _ => return,
+    };
+    let current = if time.trim().is_empty() {
+        now.time()
+    } else {
+        match NaiveTime::parse_from_str(time, "%H:%M") {
+            Ok(current) => current,
+            Err(_) => return, // The error at the field says what's wrong
+        }
+    };
+    let parsed_date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let stepped =
+        datetime::step_quarter_hour(parsed_date.unwrap_or(now.date()).and_time(current), steps);
+    *time = stepped.format("%H:%M").to_string();
+    if parsed_date.is_some() {
+        *date = stepped.format("%Y-%m-%d").to_string();
+    }
+    let field = edit_state.selected_field;
+    edit_state.input_errors.remove(&field);
+}
+
+/// Current date and time in the administration's timezone, for relative input
+fn admin_now(model: &AppModel) -> NaiveDateTime {
+    let admin_timezone_str = model
+        .administration
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    datetime::get_now(&admin_timezone_str)
+}
+
+/// Process a message and update the model state
+pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message> {
+    match msg {
+        Message::Quit => {
+            model.running_state = RunningState::Done;
+            None
+        }
+
+        // --- Time Entry Navigation/Refresh ---
+        Message::TimeEntryPreviousWeek => {
+            model.week_offset -= 1;
+            model.log_notice(format!(
+                "Navigating to previous week (offset: {})",
+                model.week_offset
+            ));
+            Some(Message::TimeEntryRefresh)
+        }
+        Message::TimeEntryNextWeek => {
+            model.week_offset += 1;
+            model.log_notice(format!(
+                "Navigating to next week (offset: {})",
+                model.week_offset
+            ));
+            Some(Message::TimeEntryRefresh)
+        }
+        Message::TimeEntryCurrentWeek => {
+            model.week_offset = 0;
+            model.log_notice(t!("update_log_navigating_current_week").to_string());
+            Some(Message::TimeEntryRefresh)
+        }
+        Message::TimeEntryGoToWeek(week_offset) => {
+            model.week_offset = week_offset;
+            model.log_notice(t!(
+                "update_log_navigating_to_week",
+                week = datetime::get_title_week_description(week_offset)
+            ));
+            Some(Message::TimeEntryRefresh)
+        }
+        Message::TimeEntryRefresh => {
+            model.log_notice(t!("update_log_manual_refresh").to_string());
+            api::refresh_time_entries(model).await;
+            model.refresh_week_lock();
+            model.log_success(t!("update_time_entries_refreshed").to_string());
+            None
+        }
+        Message::PluginEntriesLoaded => {
+            let fetch = model.plugin_fetch.take()?;
+            // Progress sent during the fetch is over now
+            if let Some(plugin_manager) = model.plugin_manager.as_mut() {
+                let notifications = plugin_manager.take_notifications();
+                handle_plugin_notifications(model, notifications);
+            }
+            model.plugin_progress.clear();
+
+            match fetch.await {
+                Ok((entries, errors)) => api::add_plugin_time_entries(model, entries, errors),
+                Err(err) => {
+                    let message = t!("plugin_fetch_failed", error = err.to_string());
+                    model.log_error(message.clone());
+                    ui::show_error(model, message);
+                }
+            }
+            None
+        }
+        Message::TimeEntrySubmitWeek => {
+            let key = model.week_key(None);
+            if key.user_id.is_empty() {
+                ui::show_error(model, t!("update_week_no_user"));
+                return None;
+            }
+
+            if model
+                .locks
+                .as_ref()
+                .is_some_and(|locks| locks.is_week_locked(&key))
+            {
+                ui::show_confirmation(
+                    model,
+                    t!("unlock_week_title").to_string(),
+                    t!("unlock_week_prompt", week = key.week, year = key.year).to_string(),
+                    Some(Message::ExecuteUnlockWeek),
+                    None,
+                );
+            } else {
+                let count = model
+                    .time_entries_for_table_backup
+                    .iter()
+                    .filter(|e| e.source == "moneybird")
+                    .filter(|e| e.user_id.as_deref() == Some(key.user_id.as_str()))
+                    .count();
+                ui::show_confirmation(
+                    model,
+                    t!("submit_week_title").to_string(),
+                    t!(
+                        "submit_week_prompt",
+                        week = key.week,
+                        year = key.year,
+                        count = count
+                    )
+                    .to_string(),
+                    Some(Message::ExecuteSubmitWeek),
+                    None,
+                );
+            }
+            None
+        }
+        Message::TimeEntryFilterCycleState => {
+            model.time_entry_filter.state = model.time_entry_filter.state.next();
+            model.log_notice(t!(
+                "update_filter_state",
+                state = model.time_entry_filter.state.label()
+            ));
+            Some(Message::TimeEntryRefresh)
+        }
+        Message::TimeEntryFilterToggleContact => {
+            if model.time_entry_filter.contact_id.is_some() {
+                model.time_entry_filter.contact_id = None;
+                model.log_notice(t!("update_filter_contact_cleared"));
+                return Some(Message::TimeEntryRefresh);
+            }
+
+            let contact_id = get_selected_moneybird_entry(model).and_then(|entry| {
+                entry
+                    .contact_id
+                    .or_else(|| entry.contact.and_then(|contact| contact.id))
+            });
+            match contact_id {
+                Some(contact_id) => {
+                    let contact_name = ui::get_contact_name(&model.contacts, Some(&contact_id));
+                    model.time_entry_filter.contact_id = Some(contact_id);
+                    model.log_notice(t!("update_filter_contact_set", contact = contact_name));
+                    Some(Message::TimeEntryRefresh)
+                }
+                None => {
+                    model.log_warning(t!("update_filter_needs_moneybird_entry"));
+                    None
+                }
+            }
+        }
+        Message::TimeEntryFilterToggleProject => {
+            if model.time_entry_filter.project_id.is_some() {
+                model.time_entry_filter.project_id = None;
+                model.log_notice(t!("update_filter_project_cleared"));
+                return Some(Message::TimeEntryRefresh);
+            }
+
+            let project_id = get_selected_moneybird_entry(model).and_then(|entry| {
+                entry
+                    .project_id
+                    .or_else(|| entry.project.and_then(|project| project.id))
+            });
+            match project_id {
+                Some(project_id) => {
+                    let project_name = ui::get_project_name(&model.projects, Some(&project_id));
+                    model.time_entry_filter.project_id = Some(project_id);
+                    model.log_notice(t!("update_filter_project_set", project = project_name));
+                    Some(Message::TimeEntryRefresh)
+                }
+                None => {
+                    model.log_warning(t!("update_filter_needs_moneybird_entry"));
+                    None
+                }
+            }
+        }
+        Message::TimeEntrySelectNext => {
+            if model.group_state.group_by != GroupBy::None {
+                // Step through the visible rows, including the group headers
+                let rows = model.table_rows();
+                if let Some(next_row) =
+                    calculate_next_index(model.selected_table_row(&rows), rows.len())
+                {
+                    model.select_table_row(&rows, next_row);
+                }
+                return None;
+            }
+
+            let count = model.time_entries_for_table.len();
+            if let Some(next_index) =
+                calculate_next_index(model.time_entry_table_state.selected(), count)
+            {
+                model.time_entry_table_state.select(Some(next_index));
+            }
+            None
+        }
+        Message::TimeEntrySelectPrevious => {
+            if model.group_state.group_by != GroupBy::None {
+                let rows = model.table_rows();
+                if let Some(prev_row) =
+                    calculate_previous_index(model.selected_table_row(&rows), rows.len())
+                {
+                    model.select_table_row(&rows, prev_row);
+                }
+                return None;
+            }
+
+            let count = model.time_entries_for_table.len();
             if let Some(prev_index) =
                 calculate_previous_index(model.time_entry_table_state.selected(), count)
             {
@@ -667,44 +2259,358 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             }
             None
         }
-        Message::TimeEntrySelectRow(index) => {
-            let count = model.time_entries_for_table.len();
-            if let Some(valid_index) = validate_row_index(index, count) {
-                model.time_entry_table_state.select(Some(valid_index));
-            } else {
-                model.log_warning(format!(
-                    "TimeEntrySelectRow: Index {} out of bounds ({})",
-                    index, count
-                ));
+        Message::TimeEntrySelectRow(index) => {
+            // Grouped tables are clicked by visible row
+            if model.group_state.group_by != GroupBy::None {
+                let rows = model.table_rows();
+                if let Some(valid_row) = validate_row_index(index, rows.len()) {
+                    model.select_table_row(&rows, valid_row);
+                }
+                return None;
+            }
+
+            let count = model.time_entries_for_table.len();
+            if let Some(valid_index) = validate_row_index(index, count) {
+                model.time_entry_table_state.select(Some(valid_index));
+            } else {
+                model.log_warning(format!(
+                    "TimeEntrySelectRow: Index {} out of bounds ({})",
+                    index, count
+                ));
+            }
+            None
+        }
+        Message::TimeEntrySearchShow => {
+            model.search_state.active = true;
+            model.search_state.text_input = TextArea::default();
+            model.search_state.error = None;
+            None
+        }
+        Message::TimeEntrySearchHide => {
+            model.search_state.active = false;
+            model.time_entries_for_table = model.time_entries_for_table_backup.clone();
+            model.ensure_valid_selection();
+            None
+        }
+        Message::TimeEntryClearSearch => {
+            if model.search_state.active {
+                model.search_state.text_input = TextArea::default();
+                model.filter_items();
+            }
+            None
+        }
+        Message::TimeEntrySearchKeyPress(key) => {
+            if model.search_state.active {
+                model.search_state.text_input.input(key);
+                model.filter_items();
+            }
+            None
+        }
+        Message::TimeEntrySortBy(column) => {
+            // Clicking the sorted column again flips the direction
+            if model.config.sort.column == column {
+                model.config.sort.direction = model.config.sort.direction.toggle();
+            } else {
+                model.config.sort.column = column;
+                model.config.sort.direction = SortDirection::Ascending;
+            }
+            apply_table_sort(model);
+            None
+        }
+        Message::TimeEntrySortNextColumn => {
+            model.config.sort.column = model.config.sort.column.next();
+            apply_table_sort(model);
+            None
+        }
+        Message::TimeEntrySortToggleDirection => {
+            model.config.sort.direction = model.config.sort.direction.toggle();
+            apply_table_sort(model);
+            None
+        }
+        Message::TimeEntryGroupCycle => {
+            let group_by = model.group_state.group_by.next();
+            model.set_group_by(group_by);
+            model.log_notice(t!("update_grouped_by", group = group_by.label()));
+            None
+        }
+        Message::TimeEntryGroupToggle => {
+            if model.group_state.group_by == GroupBy::None {
+                model.log_notice(t!("update_group_not_grouped"));
+                return None;
+            }
+            if let Some((label, collapsed)) = model.toggle_selected_group() {
+                if collapsed {
+                    model.log_debug(t!("update_group_collapsed", group = label));
+                } else {
+                    model.log_debug(t!("update_group_expanded", group = label));
+                }
+            }
+            None
+        }
+        Message::TimeEntryGroupToggleAll => {
+            if model.group_state.group_by == GroupBy::None {
+                model.log_notice(t!("update_group_not_grouped"));
+                return None;
+            }
+            if model.toggle_all_groups() {
+                model.log_debug(t!("update_groups_collapsed"));
+            } else {
+                model.log_debug(t!("update_groups_expanded"));
+            }
+            None
+        }
+        Message::SavedSearchNameShow => {
+            let query = model
+                .search_state
+                .text_input
+                .lines()
+                .first()
+                .cloned()
+                .unwrap_or_default();
+            if query.trim().is_empty() {
+                ui::show_error(model, t!("saved_search_empty_query"));
+                return None;
+            }
+            model.saved_search_state.name_input = Some(TextArea::default());
+            None
+        }
+        Message::SavedSearchNameKeyPress(key) => {
+            if let Some(name_input) = model.saved_search_state.name_input.as_mut() {
+                name_input.input(key);
+            }
+            None
+        }
+        Message::SavedSearchNameCancel => {
+            model.saved_search_state.name_input = None;
+            None
+        }
+        Message::SavedSearchSave => {
+            let name = model
+                .saved_search_state
+                .name_input
+                .as_ref()
+                .and_then(|input| input.lines().first().cloned())
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            if name.is_empty() {
+                return None; // Keep the prompt open until a name is entered
+            }
+
+            let query = model
+                .search_state
+                .text_input
+                .lines()
+                .first()
+                .cloned()
+                .unwrap_or_default();
+            model.saved_search_state.name_input = None;
+            model.config.upsert_saved_search(&name, query.trim());
+            if save_config_or_show_error(model) {
+                model.log_success(t!("saved_search_saved", name = name, query = query));
+            }
+            None
+        }
+        Message::CommandPaletteShow => {
+            show_command_palette(model, "");
+            None
+        }
+        Message::TimeEntryGoToShow => {
+            // The go to prompt is the palette with the goto command already typed
+            show_command_palette(model, "goto ");
+            None
+        }
+        Message::CommandPaletteHide => {
+            model.command_palette_state.active = false;
+            None
+        }
+        Message::CommandPaletteKeyPress(key) => {
+            model.command_palette_state.input.input(key);
+            model.command_palette_state.error = None;
+            // The best match is selected while typing
+            let selected = (!model.palette_matches().is_empty()).then_some(0);
+            model.command_palette_state.list_state.select(selected);
+            None
+        }
+        Message::CommandPaletteSelectNext => {
+            let count = model.palette_matches().len();
+            if let Some(next_index) =
+                calculate_next_index(model.command_palette_state.list_state.selected(), count)
+            {
+                model
+                    .command_palette_state
+                    .list_state
+                    .select(Some(next_index));
+            }
+            None
+        }
+        Message::CommandPaletteSelectPrevious => {
+            let count = model.palette_matches().len();
+            if let Some(previous_index) =
+                calculate_previous_index(model.command_palette_state.list_state.selected(), count)
+            {
+                model
+                    .command_palette_state
+                    .list_state
+                    .select(Some(previous_index));
+            }
+            None
+        }
+        Message::CommandPaletteComplete => {
+            let selected = model.command_palette_state.list_state.selected();
+            if let Some(found) = selected.and_then(|idx| model.palette_matches().get(idx).cloned())
+            {
+                // Keep a typed argument, only the name gets completed
+                let input = model.palette_input();
+                let (_, argument) = palette::split_input(&input);
+                let mut completed = TextArea::default();
+                completed.insert_str(format!("{} {}", found.command.name(), argument));
+                model.command_palette_state.input = completed;
+                model.command_palette_state.error = None;
+                model.command_palette_state.list_state.select(Some(0));
+            }
+            None
+        }
+        Message::CommandPaletteExecute => {
+            let input = model.palette_input();
+            let (name, argument) = palette::split_input(&input);
+            let matches = model.palette_matches();
+            let selected = model.command_palette_state.list_state.selected();
+            // An exact name wins over the selection, so `week 42` works without looking
+            let command = matches
+                .iter()
+                .find(|found| found.command.name() == name)
+                .or_else(|| selected.and_then(|idx| matches.get(idx)))
+                .map(|found| found.command);
+
+            let Some(command) = command else {
+                model.command_palette_state.error =
+                    Some(t!("palette_unknown_command", command = name).to_string());
+                return None;
+            };
+            if command.requires_argument() && argument.is_empty() {
+                // Complete the name so only the argument is left to type
+                return Some(Message::CommandPaletteComplete);
+            }
+
+            let admin_timezone_str = model
+                .administration
+                .time_zone
+                .clone()
+                .unwrap_or_else(|| "UTC".to_string());
+            match palette::command_message(
+                command,
+                argument,
+                model.command_palette_state.mode,
+                &admin_timezone_str,
+                &model.config.week_starts_on,
+            ) {
+                Ok(message) => {
+                    model.command_palette_state.active = false;
+                    model.log_debug(t!("palette_running", command = input.trim()));
+                    Some(message)
+                }
+                Err(err) => {
+                    model.command_palette_state.error = Some(err);
+                    None
+                }
+            }
+        }
+        Message::SavedSearchPickerShow => {
+            if model.config.saved_searches.is_empty() {
+                ui::show_info(
+                    model,
+                    "no_saved_searches",
+                    t!("ui_saved_searches").to_string(),
+                    t!("saved_search_none").to_string(),
+                );
+                return None;
+            }
+            model.saved_search_state.picker_active = true;
+            model.saved_search_state.list_state.select(Some(0));
+            None
+        }
+        Message::SavedSearchPickerHide => {
+            model.saved_search_state.picker_active = false;
+            None
+        }
+        Message::SavedSearchSelectNext => {
+            let count = model.config.saved_searches.len();
+            if let Some(next_index) =
+                calculate_next_index(model.saved_search_state.list_state.selected(), count)
+            {
+                model.saved_search_state.list_state.select(Some(next_index));
             }
             None
         }
-        Message::TimeEntrySearchShow => {
-            model.search_state.active = true;
-            model.search_state.text_input = TextArea::default();
+        Message::SavedSearchSelectPrevious => {
+            let count = model.config.saved_searches.len();
+            if let Some(previous_index) =
+                calculate_previous_index(model.saved_search_state.list_state.selected(), count)
+            {
+                model
+                    .saved_search_state
+                    .list_state
+                    .select(Some(previous_index));
+            }
             None
         }
-        Message::TimeEntrySearchHide => {
-            model.search_state.active = false;
-            model.time_entries_for_table = model.time_entries_for_table_backup.clone();
-            model.ensure_valid_selection();
+        Message::SavedSearchApply(index) => {
+            let Some(saved_search) = model.config.saved_searches.get(index).cloned() else {
+                model.log_warning(t!("saved_search_not_found", number = index + 1));
+                return None;
+            };
+            model.saved_search_state.picker_active = false;
+            model.apply_search(&saved_search.query);
+            model.log_notice(t!(
+                "saved_search_applied",
+                name = saved_search.name,
+                query = saved_search.query
+            ));
             None
         }
-        Message::TimeEntryClearSearch => {
-            if model.search_state.active {
-                model.search_state.text_input = TextArea::default();
-                model.filter_items();
+        Message::SavedSearchTogglePin => {
+            let name = model
+                .saved_search_state
+                .list_state
+                .selected()
+                .and_then(|idx| model.config.saved_searches.get(idx))
+                .map(|search| search.name.clone())?;
+
+            if model.config.default_search.as_deref() == Some(name.as_str()) {
+                model.config.default_search = None;
+                model.log_notice(t!("saved_search_unpinned", name = name));
+            } else {
+                model.config.default_search = Some(name.clone());
+                model.log_notice(t!("saved_search_pinned", name = name));
             }
+            save_config_or_show_error(model);
             None
         }
-        Message::TimeEntrySearchKeyPress(key) => {
-            if model.search_state.active {
-                model.search_state.text_input.input(key);
-                model.filter_items();
+        Message::SavedSearchDelete => {
+            if let Some(name) = model
+                .saved_search_state
+                .list_state
+                .selected()
+                .and_then(|idx| model.config.saved_searches.get(idx))
+                .map(|search| search.name.clone())
+            {
+                ui::show_confirmation(
+                    model,
+                    t!("delete_saved_search").to_string(),
+                    t!("confirm_delete_saved_search", name = name.clone()).to_string(),
+                    Some(Message::ExecuteDeleteSavedSearch(name)),
+                    None,
+                );
             }
             None
         }
         Message::TimeEntryCreate => {
+            // New entries start now, so the current week must be open
+            let key = model.week_key_at(0, model.config.user_id.clone());
+            if refuse_if_week_locked(model, &key) {
+                return None;
+            }
             model.log_notice(t!("update_log_initiating_create").to_string());
             let mut edit_state = EditState {
                 edit_type: EditType::Create,
@@ -747,12 +2653,21 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             None
         }
         Message::TimeEntryDelete => {
+            // A group header hides its entries, so there is nothing to delete
+            if model.is_group_header_selected() {
+                return None;
+            }
             if let Some(selected_idx) = model.time_entry_table_state.selected() {
                 if selected_idx < model.time_entries_for_table.len() {
                     let entry_description = model.time_entries_for_table[selected_idx]
                         .description
                         .clone();
                     let entry_id = model.time_entries_for_table[selected_idx].id.clone();
+                    let key =
+                        model.week_key(model.time_entries_for_table[selected_idx].user_id.clone());
+                    if refuse_if_week_locked(model, &key) {
+                        return None;
+                    }
 
                     ui::show_confirmation(
                         model,
@@ -771,8 +2686,17 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
         }
 
         Message::EditTimeEntry => {
+            // Editing a group header expands or collapses it
+            if model.is_group_header_selected() {
+                return Some(Message::TimeEntryGroupToggle);
+            }
             if let Some(selected_idx) = model.time_entry_table_state.selected() {
                 if selected_idx < model.time_entries_for_table.len() {
+                    let key =
+                        model.week_key(model.time_entries_for_table[selected_idx].user_id.clone());
+                    if refuse_if_week_locked(model, &key) {
+                        return None;
+                    }
                     let selected_entry = &model.time_entries_for_table[selected_idx];
                     let original_entry = model
                         .time_entries
@@ -875,46 +2799,94 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                 Option<String>,
                 String,
             )> = None;
-            let mut validation_failed = false;
+            let mut validation_error: Option<String> = None;
+            let now = datetime::get_now(&admin_timezone_str);
+
+            // Expand what was typed in the focused field and check the whole entry
+            match get_active_edit_state_mut(model) {
+                Some(edit_state) => {
+                    leave_edit_field(edit_state, now);
+                    edit_state.save_attempted = true;
+                }
+                // No active edit state, nothing to save
+                None => return None,
+            }
+            model.validate_edit();
 
             // --- Scope for mutable borrow of edit_state ---
             {
-                if let Some(edit_state) = get_active_edit_state_mut(model) {
-                    update_edit_field_from_editor(edit_state);
-
-                    // Check description validity first
-                    if edit_state.description.is_empty() {
-                        validation_failed = true;
-                    } else {
-                        // If valid, prepare the data within this scope
-                        let data = edit_state.try_into_time_entry(&admin_timezone_str); // Use pre-fetched timezone
-                        prepared_data = Some((
-                            data,
-                            edit_state.edit_type == EditType::Create,
-                            edit_state.time_entry_id.clone(),
-                            edit_state.description.clone(),
-                        ));
+                let edit_state = &mut model.edit_state;
+                let error_count = edit_state.error_count();
+                if error_count > 0 {
+                    // The errors are shown under their fields, focus the first one
+                    if let Some(field) = EDIT_FIELD_ORDER
+                        .iter()
+                        .find(|field| edit_state.field_error(**field).is_some())
+                    {
+                        edit_state.selected_field = *field;
+                        initialize_editor_or_autocomplete(edit_state);
                     }
+                    validation_error = Some(t!("edit_fix_errors", count = error_count).to_string());
                 } else {
-                    // No active edit state, nothing to save
-                    return None;
+                    let rounding = &model.config.rounding;
+                    let rule = (rounding.apply == RoundingApply::Save).then(|| {
+                        rounding
+                            .rule_for(edit_state.contact_id.as_deref(), &edit_state.contact_name)
+                    });
+                    match edit_state.try_into_time_entry(&admin_timezone_str, rule) {
+                        Ok(data) => {
+                            prepared_data = Some((
+                                data,
+                                edit_state.edit_type == EditType::Create,
+                                edit_state.time_entry_id.clone(),
+                                edit_state.description.clone(),
+                            ));
+                        }
+                        Err(err) => validation_error = Some(err),
+                    }
                 }
             } // Mutable borrow of edit_state (and model) ends here
 
             // --- Handle validation failure outside the borrow scope ---
-            if validation_failed {
-                ui::show_error(model, t!("update_description_required"));
+            if let Some(err) = validation_error {
+                model.log_warning(err);
                 return None;
             }
 
             // --- Proceed only if data was prepared successfully ---
             if let Some((time_entry_data, is_creating, entry_id_opt, description)) = prepared_data {
+                // Submitted weeks stay as they are, also the week an update moves the entry from
+                let original = entry_id_opt
+                    .as_ref()
+                    .and_then(|id| {
+                        model
+                            .time_entries
+                            .iter()
+                            .find(|entry| entry.id.as_ref() == Some(id))
+                    })
+                    .cloned();
+                let saved = TimeEntry {
+                    user_id: original
+                        .as_ref()
+                        .and_then(|entry| entry.user_id.clone())
+                        .or_else(|| model.config.user_id.clone()),
+                    ..time_entry_data.clone()
+                };
+                let mut keys = vec![model.week_key_for_entry(&saved)];
+                keys.extend(original.iter().map(|entry| model.week_key_for_entry(entry)));
+                for key in &keys {
+                    if refuse_if_week_locked(model, key) {
+                        return None;
+                    }
+                }
+
                 // Get immutable borrows or clones needed for API call
                 let admin_id = model.administration.id.clone().unwrap_or_default();
                 let client = model.client.clone();
                 let user_id = model.config.user_id.clone().unwrap_or_default();
 
-                if is_creating || was_import {
+                // An import of an already imported entry updates the existing entry
+                if is_creating || (was_import && entry_id_opt.is_none()) {
                     // Log using immutable borrow of model
                     model.log_notice(format!("Creating new time entry: {}", description));
                     let endpoint = "time_entries.json";
@@ -929,7 +2901,18 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                     )
                     .await
                     {
-                        Ok(_created_entry) => {
+                        Ok(created_entry) => {
+                            if was_import {
+                                link_imported_entry(model, created_entry.id.as_deref());
+                                if let Some(moneybird_id) = &created_entry.id {
+                                    notify_plugins(model, TimeEntryEvent::Imported, moneybird_id);
+                                }
+                            }
+                            model.undo_stack.record(if was_import {
+                                Change::Imported(created_entry)
+                            } else {
+                                Change::Created(created_entry)
+                            });
                             let success_msg = if was_import {
                                 t!("update_import_success")
                             } else {
@@ -959,6 +2942,7 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                         entry_id = entry_id.clone(),
                         description = description
                     ));
+                    let original_entry = fetch_original_entry(model, &entry_id).await;
                     let endpoint = format!("time_entries/{}.json", entry_id);
                     crate::api::log_debug_curl(model, &endpoint, "PATCH");
 
@@ -970,7 +2954,17 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                     )
                     .await
                     {
-                        Ok(_updated_entry) => {
+                        Ok(updated_entry) => {
+                            if was_import {
+                                link_imported_entry(model, updated_entry.id.as_deref());
+                            }
+                            notify_plugins(model, TimeEntryEvent::Updated, &entry_id);
+                            if let Some(before) = original_entry {
+                                model.undo_stack.record(Change::Updated {
+                                    before,
+                                    after: updated_entry,
+                                });
+                            }
                             model.log_success(t!(
                                 "time_entry_updated_successfully",
                                 id = entry_id.clone()
@@ -996,6 +2990,9 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
 
                 // --- Reset State (only on success) ---
                 if next_message.is_some() && (was_import || is_creating) {
+                    if was_import {
+                        offer_learned_mapping(model);
+                    }
                     model.edit_state = EditState::default();
                 }
             }
@@ -1013,84 +3010,45 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             }
         }
         Message::EditTimeEntryNextField => {
+            let now = admin_now(model);
             if let Some(edit_state) = get_active_edit_state_mut(model) {
-                let field_before_move = edit_state.selected_field;
-                update_edit_field_from_editor(edit_state);
-
-                // Format the time field if focus is leaving StartTime or EndTime
-                match field_before_move {
-                    EditField::StartTime => {
-                        edit_state.start_time = format_time_input(&edit_state.start_time);
-                    }
-                    EditField::EndTime => {
-                        edit_state.end_time = format_time_input(&edit_state.end_time);
-                    }
-                    _ => {}
-                }
-
-                const FIELD_ORDER: &[crate::model::EditField] = &[
-                    crate::model::EditField::Description,
-                    crate::model::EditField::Contact,
-                    crate::model::EditField::Project,
-                    crate::model::EditField::StartTime,
-                    crate::model::EditField::EndTime,
-                    crate::model::EditField::StartDate,
-                    crate::model::EditField::EndDate,
-                ];
-                if let Some(current_index) = FIELD_ORDER
+                leave_edit_field(edit_state, now);
+                if let Some(current_index) = EDIT_FIELD_ORDER
                     .iter()
                     .position(|&field| field == edit_state.selected_field)
                 {
-                    let next_index = (current_index + 1) % FIELD_ORDER.len();
-                    edit_state.selected_field = FIELD_ORDER[next_index];
+                    let next_index = (current_index + 1) % EDIT_FIELD_ORDER.len();
+                    edit_state.selected_field = EDIT_FIELD_ORDER[next_index];
                     initialize_editor_or_autocomplete(edit_state);
                 }
             }
+            model.validate_edit();
             None
         }
         Message::EditTimeEntryPreviousField => {
+            let now = admin_now(model);
             if let Some(edit_state) = get_active_edit_state_mut(model) {
-                let field_before_move = edit_state.selected_field;
-                update_edit_field_from_editor(edit_state);
-
-                // Format the time field if focus is leaving StartTime or EndTime
-                match field_before_move {
-                    EditField::StartTime => {
-                        edit_state.start_time = format_time_input(&edit_state.start_time);
-                    }
-                    EditField::EndTime => {
-                        edit_state.end_time = format_time_input(&edit_state.end_time);
-                    }
-                    _ => {}
-                }
-
-                const FIELD_ORDER: &[crate::model::EditField] = &[
-                    crate::model::EditField::Description,
-                    crate::model::EditField::Contact,
-                    crate::model::EditField::Project,
-                    crate::model::EditField::StartTime,
-                    crate::model::EditField::EndTime,
-                    crate::model::EditField::StartDate,
-                    crate::model::EditField::EndDate,
-                ];
-                if let Some(current_index) = FIELD_ORDER
+                leave_edit_field(edit_state, now);
+                if let Some(current_index) = EDIT_FIELD_ORDER
                     .iter()
                     .position(|&field| field == edit_state.selected_field)
                 {
                     let prev_index = if current_index == 0 {
-                        FIELD_ORDER.len() - 1
+                        EDIT_FIELD_ORDER.len() - 1
                     } else {
                         current_index - 1
                     };
-                    edit_state.selected_field = FIELD_ORDER[prev_index];
+                    edit_state.selected_field = EDIT_FIELD_ORDER[prev_index];
                     initialize_editor_or_autocomplete(edit_state);
                 }
             }
+            model.validate_edit();
             None
         }
         Message::EditTimeEntryFieldClick(field) => {
+            let now = admin_now(model);
             if let Some(edit_state) = get_active_edit_state_mut(model) {
-                update_edit_field_from_editor(edit_state);
+                leave_edit_field(edit_state, now);
                 edit_state.selected_field = field;
                 initialize_editor_or_autocomplete(edit_state);
                 model.log_debug(t!(
@@ -1098,6 +3056,17 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                     field = format!("{:?}", field)
                 ));
             }
+            model.validate_edit();
+            None
+        }
+        Message::EditTimeEntryStepTime(steps) => {
+            let now = admin_now(model);
+            if let Some(edit_state) = get_active_edit_state_mut(model) {
+                update_edit_field_from_editor(edit_state);
+                step_edit_time(edit_state, steps, now);
+                initialize_editor_or_autocomplete(edit_state);
+            }
+            model.validate_edit();
             None
         }
         Message::EditTimeEntryKeyPress(key) => {
@@ -1108,8 +3077,12 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                 ) {
                     edit_state.editor.input(key);
                     update_edit_field_from_editor(edit_state);
+                    if edit_state.selected_field == EditField::Description {
+                        sync_description_autocomplete(edit_state);
+                    }
                 }
             }
+            model.validate_edit();
             None
         }
         Message::EditTimeEntrySelectProject => {
@@ -1157,10 +3130,14 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                         crate::model::EditField::Contact => {
                             handle_autocomplete_select_contact(model)
                         }
+                        crate::model::EditField::Description => {
+                            handle_autocomplete_select_description(model)
+                        }
                         _ => {}
                     }
                 }
             }
+            model.validate_edit();
             None
         }
         Message::AutocompleteRefresh => {
@@ -1175,6 +3152,9 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                         crate::model::EditField::Contact => {
                             return handle_autocomplete_refresh_contact(model).await
                         }
+                        crate::model::EditField::Description => {
+                            return handle_autocomplete_refresh_description(model).await
+                        }
                         _ => {}
                     }
                 }
@@ -1190,6 +3170,10 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                     crate::model::EditField::Contact => {
                         handle_autocomplete_navigation(&mut edit_state.contact_autocomplete, true)
                     }
+                    crate::model::EditField::Description => handle_autocomplete_navigation(
+                        &mut edit_state.description_autocomplete,
+                        true,
+                    ),
                     _ => {}
                 }
             }
@@ -1204,6 +3188,10 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
                     crate::model::EditField::Contact => {
                         handle_autocomplete_navigation(&mut edit_state.contact_autocomplete, false)
                     }
+                    crate::model::EditField::Description => handle_autocomplete_navigation(
+                        &mut edit_state.description_autocomplete,
+                        false,
+                    ),
                     _ => {}
                 }
             }
@@ -1231,6 +3219,12 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             }
             None
         }
+        Message::AutocompleteHide => {
+            if let Some(edit_state) = get_active_edit_state_mut(model) {
+                edit_state.description_autocomplete.is_dropdown_visible = false;
+            }
+            None
+        }
         Message::AutocompleteResultsProject(projects) => {
             // This message might need adjustment depending on whether it's for regular or import state
             // For now, assume it updates the regular edit state's autocomplete
@@ -1262,6 +3256,11 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             None
         }
 
+        Message::PluginNotifications(notifications) => {
+            handle_plugin_notifications(model, notifications);
+            None
+        }
+
         // --- Plugin View Handling ---
         Message::PluginViewShow => {
             model.plugin_view_state.active = true;
@@ -1353,17 +3352,168 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
 
         // --- Import Handling ---
         Message::ImportTimeEntry => {
-            if !model.edit_state.active && !is_import_active(model) {
-                initialize_time_entry_import(model).await
-            } else {
-                None
+            if model.edit_state.active || is_import_active(model) {
+                return None;
+            }
+            // Importing the same entry twice would count its time twice
+            let linked_id = model
+                .time_entry_table_state
+                .selected()
+                .filter(|_| !model.is_group_header_selected())
+                .and_then(|idx| model.time_entries_for_table.get(idx))
+                .and_then(|entry| entry.linked_id.clone());
+            match linked_id {
+                Some(moneybird_id) => {
+                    ui::show_choice(
+                        model,
+                        t!("import_linked_title"),
+                        t!("import_linked_prompt"),
+                        (
+                            t!("import_update_existing"),
+                            Message::ExecuteImportTimeEntry(Some(moneybird_id)),
+                        ),
+                        (t!("import_as_new"), Message::ExecuteImportTimeEntry(None)),
+                    );
+                    None
+                }
+                None => initialize_time_entry_import(model, None).await,
+            }
+        }
+        Message::ExecuteImportTimeEntry(existing_id) => {
+            initialize_time_entry_import(model, existing_id).await
+        }
+        Message::ExecuteLearnMappings(rules) => {
+            for rule in rules {
+                model.config.learn_mapping(rule);
+            }
+            if save_config_or_show_error(model) {
+                model.log_success(t!("mapping_learned"));
+            }
+            None
+        }
+
+        // --- Batch Import ---
+        Message::BatchImportShow => show_batch_import(model).await,
+        Message::BatchImportHide => {
+            let imported = model
+                .batch_import_state
+                .items
+                .iter()
+                .any(|item| matches!(item.result, Some(Ok(_))));
+            model.batch_import_state = Default::default();
+            imported.then_some(Message::TimeEntryRefresh)
+        }
+        Message::BatchImportSelectNext => {
+            let state = &mut model.batch_import_state;
+            if let Some(next_index) =
+                calculate_next_index(state.table_state.selected(), state.items.len())
+            {
+                state.table_state.select(Some(next_index));
+            }
+            None
+        }
+        Message::BatchImportSelectPrevious => {
+            let state = &mut model.batch_import_state;
+            if let Some(prev_index) =
+                calculate_previous_index(state.table_state.selected(), state.items.len())
+            {
+                state.table_state.select(Some(prev_index));
+            }
+            None
+        }
+        Message::BatchImportToggleCheck => {
+            let state = &mut model.batch_import_state;
+            if let Some(item) = state
+                .table_state
+                .selected()
+                .and_then(|index| state.items.get_mut(index))
+            {
+                item.checked = !item.checked;
+            }
+            None
+        }
+        Message::BatchImportToggleAll => {
+            let items = &mut model.batch_import_state.items;
+            let check = items.iter().any(|item| !item.checked);
+            for item in items.iter_mut() {
+                item.checked = check;
+            }
+            None
+        }
+        Message::BatchImportEdit(target) => {
+            edit_batch_import_item(model, target);
+            // The projects are cached, so they are filtered right away
+            (target == BatchImportTarget::Project).then_some(Message::BatchImportSearchCandidates)
+        }
+        Message::BatchImportInputKeyPress(key) => {
+            let state = &mut model.batch_import_state;
+            match state.editing {
+                Some(BatchImportTarget::Contact) => {
+                    // The contacts are searched once typing pauses
+                    handle_autocomplete_keypress(&mut state.contact_autocomplete, key.code);
+                    None
+                }
+                Some(BatchImportTarget::Project) => {
+                    handle_autocomplete_keypress(&mut state.project_autocomplete, key.code);
+                    Some(Message::BatchImportSearchCandidates)
+                }
+                None => None,
             }
         }
+        Message::BatchImportSearchCandidates => {
+            refresh_batch_import_candidates(model).await;
+            None
+        }
+        Message::BatchImportCandidateNext => {
+            navigate_batch_import_candidates(model, true);
+            None
+        }
+        Message::BatchImportCandidatePrevious => {
+            navigate_batch_import_candidates(model, false);
+            None
+        }
+        Message::BatchImportApplyCandidate => {
+            apply_batch_import_candidate(model);
+            None
+        }
+        Message::BatchImportEditCancel => {
+            model.batch_import_state.editing = None;
+            None
+        }
+        Message::BatchImportMerge => {
+            merge_batch_import_fragments(model);
+            None
+        }
+        Message::BatchImportConfirm => {
+            confirm_batch_import(model);
+            None
+        }
+        Message::ExecuteBatchImport => execute_batch_import(model).await,
 
         // --- Modal Handling ---
-        Message::ConfirmModal(modal_id) => handle_modal_close(model, modal_id, true),
+        Message::ConfirmModal(modal_id) => {
+            handle_modal_close(model, modal_id, |modal| modal.on_confirm.clone())
+        }
         Message::DismissModal(modal_id, is_cancel) => {
-            handle_modal_close(model, modal_id, !is_cancel)
+            handle_modal_close(model, modal_id, |modal| {
+                if is_cancel {
+                    modal.on_cancel.clone()
+                } else {
+                    modal.on_confirm.clone()
+                }
+            })
+        }
+        Message::AlternativeModal(modal_id) => {
+            // Only modals that offer an alternative close on it
+            if model
+                .modal_stack
+                .top()
+                .is_some_and(|modal| modal.on_alternative.is_some())
+            {
+                handle_modal_close(model, modal_id, |modal| modal.on_alternative.clone())
+            } else {
+                None
+            }
         }
 
         // --- Execution Actions (triggered by modals) ---
@@ -1371,17 +3521,110 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             handle_export(model);
             None
         }
+        Message::ExecuteDeleteSavedSearch(name) => {
+            model
+                .config
+                .saved_searches
+                .retain(|search| search.name != name);
+            if model.config.default_search.as_deref() == Some(name.as_str()) {
+                model.config.default_search = None;
+            }
+            if save_config_or_show_error(model) {
+                model.log_success(t!("saved_search_deleted", name = name));
+            }
+
+            // Keep the selection on a valid row, or close the picker when nothing is left
+            let count = model.config.saved_searches.len();
+            if count == 0 {
+                model.saved_search_state.picker_active = false;
+            } else if model
+                .saved_search_state
+                .list_state
+                .selected()
+                .is_some_and(|idx| idx >= count)
+            {
+                model.saved_search_state.list_state.select(Some(count - 1));
+            }
+            None
+        }
+        Message::ExecuteSubmitWeek => {
+            let key = model.week_key(None);
+            match submit_week(model, &key) {
+                Ok(week_lock) => {
+                    model.log_success(t!(
+                        "update_week_submitted",
+                        week = week_lock.week,
+                        year = week_lock.year,
+                        snapshot = week_lock.snapshot.to_string_lossy(),
+                        checksum = week_lock.checksum.clone()
+                    ));
+                    ui::show_info(
+                        model,
+                        "submit_week_success",
+                        t!("success").to_string(),
+                        t!(
+                            "update_week_submitted",
+                            week = week_lock.week,
+                            year = week_lock.year,
+                            snapshot = week_lock.snapshot.to_string_lossy(),
+                            checksum = week_lock.checksum
+                        )
+                        .to_string(),
+                    );
+                }
+                Err(err) => {
+                    let error_msg = t!("update_failed_submit_week", error = err).to_string();
+                    model.log_error(error_msg.clone());
+                    ui::show_error(model, error_msg);
+                }
+            }
+            model.refresh_week_lock();
+            None
+        }
+        Message::ExecuteUnlockWeek => {
+            let key = model.week_key(None);
+            let removed = match &model.locks {
+                Some(locks) => locks.remove_lock(&key).map_err(|e| e.to_string()),
+                None => Err(t!("config_determine_dir_error").to_string()),
+            };
+            match removed {
+                Ok(()) => {
+                    let message =
+                        t!("update_week_unlocked", week = key.week, year = key.year).to_string();
+                    model.log_notice(message.clone());
+                    ui::show_info(
+                        model,
+                        "unlock_week_success",
+                        t!("success").to_string(),
+                        message,
+                    );
+                }
+                Err(err) => {
+                    let error_msg = t!("update_failed_unlock_week", error = err).to_string();
+                    model.log_error(error_msg.clone());
+                    ui::show_error(model, error_msg);
+                }
+            }
+            model.refresh_week_lock();
+            None
+        }
         Message::ExecuteDeleteTimeEntry(entry_id) => {
             model.log_notice(t!(
                 "update_deleting_time_entry",
                 entry_id = entry_id.clone()
             ));
+            let original_entry = fetch_original_entry(model, &entry_id).await;
             let admin_id = model.administration.id.clone().unwrap_or_default();
             let client = model.client.clone();
             let delete_result =
                 crate::api::delete_time_entry_by_id(&client, &admin_id, &entry_id).await;
             match delete_result {
                 Ok(_) => {
+                    notify_plugins(model, TimeEntryEvent::Deleted, &entry_id);
+                    unlink_deleted_entry(model, &entry_id);
+                    if let Some(entry) = original_entry {
+                        model.undo_stack.record(Change::Deleted(entry));
+                    }
                     model.log_success(t!("update_time_entry_deleted", entry_id = entry_id.clone()));
                     ui::show_info(
                         model,
@@ -1401,8 +3644,72 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             }
             None
         }
+        Message::TimeEntryMergeFragments => {
+            if model.is_group_header_selected() {
+                return None;
+            }
+            let entry = model
+                .time_entry_table_state
+                .selected()
+                .and_then(|idx| model.time_entries_for_table.get(idx))
+                .cloned()?;
+            if entry.plugin_name.is_none() && entry.source == "moneybird" {
+                ui::show_error(model, t!("merge_only_plugin_entries"));
+                return None;
+            }
+            match model.merge_plugin_fragments(&entry.id) {
+                0 => show_nothing_to_merge(model),
+                count => model.log_success(t!("merge_done", count = count)),
+            }
+            None
+        }
+        Message::TimeEntryFixConflict => {
+            show_fix_conflict(model);
+            None
+        }
+        Message::ExecuteTrimOverlap(first_id, second_id) => {
+            trim_overlap(model, &first_id, &second_id).await
+        }
+        Message::ExecuteMergeOverlap(first_id, second_id) => {
+            merge_overlap(model, &first_id, &second_id).await
+        }
+        Message::TimeEntryUndo => undo_or_redo(model, true).await,
+        Message::TimeEntryRedo => undo_or_redo(model, false).await,
+        Message::UserSwitcherShow => {
+            // Users are fetched at startup, but try again if that failed
+            if model.users.is_empty() {
+                let admin_id = model.administration.id.clone().unwrap_or_default();
+                match api::get_all_users(&model.client, &admin_id).await {
+                    Ok(users) => model.users = users,
+                    Err(err) => {
+                        let error_msg = t!("error_fetch_users", error = err.to_string());
+                        model.log_error(error_msg.clone());
+                        ui::show_error(model, error_msg);
+                        return None;
+                    }
+                }
+            }
+
+            // The first row is the whole team, followed by the individual users
+            let selected_index = match &model.time_entry_filter.user_id {
+                Some(user_id) => model
+                    .users
+                    .iter()
+                    .position(|user| user.id.as_ref() == Some(user_id))
+                    .map(|index| index + 1)
+                    .unwrap_or(0),
+                None => 0,
+            };
+            model.user_selection_state.select(Some(selected_index));
+            model.user_switcher_active = true;
+            None
+        }
+        Message::UserSwitcherHide => {
+            model.user_switcher_active = false;
+            None
+        }
         Message::UserSelectNext => {
-            let count = model.users.len();
+            let count = model.users.len() + usize::from(model.user_switcher_active);
             if let Some(next_index) =
                 calculate_next_index(model.user_selection_state.selected(), count)
             {
@@ -1411,7 +3718,7 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             None
         }
         Message::UserSelectPrevious => {
-            let count = model.users.len();
+            let count = model.users.len() + usize::from(model.user_switcher_active);
             if let Some(prev_index) =
                 calculate_previous_index(model.user_selection_state.selected(), count)
             {
@@ -1419,6 +3726,20 @@ pub(crate) async fn update(model: &mut AppModel, msg: Message) -> Option<Message
             }
             None
         }
+        Message::UserConfirmSelection if model.user_switcher_active => {
+            let selected_index = model.user_selection_state.selected().unwrap_or(0);
+            model.user_switcher_active = false;
+
+            if selected_index == 0 {
+                model.time_entry_filter.user_id = None;
+                model.log_notice(t!("update_filter_whole_team"));
+            } else if let Some(user) = model.users.get(selected_index - 1) {
+                let user_name = ui::get_user_name(&model.users, user.id.as_ref());
+                model.time_entry_filter.user_id = user.id.clone();
+                model.log_notice(t!("update_filter_user_set", user = user_name));
+            }
+            Some(Message::TimeEntryRefresh)
+        }
         Message::UserConfirmSelection => {
             let mut selected_user_id: Option<String> = None;
             let mut log_no_id_error = false;
@@ -1759,3 +4080,179 @@ fn toggle_plugin_config_enabled(config_path: &std::path::Path) -> Result<bool, S
 
     Ok(new_enabled_state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin_entry(id: &str, description: &str, start: &str, end: &str) -> TimeEntryForTable {
+        TimeEntryForTable {
+            id: id.to_string(),
+            customer: "Acme".to_string(),
+            project: "Website".to_string(),
+            description: description.to_string(),
+            started_at: format!("2025-04-07T{}:00Z", start),
+            ended_at: format!("2025-04-07T{}:00Z", end),
+            source: "gitlab".to_string(),
+            plugin_name: Some("gitlab".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn batch_item(entry: TimeEntryForTable) -> BatchImportItem {
+        BatchImportItem {
+            entry,
+            checked: true,
+            contact: Some(Contact {
+                id: Some("1".to_string()),
+                company_name: Some("Acme BV".to_string()),
+                ..Default::default()
+            }),
+            project: Some(Project {
+                id: Some("2".to_string()),
+                name: Some("Website 2025".to_string()),
+                ..Default::default()
+            }),
+            result: None,
+        }
+    }
+
+    fn moneybird_entry(start: &str, end: &str) -> TimeEntryForTable {
+        TimeEntryForTable {
+            id: "10".to_string(),
+            started_at: format!("2025-04-07T{}:00Z", start),
+            ended_at: format!("2025-04-07T{}:00Z", end),
+            source: "moneybird".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_import_time_entry() {
+        let model = AppModel::default();
+        let item = batch_item(plugin_entry("1", "Fix login", "09:00", "10:30"));
+
+        let time_entry = batch_import_time_entry(&model, &item, &[], "UTC").unwrap();
+        assert_eq!(time_entry.description.as_deref(), Some("Fix login"));
+        assert_eq!(time_entry.contact_id.as_deref(), Some("1"));
+        assert_eq!(time_entry.project_id.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_batch_import_time_entry_validation() {
+        let mut model = AppModel::default();
+
+        let item = batch_item(plugin_entry("1", " ", "09:00", "10:00"));
+        let error = batch_import_time_entry(&model, &item, &[], "UTC").unwrap_err();
+        assert_eq!(error, t!("edit_description_required"));
+
+        let item = batch_item(plugin_entry("1", "Fix login", "10:00", "09:00"));
+        let error = batch_import_time_entry(&model, &item, &[], "UTC").unwrap_err();
+        assert_eq!(error, t!("edit_end_before_start"));
+
+        let item = batch_item(plugin_entry("1", "Fix login", "00:00", "13:00"));
+        let error = batch_import_time_entry(&model, &item, &[], "UTC").unwrap_err();
+        assert_eq!(error, t!("edit_too_long", hours = 12.0));
+
+        let item = batch_item(plugin_entry("1", "Fix login", "09:00", "10:00"));
+        let entries = [moneybird_entry("09:30", "11:00")];
+        let error = batch_import_time_entry(&model, &item, &entries, "UTC").unwrap_err();
+        assert_eq!(error, t!("edit_overlaps", start = "09:30", end = "11:00"));
+
+        model.config.validation.require_project = true;
+        let item = BatchImportItem {
+            project: None,
+            ..batch_item(plugin_entry("1", "Fix login", "09:00", "10:00"))
+        };
+        let error = batch_import_time_entry(&model, &item, &[], "UTC").unwrap_err();
+        assert_eq!(error, t!("edit_project_required"));
+    }
+
+    #[test]
+    fn test_apply_batch_import_candidate() {
+        let mut model = AppModel::default();
+        let state = &mut model.batch_import_state;
+        state.items = vec![
+            batch_item(plugin_entry("1", "Fix login", "09:00", "10:00")),
+            batch_item(plugin_entry("2", "Fix logout", "10:00", "11:00")),
+            BatchImportItem {
+                entry: TimeEntryForTable {
+                    project: "Other".to_string(),
+                    ..plugin_entry("3", "Support", "11:00", "12:00")
+                },
+                ..batch_item(plugin_entry("3", "Support", "11:00", "12:00"))
+            },
+        ];
+        state.table_state.select(Some(0));
+        state.editing = Some(BatchImportTarget::Project);
+        let picked = Project {
+            id: Some("3".to_string()),
+            name: Some("Website 2026".to_string()),
+            ..Default::default()
+        };
+        state.project_autocomplete.update_items(vec![picked]);
+
+        apply_batch_import_candidate(&mut model);
+        let state = &model.batch_import_state;
+        let project_ids: Vec<Option<&str>> = state
+            .items
+            .iter()
+            .map(|item| item.project.as_ref().and_then(|p| p.id.as_deref()))
+            .collect();
+        assert_eq!(project_ids, vec![Some("3"), Some("3"), Some("2")]);
+        assert_eq!(state.editing, None);
+
+        // The rule waits for the end of the import instead of being saved right away
+        assert!(model.config.mappings.is_empty());
+        assert_eq!(state.learned.len(), 1);
+        let (line, rule) = &state.learned[0];
+        assert_eq!(line, "Website → Website 2026");
+        assert!(rule.matches("gitlab", "Anyone", "Website"));
+        assert_eq!(rule.project_id.as_deref(), Some("3"));
+
+        // Picking again for the same plugin project replaces the rule
+        model.batch_import_state.editing = Some(BatchImportTarget::Project);
+        apply_batch_import_candidate(&mut model);
+        assert_eq!(model.batch_import_state.learned.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_import() {
+        let mut model = AppModel::default();
+        let learned = config::MappingRule {
+            project_id: Some("3".to_string()),
+            ..config::MappingRule::exact("gitlab", "", "Website")
+        };
+        let state = &mut model.batch_import_state;
+        state.items = vec![
+            batch_item(plugin_entry("1", "", "09:00", "10:00")),
+            BatchImportItem {
+                checked: false,
+                ..batch_item(plugin_entry("2", "Fix logout", "10:00", "11:00"))
+            },
+        ];
+        state.learned = vec![("Website → Website 2026".to_string(), learned.clone())];
+
+        // Only the checked entry is tried, and it fails before reaching MoneyBird
+        assert!(execute_batch_import(&mut model).await.is_none());
+        let items = &model.batch_import_state.items;
+        assert_eq!(
+            items[0].result,
+            Some(Err(t!("edit_description_required").to_string()))
+        );
+        assert!(items[1].result.is_none());
+        assert!(model.batch_import_state.learned.is_empty());
+
+        // The summary comes first, the learned rules are offered after it
+        let summary = model.modal_stack.pop().unwrap();
+        assert_eq!(summary.id.as_deref(), Some("batch_import_done"));
+        assert_eq!(
+            summary.message,
+            t!("batch_import_summary", imported = 0, total = 1)
+        );
+        let offer = model.modal_stack.pop().unwrap();
+        assert!(offer.message.contains("Website → Website 2026"));
+        assert!(offer.on_confirm == Some(Message::ExecuteLearnMappings(vec![learned])));
+        assert!(model.modal_stack.is_empty());
+    }
+}